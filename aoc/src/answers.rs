@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use aoc_common::examples::answer_from_toml;
+use aoc_common::{Answer, Part};
+
+/// `answers.toml` at the root of the workspace.
+pub const ANSWERS_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.toml");
+
+/// The known-correct answers for the real inputs (`input.txt`), one table per day:
+///
+/// ```toml
+/// [d5]
+/// part_1 = "RNZLFZSJH"
+/// part_2 = "CNSFCGJSM"
+/// ```
+pub struct Answers(HashMap<(String, Part), Answer>);
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))?;
+        Self::parse(&text).map_err(|err| format!("{}: {err}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let table: toml::Table = text.parse().map_err(|err: toml::de::Error| err.message().to_string())?;
+        let mut answers = HashMap::new();
+        for (day, parts) in table {
+            let parts = parts.as_table().ok_or_else(|| format!("{day} should be a table"))?;
+            for (key, value) in parts {
+                let part = match key.as_str() {
+                    "part_1" => Part::One,
+                    "part_2" => Part::Two,
+                    _ => return Err(format!("{day}.{key}: expected part_1 or part_2")),
+                };
+                let answer = answer_from_toml(value)
+                    .ok_or_else(|| format!("{day}.{key} should be a number or a string"))?;
+                answers.insert((day.clone(), part), answer);
+            }
+        }
+        Ok(Answers(answers))
+    }
+
+    pub fn get(&self, day: &str, part: Part) -> Option<&Answer> {
+        self.0.get(&(String::from(day), part))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("[d1]\npart_1 = 24000\n\n[d10]\npart_2 = '''\n##  \n#'''\n").unwrap();
+        assert_eq!(answers.get("d1", Part::One), Some(&Answer::Number(24000)));
+        assert_eq!(answers.get("d1", Part::Two), None);
+        assert_eq!(answers.get("d10", Part::Two), Some(&Answer::Text(String::from("##  \n#"))));
+        assert!(Answers::parse("[d1]\npart_3 = 1\n").is_err());
+        assert!(Answers::parse("[d1]\npart_1 = 1.5\n").is_err())
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+use std::time::Duration;
+
+use aoc_common::args::{Format, RunArgs, UsageError};
+use aoc_common::input::InputSource;
+use aoc_common::runner::{read_input, solve_timed, start_logging, start_pool};
+use aoc_common::{Day, Part};
+
+use crate::days::select_days;
+
+/// `bench_baseline.toml` at the root of the workspace. The timings only make sense on the
+/// machine they were taken on, so the file isn't checked in.
+pub const BASELINE_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../bench_baseline.toml");
+
+pub const BENCH_USAGE: &str =
+    "[<day|all>] [--part 1|2] [--warmup N] [--samples N] [--threshold PERCENT] [--save]";
+
+struct BenchArgs {
+    run_args: RunArgs,
+    /// Runs before the timed ones, to warm up caches and the allocator
+    warmup: usize,
+    samples: usize,
+    /// How many percent slower than the baseline a phase may get before it is flagged
+    threshold: f64,
+    /// Store the medians as the new baseline instead of comparing with it
+    save: bool,
+}
+
+impl BenchArgs {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, UsageError> {
+        let mut bench_args = BenchArgs { run_args: RunArgs::parse([])?, warmup: 1, samples: 5, threshold: 10.0, save: false };
+        let mut run_args = vec![];
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--warmup" => bench_args.warmup = flag_value(&arg, args.next())?,
+                "--samples" => bench_args.samples = flag_value(&arg, args.next())?,
+                "--threshold" => bench_args.threshold = flag_value(&arg, args.next())?,
+                "--save" => bench_args.save = true,
+                _ => run_args.push(arg),
+            }
+        }
+        bench_args.run_args = RunArgs::parse(run_args)?;
+        if bench_args.run_args.input != InputSource::Bundled {
+            return Err(UsageError(String::from("bench always uses the real input.txt")));
+        }
+        if bench_args.run_args.format != Format::Text {
+            return Err(UsageError(String::from("bench only prints a table")));
+        }
+        if bench_args.samples == 0 {
+            return Err(UsageError(String::from("--samples must be at least 1")));
+        }
+        Ok(bench_args)
+    }
+}
+
+pub(crate) fn flag_value<T: FromStr>(flag: &str, value: Option<String>) -> Result<T, UsageError> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| UsageError(format!("{flag} needs a number")))
+}
+
+/// Median times from an earlier run, by day and phase (`parse`, `part_1`, `part_2`).
+#[derive(Debug, Default, PartialEq)]
+struct Baseline(BTreeMap<String, BTreeMap<String, Duration>>);
+
+impl Baseline {
+    /// No file yet is the same as an empty baseline.
+    fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|err| format!("{}: {err}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Baseline::default()),
+            Err(err) => Err(format!("{}: {err}", path.display())),
+        }
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let table: toml::Table = text.parse().map_err(|err: toml::de::Error| err.message().to_string())?;
+        let mut baseline = Baseline::default();
+        for (day, phases) in table {
+            let phases = phases.as_table().ok_or_else(|| format!("{day} should be a table"))?;
+            for (phase, seconds) in phases {
+                let seconds = seconds
+                    .as_float()
+                    .filter(|seconds| *seconds >= 0.0)
+                    .ok_or_else(|| format!("{day}.{phase} should be a number of seconds"))?;
+                baseline.set(&day, phase, Duration::from_secs_f64(seconds));
+            }
+        }
+        Ok(baseline)
+    }
+
+    fn get(&self, day: &str, phase: &str) -> Option<Duration> {
+        self.0.get(day)?.get(phase).copied()
+    }
+
+    fn set(&mut self, day: &str, phase: &str, time: Duration) {
+        self.0.entry(String::from(day)).or_default().insert(String::from(phase), time);
+    }
+
+    fn to_toml(&self) -> String {
+        let table: toml::Table = self
+            .0
+            .iter()
+            .map(|(day, phases)| {
+                let phases: toml::Table = phases
+                    .iter()
+                    .map(|(phase, time)| (phase.clone(), toml::Value::Float(time.as_secs_f64())))
+                    .collect();
+                (day.clone(), toml::Value::Table(phases))
+            })
+            .collect();
+        format!("# Median times in seconds, written by `aoc bench --save`\n\n{table}")
+    }
+}
+
+fn phase_name(part: Part) -> &'static str {
+    match part {
+        Part::One => "part_1",
+        Part::Two => "part_2",
+    }
+}
+
+fn median(times: &[Duration]) -> Duration {
+    let mut times = times.to_vec();
+    times.sort();
+    let mid = times.len() / 2;
+    if times.len().is_multiple_of(2) {
+        (times[mid - 1] + times[mid]) / 2
+    } else {
+        times[mid]
+    }
+}
+
+/// How many percent slower (or, if negative, faster) `time` is than `baseline`.
+fn change(time: Duration, baseline: Duration) -> f64 {
+    (time.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0
+}
+
+/// `aoc bench [<day|all>] [--part N] [--warmup N] [--samples N] [--threshold PERCENT] [--save]`:
+/// time parsing and each part separately and compare the medians with the saved baseline.
+pub fn bench(args: Vec<String>) -> Result<(), UsageError> {
+    let mut args = args.into_iter().peekable();
+    let name = args.next_if(|arg| !arg.starts_with('-')).unwrap_or_else(|| String::from("all"));
+    let days = select_days(&name).ok_or_else(|| UsageError(format!("No such day: {name}")))?;
+    let args = BenchArgs::parse(args)?;
+    start_logging(&args.run_args);
+    start_pool(&args.run_args);
+    let baseline_path = Path::new(BASELINE_FILE);
+    let mut baseline = Baseline::load(baseline_path).unwrap_or_else(|err| {
+        eprintln!("Could not load the baseline: {err}");
+        process::exit(1)
+    });
+
+    print_row(["Day", "Phase", "Median", "Min", "Baseline", "Change", ""]);
+    let mut n_slower = 0;
+    for day in days {
+        for (phase, times) in time_day(day, &args) {
+            let time = median(&times);
+            let min = times.iter().min().unwrap();
+            let previous = baseline.get(day.name, phase);
+            let (change, flag) = match previous {
+                Some(previous) if !args.save => {
+                    let change = change(time, previous);
+                    let slower = change > args.threshold;
+                    n_slower += usize::from(slower);
+                    (format!("{change:+.1}%"), if slower { "SLOWER" } else { "" })
+                }
+                _ => (String::new(), ""),
+            };
+            let previous = previous.map_or_else(String::new, |previous| format!("{previous:.1?}"));
+            print_row([day.name, phase, &format!("{time:.1?}"), &format!("{min:.1?}"), &previous, &change, flag]);
+            if args.save {
+                baseline.set(day.name, phase, time);
+            }
+        }
+        // Saved after every day since benchmarking all of them takes a while
+        if args.save {
+            std::fs::write(baseline_path, baseline.to_toml()).unwrap_or_else(|err| {
+                eprintln!("Could not save the baseline to {}: {err}", baseline_path.display());
+                process::exit(1)
+            });
+        }
+    }
+    if n_slower > 0 {
+        println!("\n{n_slower} phases got more than {}% slower", args.threshold);
+        process::exit(1)
+    }
+    Ok(())
+}
+
+// The sampled times of parsing and each part
+fn time_day(day: &Day, args: &BenchArgs) -> Vec<(&'static str, Vec<Duration>)> {
+    let input = read_input(day, &args.run_args);
+    for _ in 0..args.warmup {
+        solve_timed(day, &input, &args.run_args);
+    }
+    let mut phases: Vec<(&'static str, Vec<Duration>)> = vec![("parse", vec![])];
+    phases.extend(args.run_args.parts().into_iter().map(|part| (phase_name(part), vec![])));
+    for _ in 0..args.samples {
+        let solved = solve_timed(day, &input, &args.run_args);
+        phases[0].1.push(solved.parse_time);
+        for (ind, (_, _, time)) in solved.answers.into_iter().enumerate() {
+            phases[ind + 1].1.push(time)
+        }
+    }
+    phases
+}
+
+fn print_row([day, phase, median, min, baseline, change, flag]: [&str; 7]) {
+    let line = format!("{day:8}  {phase:6}  {median:>10}  {min:>10}  {baseline:>10}  {change:>8}  {flag}");
+    println!("{}", line.trim_end());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median() {
+        let ms = Duration::from_millis;
+        assert_eq!(median(&[ms(3), ms(1), ms(2)]), ms(2));
+        assert_eq!(median(&[ms(4), ms(1), ms(2), ms(3)]), Duration::from_micros(2500))
+    }
+
+    #[test]
+    fn test_change() {
+        let ms = Duration::from_millis;
+        assert!((change(ms(150), ms(100)) - 50.0).abs() < 1e-9);
+        assert!((change(ms(50), ms(100)) + 50.0).abs() < 1e-9)
+    }
+
+    #[test]
+    fn test_baseline() {
+        let mut baseline = Baseline::default();
+        baseline.set("d1", "parse", Duration::from_micros(1500));
+        baseline.set("d1", "part_2", Duration::from_secs(2));
+        assert_eq!(Baseline::parse(&baseline.to_toml()), Ok(baseline));
+        assert!(Baseline::parse("[d1]\nparse = \"fast\"\n").is_err())
+    }
+
+    #[test]
+    fn test_bench_args() {
+        let args = |args: &[&str]| BenchArgs::parse(args.iter().map(|arg| arg.to_string()));
+        let bench_args = args(&["--samples", "3", "--part", "2", "--save"]).unwrap();
+        assert_eq!((bench_args.samples, bench_args.warmup, bench_args.save), (3, 1, true));
+        assert_eq!(bench_args.run_args.parts(), vec![Part::Two]);
+        assert!(args(&["--threshold"]).is_err());
+        assert!(args(&["--example"]).is_err())
+    }
+}
@@ -0,0 +1,106 @@
+use std::sync::mpsc;
+use std::thread;
+
+use aoc_common::Day;
+use rayon::prelude::*;
+
+pub const DAYS: [&Day; 26] = [
+    &d1::DAY,
+    &d2::DAY,
+    &d3::DAY,
+    &d4::DAY,
+    &d5::DAY,
+    &d6::DAY,
+    &d7::DAY,
+    &d8::DAY,
+    &d9::DAY,
+    &d10::DAY,
+    &d11::DAY,
+    &d12::DAY,
+    &d13::DAY,
+    &d14::DAY,
+    &d15::DAY,
+    &d16::DAY,
+    &d17::DAY,
+    &d18::DAY,
+    &d19::DAY,
+    &d19_bfs::DAY,
+    &d20::DAY,
+    &d21::DAY,
+    &d22::DAY,
+    &d23::DAY,
+    &d24::DAY,
+    &d25::DAY,
+];
+
+/// Looks a day up by crate name, with or without the `d`: `7`, `d7`, `19_bfs`.
+pub fn find_day(name: &str) -> Option<&'static Day> {
+    let name = name.strip_prefix('d').unwrap_or(name);
+    DAYS.into_iter().find(|day| day.name[1..] == *name)
+}
+
+/// `all` selects every day, anything else a single one.
+pub fn select_days(name: &str) -> Option<Vec<&'static Day>> {
+    if name == "all" {
+        Some(DAYS.to_vec())
+    } else {
+        find_day(name).map(|day| vec![day])
+    }
+}
+
+/// Runs `solve` for the days in parallel on the rayon pool, but hands the results to `done` in the
+/// order of `days`, each as soon as it and those before it are there.
+pub fn solve_in_order<T: Send>(
+    days: &[&'static Day],
+    solve: impl Fn(&'static Day) -> T + Sync,
+    mut done: impl FnMut(&'static Day, T),
+) {
+    let (senders, receivers): (Vec<_>, Vec<_>) = days.iter().map(|_| mpsc::channel()).unzip();
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            days.par_iter().zip(senders).for_each(|(day, sender)| {
+                // Only fails if the receiving end is gone, and then no one is waiting for it
+                let _ = sender.send(solve(day));
+            })
+        });
+        for (day, receiver) in days.iter().zip(receivers) {
+            // Nothing comes if `solve` panicked, and the panic is passed on at the end of the scope
+            if let Ok(result) = receiver.recv() {
+                done(day, result)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_day() {
+        assert_eq!(find_day("7").unwrap().name, "d7");
+        assert_eq!(find_day("d19").unwrap().name, "d19");
+        assert_eq!(find_day("19_bfs").unwrap().name, "d19_bfs");
+        assert!(find_day("26").is_none());
+        assert!(find_day("d").is_none());
+    }
+
+    #[test]
+    fn test_select_days() {
+        assert_eq!(select_days("all").unwrap().len(), DAYS.len());
+        assert_eq!(select_days("d3").unwrap().len(), 1);
+        assert!(select_days("x").is_none());
+    }
+
+    #[test]
+    fn test_solve_in_order() {
+        let mut names = vec![];
+        // The later days are done first
+        solve_in_order(
+            &DAYS,
+            |day| thread::sleep(std::time::Duration::from_millis(30 - day.number() as u64)),
+            |day, _| names.push(day.name),
+        );
+        assert_eq!(names, DAYS.map(|day| day.name))
+    }
+}
@@ -0,0 +1,78 @@
+use std::io::{self, Write};
+
+use aoc_common::args::UsageError;
+
+use crate::bench::flag_value;
+use crate::days::find_day;
+
+pub const GEN_USAGE: &str = "<day> [--size N] [--seed S]";
+
+#[derive(Debug, PartialEq)]
+struct GenArgs {
+    /// Lines, elves, monkeys or whatever the input of the day is made of, roughly
+    size: usize,
+    /// The same seed gives the same input
+    seed: u64,
+}
+
+impl GenArgs {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, UsageError> {
+        let mut gen_args = GenArgs { size: 100, seed: 0 };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--size" => gen_args.size = flag_value(&arg, args.next())?,
+                "--seed" => gen_args.seed = flag_value(&arg, args.next())?,
+                _ => return Err(UsageError(format!("Unknown argument: {arg}"))),
+            }
+        }
+        if gen_args.size == 0 {
+            return Err(UsageError(String::from("--size must be at least 1")));
+        }
+        Ok(gen_args)
+    }
+}
+
+/// `aoc gen <day> ...`: writes a random input for a day to stdout.
+pub fn generate(args: Vec<String>) -> Result<(), UsageError> {
+    let mut args = args.into_iter();
+    let name = args.next().ok_or_else(|| UsageError(String::from("Which day? Give a day number")))?;
+    let day = find_day(&name).ok_or_else(|| UsageError(format!("No such day: {name}")))?;
+    let args = GenArgs::parse(args)?;
+    let input = day.generate(args.size, args.seed).ok_or_else(|| UsageError(format!("{} has no generator", day.name)))?;
+    // Fine if whoever reads it has stopped, e.g. `head`
+    let _ = io::stdout().lock().write_all(input.as_bytes());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::DAYS;
+
+    fn args(args: &[&str]) -> Result<GenArgs, UsageError> {
+        GenArgs::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_gen_args() {
+        assert_eq!(args(&["--size", "7", "--seed", "42"]), Ok(GenArgs { size: 7, seed: 42 }));
+        assert_eq!(args(&[]), Ok(GenArgs { size: 100, seed: 0 }));
+        assert!(args(&["--size", "0"]).is_err());
+        assert!(args(&["--seed", "-1"]).is_err());
+        assert!(args(&["--example"]).is_err())
+    }
+
+    #[test]
+    fn test_generated_inputs_parse() {
+        for day in DAYS {
+            for (size, seed) in [(1, 0), (2, 1), (10, 2), (50, 3), (200, 4)] {
+                let input = day.generate(size, seed).unwrap_or_else(|| panic!("{} has no generator", day.name));
+                if let Err(err) = day.parse(&input) {
+                    panic!("{} with --size {size} --seed {seed}: {err}\n{input}", day.name)
+                }
+                assert_eq!(day.generate(size, seed), Some(input))
+            }
+        }
+    }
+}
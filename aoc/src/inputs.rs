@@ -0,0 +1,46 @@
+use std::path::Path;
+use std::process;
+
+use aoc_common::args::UsageError;
+use aoc_common::input_store::{InputStore, YEAR};
+use aoc_common::Day;
+
+use crate::days::select_days;
+
+/// `aoc input <day|all> [--import]`: make sure the inputs are in the input cache, fetching
+/// the missing ones (or with `--import`, copying each day's `input.txt`), and tell where they are.
+pub fn inputs(args: Vec<String>) -> Result<(), UsageError> {
+    let mut args = args.into_iter();
+    let name = args
+        .next()
+        .ok_or_else(|| UsageError(String::from("Which day? Give a day number or all")))?;
+    let mut days = select_days(&name).ok_or_else(|| UsageError(format!("No such day: {name}")))?;
+    let import = match args.next().as_deref() {
+        None => false,
+        Some("--import") => true,
+        Some(arg) => return Err(UsageError(format!("Unexpected argument: {arg}"))),
+    };
+    // d19 and d19_bfs share an input
+    days.dedup_by_key(|day| day.number());
+    let store = InputStore::from_env();
+    let mut n_failed = 0;
+    for day in days {
+        let result = if import { import_input(&store, day) } else { store.input(YEAR, day.number(), day.sections).map(|_| ()) };
+        match result {
+            Ok(()) => println!("{:8}  {}", day.name, store.path(YEAR, day.number()).display()),
+            Err(err) => {
+                eprintln!("{:8}  {err}", day.name);
+                n_failed += 1
+            }
+        }
+    }
+    if n_failed > 0 {
+        process::exit(1)
+    }
+    Ok(())
+}
+
+fn import_input(store: &InputStore, day: &Day) -> std::io::Result<()> {
+    let input = std::fs::read_to_string(Path::new(day.dir).join("input.txt"))?;
+    store.insert(YEAR, day.number(), &input, day.sections)
+}
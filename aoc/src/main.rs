@@ -0,0 +1,47 @@
+use std::process;
+
+use aoc_common::args::USAGE;
+
+mod answers;
+mod bench;
+mod days;
+mod generate;
+mod inputs;
+mod run;
+mod scaffold;
+mod serve;
+mod tui;
+mod verify;
+mod viz;
+
+fn usage() -> String {
+    format!(
+        "Usage:\n  aoc run <day|all> {USAGE}\n  aoc verify [<day|all>] [--part 1|2]\n  aoc bench {}\n  aoc viz {}\n  aoc gen {}\n  aoc serve {}\n  aoc input <day|all> [--import]\n  aoc new <day>",
+        bench::BENCH_USAGE,
+        viz::VIZ_USAGE,
+        generate::GEN_USAGE,
+        serve::SERVE_USAGE
+    )
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let result = match args.next().as_deref() {
+        Some("run") => run::run(args.collect()),
+        Some("verify") => verify::verify(args.collect()),
+        Some("bench") => bench::bench(args.collect()),
+        Some("viz") => viz::viz(args.collect()),
+        Some("gen") => generate::generate(args.collect()),
+        Some("serve") => serve::serve(args.collect()),
+        Some("input") => inputs::inputs(args.collect()),
+        Some("new") => scaffold::new_day(args.collect()),
+        _ => {
+            eprintln!("{}", usage());
+            process::exit(2)
+        }
+    };
+    if let Err(err) = result {
+        eprintln!("{err}\n{}", usage());
+        process::exit(2)
+    }
+}
@@ -0,0 +1,73 @@
+use aoc_common::args::{Format, RunArgs, UsageError};
+use aoc_common::input::InputSource;
+use aoc_common::json;
+use std::process;
+
+use aoc_common::runner::{print_answers, start_logging, start_pool, try_read_input, try_solve_timed};
+
+use crate::days::{select_days, solve_in_order};
+
+/// `aoc run <day|all> [--part N] [--input <path> | --example] [--format text|json] [-v] [--log-file <path>] [--jobs N]`
+///
+/// Several days are solved in parallel, but printed in order. A day whose input can't be read or
+/// doesn't parse gets its error instead of answers, and the others are still solved.
+pub fn run(args: Vec<String>) -> Result<(), UsageError> {
+    let mut args = args.into_iter();
+    let name = args
+        .next()
+        .ok_or_else(|| UsageError(String::from("Which day? Give a day number or all")))?;
+    let days = select_days(&name).ok_or_else(|| UsageError(format!("No such day: {name}")))?;
+    let run_args = RunArgs::parse(args)?;
+    start_logging(&run_args);
+    start_pool(&run_args);
+    let several = days.len() > 1;
+    if several && matches!(run_args.input, InputSource::File(_) | InputSource::Stdin) {
+        return Err(UsageError(String::from("--input can only be used with a single day")));
+    }
+    let solve = |day| {
+        let input = try_read_input(day, &run_args)?;
+        let solved = try_solve_timed(day, &input, &run_args)?;
+        Ok::<_, String>((input, solved))
+    };
+    let mut first = true;
+    let mut failed = false;
+    solve_in_order(&days, solve, |day, solved| {
+        if run_args.format == Format::Json {
+            // JSON lines, so days simply follow each other, and errors stay out of them
+            match solved {
+                Ok((input, solved)) => json::print_answers(day.name, &input, &solved),
+                Err(err) => {
+                    eprintln!("{}: {err}", day.name);
+                    failed = true
+                }
+            }
+            return;
+        }
+        if several {
+            if !first {
+                println!();
+            }
+            println!("{}", day.name);
+        }
+        let indent = if several { "  " } else { "" };
+        match solved {
+            Ok((_, solved)) => {
+                let answers: Vec<_> = solved.answers.into_iter().map(|(part, answer, _)| (part, answer)).collect();
+                print_answers(&answers, indent)
+            }
+            Err(err) if several => {
+                println!("{indent}{err}");
+                failed = true
+            }
+            Err(err) => {
+                eprintln!("{err}");
+                failed = true
+            }
+        }
+        first = false
+    });
+    if failed {
+        process::exit(1)
+    }
+    Ok(())
+}
@@ -0,0 +1,255 @@
+use std::fs;
+use std::path::Path;
+
+use aoc_common::args::UsageError;
+
+/// The root of the workspace.
+const WORKSPACE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+const CARGO_TOML: &str = r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc_common = { workspace = true }
+
+[lints]
+workspace = true
+"#;
+
+const MAIN_RS: &str = r#"fn main() {
+    aoc_common::runner::day_main(&{name}::DAY)
+}
+"#;
+
+const LIB_RS: &str = r#"use aoc_common::{Answer, Day, ParseError, Solution};
+
+pub struct {type};
+
+pub const DAY: Day = Day::new::<{type}>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR"));
+
+impl Solution for {type} {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input)
+    }
+}
+
+fn part_1(_lines: &[String]) -> Answer {
+    Answer::Missing
+}
+
+fn part_2(_lines: &[String]) -> Answer {
+    Answer::Missing
+}
+
+fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(input.lines().map(String::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::examples::check_example;
+    use aoc_common::Part;
+
+    #[test]
+    fn test_part_1() {
+        check_example(&DAY, "test.txt", Part::One)
+    }
+
+    #[test]
+    fn test_part_2() {
+        check_example(&DAY, "test.txt", Part::Two)
+    }
+}
+"#;
+
+const EXAMPLES_TOML: &str = r#"# The answers to the examples in the puzzle text, checked by the tests in src/lib.rs
+["test.txt"]
+# part_1 = 
+# part_2 = 
+"#;
+
+/// `aoc new <day>`: create a crate for a day, e.g. `16` or `16_dp` for another take on day 16,
+/// and add it to the workspace and to the `aoc` runner.
+pub fn new_day(args: Vec<String>) -> Result<(), UsageError> {
+    let [arg] = args.as_slice() else {
+        return Err(UsageError(String::from("Give the day to create, e.g. 7 or 16_dp")));
+    };
+    let name = crate_name(arg)?;
+    let root = Path::new(WORKSPACE_DIR);
+    let day_dir = root.join(&name);
+    if day_dir.exists() {
+        return Err(UsageError(format!("{name} already exists")));
+    }
+    // All edits are worked out before anything is written, so a failure leaves the workspace as it was
+    let edits = [
+        ("Cargo.toml", add_member as fn(&str, &str) -> Result<String, String>),
+        ("aoc/Cargo.toml", add_dependency),
+        ("aoc/src/days.rs", add_day),
+    ]
+    .map(|(file, edit)| {
+        let path = root.join(file);
+        let text = fs::read_to_string(&path).map_err(|err| format!("{file}: {err}"))?;
+        edit(&text, &name).map(|text| (path, text)).map_err(|err| format!("{file}: {err}"))
+    });
+    let files = [
+        ("Cargo.toml", fill(CARGO_TOML, &name)),
+        ("src/main.rs", fill(MAIN_RS, &name)),
+        ("src/lib.rs", fill(LIB_RS, &name)),
+        ("examples.toml", String::from(EXAMPLES_TOML)),
+        ("test.txt", String::new()),
+        ("input.txt", String::new()),
+    ];
+    let result = edits.into_iter().collect::<Result<Vec<_>, String>>().and_then(|edits| {
+        fs::create_dir_all(day_dir.join("src")).map_err(|err| err.to_string())?;
+        for (file, text) in files {
+            fs::write(day_dir.join(file), text).map_err(|err| format!("{file}: {err}"))?;
+        }
+        for (path, text) in edits {
+            fs::write(&path, text).map_err(|err| format!("{}: {err}", path.display()))?;
+        }
+        Ok(())
+    });
+    match result {
+        Ok(()) => {
+            println!("Created {name}; put the example in {name}/test.txt and its answers in {name}/examples.toml");
+            Ok(())
+        }
+        Err(err) => {
+            eprintln!("Could not create {name}: {err}");
+            std::process::exit(1)
+        }
+    }
+}
+
+// `7` or `d7` gives `d7`, `16_dp` gives `d16_dp`
+fn crate_name(arg: &str) -> Result<String, UsageError> {
+    let name = arg.strip_prefix('d').unwrap_or(arg);
+    let (number, suffix) = match name.split_once('_') {
+        Some((number, suffix)) => (number, Some(suffix)),
+        None => (name, None),
+    };
+    let valid_number = number.parse::<u8>().is_ok_and(|number| (1..=25).contains(&number))
+        && !number.starts_with('0');
+    let valid_suffix = suffix.is_none_or(|suffix| {
+        !suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    });
+    if valid_number && valid_suffix {
+        Ok(format!("d{name}"))
+    } else {
+        Err(UsageError(format!("Not a day: {arg}, expected a number from 1 to 25, optionally followed by _name")))
+    }
+}
+
+// `d19_bfs` gives `Day19Bfs`
+fn type_name(name: &str) -> String {
+    let words: String = name[1..]
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| first.to_ascii_uppercase().to_string() + chars.as_str())
+        })
+        .collect();
+    format!("Day{words}")
+}
+
+fn fill(template: &str, name: &str) -> String {
+    template.replace("{name}", name).replace("{type}", &type_name(name))
+}
+
+// A new line at the end of the workspace members
+fn add_member(cargo_toml: &str, name: &str) -> Result<String, String> {
+    let start = cargo_toml.find("members = [").ok_or("no workspace members")?;
+    let end = start + cargo_toml[start..].find("\n]").ok_or("no end to the workspace members")?;
+    Ok(format!("{}\n    \"{name}\",{}", &cargo_toml[..end], &cargo_toml[end..]))
+}
+
+// After the last of the day dependencies
+fn add_dependency(cargo_toml: &str, name: &str) -> Result<String, String> {
+    let last = cargo_toml.rfind(" = { path = \"../").ok_or("no day dependencies")?;
+    let end = last + cargo_toml[last..].find('\n').unwrap_or(cargo_toml.len() - last);
+    Ok(format!("{}\n{name} = {{ path = \"../{name}\" }}{}", &cargo_toml[..end], &cargo_toml[end..]))
+}
+
+// At the end of `DAYS`, which gets one longer
+fn add_day(days_rs: &str, name: &str) -> Result<String, String> {
+    let prefix = "pub const DAYS: [&Day; ";
+    let start = days_rs.find(prefix).ok_or("no DAYS")? + prefix.len();
+    let len_end = start + days_rs[start..].find(']').ok_or("no length of DAYS")?;
+    let len: usize = days_rs[start..len_end].parse().map_err(|_| "no length of DAYS")?;
+    let end = len_end + days_rs[len_end..].find("\n];").ok_or("no end of DAYS")?;
+    Ok(format!(
+        "{}{}{}\n    &{name}::DAY,{}",
+        &days_rs[..start],
+        len + 1,
+        &days_rs[len_end..end],
+        &days_rs[end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        assert_eq!(crate_name("7"), Ok(String::from("d7")));
+        assert_eq!(crate_name("d16_dp"), Ok(String::from("d16_dp")));
+        assert!(crate_name("26").is_err());
+        assert!(crate_name("07").is_err());
+        assert!(crate_name("16_").is_err());
+        assert!(crate_name("16-dp").is_err());
+        assert_eq!(type_name("d7"), "Day7");
+        assert_eq!(type_name("d19_bfs"), "Day19Bfs")
+    }
+
+    #[test]
+    fn test_add_member() {
+        let cargo_toml = "[workspace]\nmembers = [\n    \"aoc\",\n    \"d1\", \"d2\",\n]\n\n[workspace.dependencies]\n";
+        assert_eq!(
+            add_member(cargo_toml, "d3").unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"d1\", \"d2\",\n    \"d3\",\n]\n\n[workspace.dependencies]\n"
+        )
+    }
+
+    #[test]
+    fn test_add_dependency() {
+        let cargo_toml = "[dependencies]\ntoml = { workspace = true }\nd1 = { path = \"../d1\" }\n\n[lints]\n";
+        assert_eq!(
+            add_dependency(cargo_toml, "d2").unwrap(),
+            "[dependencies]\ntoml = { workspace = true }\nd1 = { path = \"../d1\" }\nd2 = { path = \"../d2\" }\n\n[lints]\n"
+        )
+    }
+
+    #[test]
+    fn test_add_day() {
+        let days_rs = "pub const DAYS: [&Day; 2] = [\n    &d1::DAY,\n    &d2::DAY,\n];\n";
+        assert_eq!(
+            add_day(days_rs, "d3").unwrap(),
+            "pub const DAYS: [&Day; 3] = [\n    &d1::DAY,\n    &d2::DAY,\n    &d3::DAY,\n];\n"
+        );
+        assert!(add_day("fn main() {}", "d3").is_err())
+    }
+
+    #[test]
+    fn test_templates() {
+        let lib_rs = fill(LIB_RS, "d16_dp");
+        assert!(lib_rs.contains("pub struct Day16Dp;"));
+        assert!(!lib_rs.contains("{type}") && !lib_rs.contains("{name}"));
+        assert!(fill(MAIN_RS, "d16_dp").contains("day_main(&d16_dp::DAY)"))
+    }
+}
@@ -0,0 +1,386 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use aoc_common::args::{Format, RunArgs, UsageError};
+use aoc_common::frames::{self, Recorder};
+use aoc_common::input::InputSource;
+use aoc_common::json::{answer_value, input_hash};
+use aoc_common::runner::{start_logging, start_pool, try_read_input};
+use aoc_common::{Answer, Day, Part};
+use rayon::prelude::*;
+use serde_json::{json, Value};
+use tiny_http::{Header, Response, Server};
+
+use crate::answers::{Answers, ANSWERS_FILE};
+use crate::bench::flag_value;
+use crate::days::{find_day, select_days};
+use crate::verify::Status;
+
+pub const SERVE_USAGE: &str = "[<day|all>] [--port N] [--part 1|2] [--jobs N] [-v] [--log-file <path>]";
+
+/// 2022, for the year of the puzzles
+const DEFAULT_PORT: u16 = 2022;
+
+struct ServeArgs {
+    run_args: RunArgs,
+    /// Always on localhost, so only the port can be picked
+    port: u16,
+}
+
+impl ServeArgs {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, UsageError> {
+        let mut port = DEFAULT_PORT;
+        let mut run_args = vec![];
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--port" => port = flag_value(&arg, args.next())?,
+                _ => run_args.push(arg),
+            }
+        }
+        let run_args = RunArgs::parse(run_args)?;
+        if run_args.input != InputSource::Bundled {
+            return Err(UsageError(String::from("serve always uses the real input.txt")));
+        }
+        if run_args.format != Format::Text {
+            return Err(UsageError(String::from("serve has its own JSON API, under /api/days")));
+        }
+        Ok(ServeArgs { run_args, port })
+    }
+}
+
+/// What the dashboard knows about a day, which is nothing until it is solved.
+enum Report {
+    Solving,
+    Done(DayReport),
+}
+
+struct DayReport {
+    /// `None` if the input couldn't be read
+    input_sha256: Option<String>,
+    /// Why there are no answers: the input is missing or doesn't parse, or the day panicked
+    error: Option<String>,
+    parse_seconds: f64,
+    parts: Vec<PartReport>,
+    stats: Vec<(&'static str, String)>,
+    /// The end of the part 1 simulation as a GIF, for the days that have frames
+    picture: Option<Vec<u8>>,
+}
+
+struct PartReport {
+    part: Part,
+    answer: Result<Answer, String>,
+    expected: Option<Answer>,
+    status: Status,
+    solve_seconds: f64,
+}
+
+impl DayReport {
+    // Every part gets a row, also when the day couldn't be solved
+    fn failed(day: &Day, args: &RunArgs, answers: &Answers, input_sha256: Option<String>, error: String) -> Self {
+        let parts = args
+            .parts()
+            .into_iter()
+            .map(|part| part_report(day, answers, part, Err(error.clone()), 0.0))
+            .collect();
+        DayReport { input_sha256, error: Some(error), parse_seconds: 0.0, parts, stats: vec![], picture: None }
+    }
+}
+
+fn part_report(day: &Day, answers: &Answers, part: Part, answer: Result<Answer, String>, solve_seconds: f64) -> PartReport {
+    let expected = answers.get(day.name, part).cloned();
+    let status = Status::new(expected.as_ref(), &answer);
+    PartReport { part, answer, expected, status, solve_seconds }
+}
+
+/// `aoc serve [<day|all>] ...`: solves the real inputs in the background and serves the answers,
+/// how they compare with `answers.toml`, the timings and the pictures on localhost, as a page
+/// at `/` and as JSON at `/api/days` and `/api/days/<day>`.
+pub fn serve(args: Vec<String>) -> Result<(), UsageError> {
+    let mut args = args.into_iter().peekable();
+    let name = args.next_if(|arg| !arg.starts_with('-')).unwrap_or_else(|| String::from("all"));
+    let days = select_days(&name).ok_or_else(|| UsageError(format!("No such day: {name}")))?;
+    let args = ServeArgs::parse(args)?;
+    start_logging(&args.run_args);
+    start_pool(&args.run_args);
+    let answers = Answers::load(Path::new(ANSWERS_FILE)).unwrap_or_else(|err| {
+        eprintln!("Could not load the answers: {err}");
+        process::exit(1)
+    });
+    let server = Server::http(("127.0.0.1", args.port)).unwrap_or_else(|err| {
+        eprintln!("Could not listen on port {}: {err}", args.port);
+        process::exit(1)
+    });
+    println!("Serving the answers on http://{}", server.server_addr());
+
+    let reports: Arc<Mutex<Vec<(&'static Day, Report)>>> =
+        Arc::new(Mutex::new(days.iter().map(|day| (*day, Report::Solving)).collect()));
+    let solving = Arc::clone(&reports);
+    // Unlike `run all` the days are shown as soon as each is done, as some take much longer than others
+    thread::spawn(move || {
+        days.par_iter().enumerate().for_each(|(ind, day)| {
+            let report = report(day, &args.run_args, &answers);
+            solving.lock().unwrap()[ind].1 = Report::Done(report);
+        })
+    });
+
+    for request in server.incoming_requests() {
+        let response = match respond(&reports.lock().unwrap(), request.url()) {
+            Some((content_type, body)) => {
+                Response::from_data(body).with_header(Header::from_bytes("Content-Type", content_type).unwrap())
+            }
+            None => Response::from_string("Not found").with_status_code(404),
+        };
+        // Only fails if the browser has gone away
+        let _ = request.respond(response);
+    }
+    Ok(())
+}
+
+/// Solves the day, and draws and describes its input if it can.
+fn report(day: &Day, args: &RunArgs, answers: &Answers) -> DayReport {
+    let input = match try_read_input(day, args) {
+        Ok(input) => input,
+        Err(err) => {
+            let mut report = DayReport::failed(day, args, answers, None, format!("missing input: {err}"));
+            report.parts.iter_mut().for_each(|part| part.status = Status::NoInput);
+            return report;
+        }
+    };
+    let input_sha256 = Some(input_hash(&input));
+    let solved = match panic::catch_unwind(|| day.solve_timed(&input, &args.parts())) {
+        Ok(Ok(solved)) => solved,
+        Ok(Err(err)) => return DayReport::failed(day, args, answers, input_sha256, format!("parse error: {err}")),
+        Err(_) => return DayReport::failed(day, args, answers, input_sha256, String::from("panicked")),
+    };
+    let parts = solved
+        .answers
+        .into_iter()
+        .map(|(part, answer, solve_time)| part_report(day, answers, part, Ok(answer), solve_time.as_secs_f64()))
+        .collect();
+    // A day whose stats panic is still reported, just without them
+    let stats = panic::catch_unwind(AssertUnwindSafe(|| day.stats(&input))).ok().flatten().and_then(Result::ok).unwrap_or_default();
+    DayReport { input_sha256, error: None, parse_seconds: solved.parse_time.as_secs_f64(), parts, stats, picture: picture(day, &input) }
+}
+
+// The last frame of part 1, scaled to be about 600 pixels wide. `None` if there is nothing to draw,
+// or it can't be drawn as a GIF.
+fn picture(day: &Day, input: &str) -> Option<Vec<u8>> {
+    let palette = day.palette()?;
+    let mut recorder = Recorder::last_only();
+    let simulated = panic::catch_unwind(AssertUnwindSafe(|| day.simulate(input, Part::One, &mut recorder)));
+    if !matches!(simulated, Ok(Some(Ok(())))) {
+        return None;
+    }
+    let frames = recorder.into_frames();
+    let ((_, min_col), (_, max_col)) = frames::canvas(&frames)?;
+    let scale = (600 / (max_col - min_col + 1) as usize).clamp(1, 8);
+    let mut gif = vec![];
+    frames::write_gif(&frames, &palette, scale, 100, &mut gif).ok()?;
+    Some(gif)
+}
+
+// The content type and body for `url`, `None` if there is nothing there
+fn respond(reports: &[(&'static Day, Report)], url: &str) -> Option<(&'static str, Vec<u8>)> {
+    let path = url.split('?').next().unwrap_or(url);
+    let json = |value: Value| Some(("application/json", value.to_string().into_bytes()));
+    match path.trim_end_matches('/') {
+        "" => Some(("text/html; charset=utf-8", page(reports).into_bytes())),
+        "/api/days" => json(Value::Array(reports.iter().map(|(day, report)| day_object(day, report)).collect())),
+        path => {
+            if let Some(name) = path.strip_prefix("/api/days/") {
+                let day = find_day(name)?;
+                let (day, report) = reports.iter().find(|(served, _)| served.name == day.name)?;
+                json(day_object(day, report))
+            } else {
+                let name = path.strip_prefix("/pictures/")?.strip_suffix(".gif")?;
+                match reports.iter().find(|(day, _)| day.name == name)? {
+                    (_, Report::Done(DayReport { picture: Some(gif), .. })) => Some(("image/gif", gif.clone())),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+/// A day in the JSON API:
+///
+/// ```json
+/// {"day": "d18", "solved": true, "error": null, "input_sha256": "5d2a...", "parse_seconds": 0.0003,
+///  "parts": [{"part": 1, "answer": 4300, "expected": 4300, "status": "ok", "solve_seconds": 0.02}, ...],
+///  "stats": {"cubes": "2192", ...}, "picture": null}
+/// ```
+///
+/// Days that are still being solved are only `{"day": "d19", "solved": false}`.
+fn day_object(day: &Day, report: &Report) -> Value {
+    let Report::Done(report) = report else {
+        return json!({"day": day.name, "solved": false});
+    };
+    let parts: Vec<Value> = report
+        .parts
+        .iter()
+        .map(|part| {
+            json!({
+                "part": match part.part {
+                    Part::One => 1,
+                    Part::Two => 2,
+                },
+                "answer": part.answer.as_ref().map_or(Value::Null, answer_value),
+                "expected": part.expected.as_ref().map_or(Value::Null, answer_value),
+                "status": part.status.label(),
+                "solve_seconds": part.solve_seconds,
+            })
+        })
+        .collect();
+    let stats: serde_json::Map<String, Value> =
+        report.stats.iter().map(|(name, value)| (name.to_string(), json!(value))).collect();
+    json!({
+        "day": day.name,
+        "solved": true,
+        "error": report.error,
+        "input_sha256": report.input_sha256,
+        "parse_seconds": report.parse_seconds,
+        "parts": parts,
+        "stats": stats,
+        "picture": report.picture.as_ref().map(|_| format!("/pictures/{}.gif", day.name)),
+    })
+}
+
+const STYLE: &str = "body { font-family: sans-serif; background: #0f0f23; color: #cccccc; margin: 2em }
+    table { border-collapse: collapse } td, th { padding: 0.2em 0.8em; text-align: left; vertical-align: top }
+    tr:nth-child(even) { background: #1a1a33 } pre { margin: 0; line-height: 1 } .bad { color: #ff6060 }
+    .ok { color: #00cc00 } figure { display: inline-block; margin: 1em } img { image-rendering: pixelated }";
+
+// The whole dashboard, with nothing loaded from elsewhere, so it works offline
+fn page(reports: &[(&'static Day, Report)]) -> String {
+    let solving = reports.iter().filter(|(_, report)| matches!(report, Report::Solving)).count();
+    // Reload until every day is there
+    let refresh = if solving > 0 { "<meta http-equiv=\"refresh\" content=\"5\">" } else { "" };
+    let mut html = format!("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">{refresh}<title>AoC 2022</title>\n<style>{STYLE}</style></head>\n<body><h1>Advent of Code 2022</h1>\n");
+    if solving > 0 {
+        html += &format!("<p>{solving} days are still being solved</p>\n");
+    }
+    html += "<table>\n<tr><th>Day</th><th>Part</th><th>Answer</th><th>Expected</th><th>Status</th><th>Time</th></tr>\n";
+    for (day, report) in reports {
+        match report {
+            Report::Solving => html += &format!("<tr><td>{}</td><td></td><td>solving...</td><td></td><td></td><td></td></tr>\n", day.name),
+            Report::Done(report) => {
+                for part in &report.parts {
+                    let answer = part.answer.as_ref().map_or_else(|err| escape(err), answer_cell);
+                    let expected = part.expected.as_ref().map_or_else(|| String::from("-"), answer_cell);
+                    let class = match part.status {
+                        Status::Ok => "ok",
+                        Status::Mismatch | Status::Regression => "bad",
+                        Status::Missing | Status::Unsolved | Status::NoInput => "",
+                    };
+                    html += &format!(
+                        "<tr><td>{}</td><td>{}</td><td>{answer}</td><td>{expected}</td><td class=\"{class}\">{}</td><td>{:.3} s</td></tr>\n",
+                        day.name,
+                        part.part,
+                        part.status.label(),
+                        part.solve_seconds
+                    );
+                }
+            }
+        }
+    }
+    html += "</table>\n";
+    for (day, report) in reports {
+        let Report::Done(report) = report else { continue };
+        if !report.stats.is_empty() {
+            html += &format!("<h2>{}</h2>\n<table>\n", day.name);
+            for (name, value) in &report.stats {
+                html += &format!("<tr><td>{}</td><td>{}</td></tr>\n", escape(name), escape(value));
+            }
+            html += "</table>\n";
+        }
+    }
+    for (day, report) in reports {
+        if let Report::Done(DayReport { picture: Some(_), .. }) = report {
+            html += &format!("<figure><img src=\"/pictures/{0}.gif\" alt=\"{0}\"><figcaption>{0}</figcaption></figure>\n", day.name);
+        }
+    }
+    html + "</body></html>\n"
+}
+
+// Multi-line answers, like the d10 CRT screen, are shown as they are
+fn answer_cell(answer: &Answer) -> String {
+    let text = escape(&answer.to_string());
+    if text.contains('\n') {
+        format!("<pre>{text}</pre>")
+    } else {
+        text
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<ServeArgs, UsageError> {
+        ServeArgs::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_serve_args() {
+        let serve_args = args(&["--port", "8080", "--part", "1"]).unwrap();
+        assert_eq!((serve_args.port, serve_args.run_args.parts()), (8080, vec![Part::One]));
+        assert_eq!(args(&[]).unwrap().port, DEFAULT_PORT);
+        assert!(args(&["--port", "http"]).is_err());
+        assert!(args(&["--example"]).is_err());
+        assert!(args(&["--format", "json"]).is_err())
+    }
+
+    fn reports() -> Vec<(&'static Day, Report)> {
+        let answers = Answers::parse("[d10]\npart_1 = 13140\n").unwrap();
+        let d10 = find_day("10").unwrap();
+        let crt = Answer::Text(String::from("##..\n#<>."));
+        let parts = vec![
+            part_report(d10, &answers, Part::One, Ok(Answer::Number(13140)), 0.5),
+            part_report(d10, &answers, Part::Two, Ok(crt), 0.25),
+        ];
+        let report = DayReport { input_sha256: Some(String::from("abc")), error: None, parse_seconds: 0.1, parts, stats: vec![("cycles", String::from("240"))], picture: None };
+        vec![(d10, Report::Done(report)), (find_day("19").unwrap(), Report::Solving)]
+    }
+
+    fn body(url: &str) -> String {
+        String::from_utf8(respond(&reports(), url).unwrap().1).unwrap()
+    }
+
+    #[test]
+    fn test_api() {
+        let d10: Value = serde_json::from_str(&body("/api/days/10")).unwrap();
+        assert_eq!(d10["parts"][0], json!({"part": 1, "answer": 13140, "expected": 13140, "status": "ok", "solve_seconds": 0.5}));
+        assert_eq!(d10["parts"][1]["answer"], json!(["##..", "#<>."]));
+        assert_eq!(d10["parts"][1]["status"], json!("missing"));
+        assert_eq!(d10["stats"], json!({"cycles": "240"}));
+        assert_eq!(d10["picture"], Value::Null);
+        let d19: Value = serde_json::from_str(&body("/api/days/d19")).unwrap();
+        assert_eq!(d19, json!({"day": "d19", "solved": false}));
+        let all: Value = serde_json::from_str(&body("/api/days/")).unwrap();
+        assert_eq!(all.as_array().unwrap().len(), 2);
+        // Not served, or no such day
+        assert!(respond(&reports(), "/api/days/5").is_none());
+        assert!(respond(&reports(), "/api/days/26").is_none());
+        assert!(respond(&reports(), "/pictures/d10.gif").is_none());
+        assert!(respond(&reports(), "/index.php").is_none())
+    }
+
+    #[test]
+    fn test_page() {
+        let (content_type, _) = respond(&reports(), "/?refresh").unwrap();
+        assert_eq!(content_type, "text/html; charset=utf-8");
+        let page = body("/");
+        assert!(page.contains("<pre>##..\n#&lt;&gt;.</pre>"));
+        assert!(page.contains("<td>d19</td><td></td><td>solving...</td>"));
+        assert!(page.contains("<td class=\"ok\">ok</td>"));
+        assert!(page.contains("http-equiv=\"refresh\""))
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use aoc_common::frames::{self, Frame, Palette, Rgb};
+use ncurses::*;
+
+/// What the keys do, shown on the status line.
+const KEYS: &str = "space play/pause  ,/. step  g/G first/last  +/- speed  arrows/PgUp/PgDn scroll  q quit";
+
+const MIN_DELAY_MS: i32 = 10;
+const MAX_DELAY_MS: i32 = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    /// Time for the next frame, when playing
+    Tick,
+    TogglePlaying,
+    Next,
+    Back,
+    First,
+    Last,
+    Faster,
+    Slower,
+    /// Moves the viewport by `(rows, cols)`
+    Scroll(isize, isize),
+    Quit,
+}
+
+fn command(key: i32) -> Option<Command> {
+    let command = match key {
+        KEY_RIGHT => Command::Scroll(0, 1),
+        KEY_LEFT => Command::Scroll(0, -1),
+        KEY_DOWN => Command::Scroll(1, 0),
+        KEY_UP => Command::Scroll(-1, 0),
+        KEY_NPAGE => Command::Scroll(10, 0),
+        KEY_PPAGE => Command::Scroll(-10, 0),
+        KEY_HOME => Command::First,
+        KEY_END => Command::Last,
+        _ => match char::from_u32(key as u32)? {
+            ' ' => Command::TogglePlaying,
+            '.' | 'n' => Command::Next,
+            ',' | 'p' => Command::Back,
+            'g' => Command::First,
+            'G' => Command::Last,
+            '+' | '=' => Command::Faster,
+            '-' => Command::Slower,
+            'q' | 'Q' => Command::Quit,
+            _ => return None,
+        },
+    };
+    Some(command)
+}
+
+/// Which frame is shown and how, apart from the drawing itself.
+#[derive(Debug, PartialEq, Eq)]
+struct Viewer {
+    n_frames: usize,
+    frame: usize,
+    playing: bool,
+    delay_ms: i32,
+    /// Rows and columns of the canvas that all of the frames are drawn on
+    size: (isize, isize),
+    /// The position on the canvas in the upper left corner of the screen
+    scroll: (isize, isize),
+}
+
+impl Viewer {
+    fn new(n_frames: usize, size: (isize, isize), delay_ms: i32) -> Self {
+        Viewer { n_frames, frame: 0, playing: true, delay_ms: delay_ms.clamp(MIN_DELAY_MS, MAX_DELAY_MS), size, scroll: (0, 0) }
+    }
+
+    /// Carries out `command`, `false` if it is time to quit.
+    fn apply(&mut self, command: Command) -> bool {
+        let last = self.n_frames.saturating_sub(1);
+        match command {
+            Command::Tick => {
+                self.frame = (self.frame + 1).min(last);
+                // Stop at the end rather than start over
+                self.playing &= self.frame < last
+            }
+            Command::TogglePlaying => {
+                if !self.playing && self.frame == last {
+                    self.frame = 0
+                }
+                self.playing = !self.playing
+            }
+            Command::Next => self.step_to((self.frame + 1).min(last)),
+            Command::Back => self.step_to(self.frame.saturating_sub(1)),
+            Command::First => self.step_to(0),
+            Command::Last => self.step_to(last),
+            Command::Faster => self.delay_ms = (self.delay_ms / 2).max(MIN_DELAY_MS),
+            Command::Slower => self.delay_ms = (self.delay_ms * 2).min(MAX_DELAY_MS),
+            Command::Scroll(rows, cols) => {
+                let (n_rows, n_cols) = self.size;
+                self.scroll = ((self.scroll.0 + rows).clamp(0, n_rows - 1), (self.scroll.1 + cols).clamp(0, n_cols - 1))
+            }
+            Command::Quit => return false,
+        }
+        true
+    }
+
+    fn step_to(&mut self, frame: usize) {
+        self.frame = frame;
+        self.playing = false
+    }
+
+    fn status(&self) -> String {
+        let state = if self.playing { "playing" } else { "paused" };
+        format!("Frame {}/{}  {state}  {} ms  |  {KEYS}", self.frame + 1, self.n_frames, self.delay_ms)
+    }
+}
+
+// The nearest of the eight curses colours, which are numbered by their red, green and blue bits
+fn curses_colour([r, g, b]: Rgb) -> i16 {
+    [r, g, b].iter().enumerate().map(|(bit, channel)| i16::from(*channel >= 0x60) << bit).sum()
+}
+
+// A colour pair for each symbol with a colour, on the terminal's own background
+fn colour_pairs(palette: &Palette) -> HashMap<char, i16> {
+    start_color();
+    use_default_colors();
+    palette
+        .colours()
+        .zip(1..)
+        .map(|((symbol, colour), pair)| {
+            init_pair(pair, curses_colour(colour), -1);
+            (symbol, pair)
+        })
+        .collect()
+}
+
+/// Shows the frames in the terminal, until `q` is pressed. Does nothing if they are all empty.
+pub fn show(frames: &[Frame], palette: &Palette, delay_ms: u16) {
+    let Some(((min_row, min_col), (max_row, max_col))) = frames::canvas(frames) else {
+        return;
+    };
+    let mut viewer = Viewer::new(frames.len(), (max_row - min_row + 1, max_col - min_col + 1), delay_ms.into());
+    initscr();
+    cbreak();
+    noecho();
+    keypad(stdscr(), true);
+    curs_set(CURSOR_VISIBILITY::CURSOR_INVISIBLE);
+    let pairs = if has_colors() { colour_pairs(palette) } else { HashMap::new() };
+    loop {
+        let (mut n_rows, mut n_cols) = (0, 0);
+        getmaxyx(stdscr(), &mut n_rows, &mut n_cols);
+        erase();
+        // The last line is the status line
+        let frame = &frames[viewer.frame];
+        for screen_row in 0..(n_rows - 1).max(0) {
+            for screen_col in 0..n_cols {
+                let pos = (min_row + viewer.scroll.0 + screen_row as isize, min_col + viewer.scroll.1 + screen_col as isize);
+                let Some(symbol) = frame.get(pos) else {
+                    continue;
+                };
+                let attribute = pairs.get(&symbol).map_or(0, |pair| COLOR_PAIR(*pair));
+                mvaddch(screen_row, screen_col, symbol as chtype | attribute);
+            }
+        }
+        let status: String = viewer.status().chars().take(n_cols.max(0) as usize).collect();
+        attron(A_REVERSE());
+        mvaddstr(n_rows - 1, 0, &status);
+        attroff(A_REVERSE());
+        refresh();
+
+        timeout(if viewer.playing { viewer.delay_ms } else { -1 });
+        let command = match getch() {
+            ERR => Command::Tick,
+            key => match command(key) {
+                Some(command) => command,
+                None => continue,
+            },
+        };
+        if !viewer.apply(command) {
+            break;
+        }
+    }
+    endwin();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_playing() {
+        let mut viewer = Viewer::new(3, (10, 10), 100);
+        assert!(viewer.playing);
+        viewer.apply(Command::Tick);
+        viewer.apply(Command::Tick);
+        assert_eq!((viewer.frame, viewer.playing), (2, false));
+        // Playing again from the end starts over
+        viewer.apply(Command::TogglePlaying);
+        assert_eq!((viewer.frame, viewer.playing), (0, true));
+        viewer.apply(Command::Next);
+        assert_eq!((viewer.frame, viewer.playing), (1, false));
+        viewer.apply(Command::Back);
+        viewer.apply(Command::Back);
+        assert_eq!(viewer.frame, 0);
+        viewer.apply(Command::Last);
+        assert_eq!(viewer.frame, 2);
+        assert!(!viewer.apply(Command::Quit))
+    }
+
+    #[test]
+    fn test_speed_and_scroll() {
+        let mut viewer = Viewer::new(3, (10, 20), 100);
+        viewer.apply(Command::Faster);
+        assert_eq!(viewer.delay_ms, 50);
+        (0..10).for_each(|_| _ = viewer.apply(Command::Slower));
+        assert_eq!(viewer.delay_ms, MAX_DELAY_MS);
+        viewer.apply(Command::Scroll(-1, 3));
+        assert_eq!(viewer.scroll, (0, 3));
+        viewer.apply(Command::Scroll(100, 100));
+        assert_eq!(viewer.scroll, (9, 19))
+    }
+
+    #[test]
+    fn test_keys() {
+        assert_eq!(command(' ' as i32), Some(Command::TogglePlaying));
+        assert_eq!(command(KEY_UP), Some(Command::Scroll(-1, 0)));
+        assert_eq!(command('x' as i32), None);
+        assert_eq!(curses_colour([0xff, 0x99, 0x00]), COLOR_YELLOW);
+        assert_eq!(curses_colour([0x0f, 0x0f, 0x23]), COLOR_BLACK)
+    }
+}
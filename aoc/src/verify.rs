@@ -0,0 +1,153 @@
+use std::panic;
+use std::path::Path;
+use std::process;
+
+use aoc_common::args::{Format, RunArgs, UsageError};
+use aoc_common::input::InputSource;
+use aoc_common::examples::same_answer;
+use aoc_common::runner::{start_logging, start_pool, try_read_input};
+use aoc_common::{Answer, Day, Part};
+
+use crate::answers::{Answers, ANSWERS_FILE};
+use crate::days::{select_days, solve_in_order};
+
+/// How a solver's answer compares to the one in the registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Status {
+    Ok,
+    /// A different answer than the registered one
+    Mismatch,
+    /// There is a registered answer but the solver no longer gives one (or panics)
+    Regression,
+    /// An answer that isn't registered yet
+    Missing,
+    /// Neither an answer nor a registered one
+    Unsolved,
+    /// The input couldn't be read, so there is nothing to compare
+    NoInput,
+}
+
+impl Status {
+    pub(crate) fn new(expected: Option<&Answer>, got: &Result<Answer, String>) -> Self {
+        match (expected, got) {
+            (Some(_), Ok(Answer::Missing) | Err(_)) => Status::Regression,
+            (Some(expected), Ok(got)) if same_answer(expected, got) => Status::Ok,
+            (Some(_), Ok(_)) => Status::Mismatch,
+            (None, Ok(Answer::Missing) | Err(_)) => Status::Unsolved,
+            (None, Ok(_)) => Status::Missing,
+        }
+    }
+
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Mismatch => "MISMATCH",
+            Status::Regression => "REGRESSION",
+            Status::Missing => "missing",
+            Status::Unsolved => "-",
+            Status::NoInput => "missing input",
+        }
+    }
+}
+
+/// `aoc verify [<day|all>] [--part N]`: solve the real inputs and compare with `answers.toml`.
+pub fn verify(args: Vec<String>) -> Result<(), UsageError> {
+    let mut args = args.into_iter().peekable();
+    let name = args.next_if(|arg| !arg.starts_with('-')).unwrap_or_else(|| String::from("all"));
+    let days = select_days(&name).ok_or_else(|| UsageError(format!("No such day: {name}")))?;
+    let run_args = RunArgs::parse(args)?;
+    if run_args.input != InputSource::Bundled {
+        return Err(UsageError(String::from("verify always uses the real input.txt")));
+    }
+    if run_args.format != Format::Text {
+        return Err(UsageError(String::from("verify only prints a table")));
+    }
+    start_logging(&run_args);
+    start_pool(&run_args);
+    let answers = Answers::load(Path::new(ANSWERS_FILE)).unwrap_or_else(|err| {
+        eprintln!("Could not load the answers: {err}");
+        process::exit(1)
+    });
+
+    // The days are solved in parallel, and their rows printed in order as soon as they can be: some
+    // of them take minutes
+    print_row(["Day", "Part", "Expected", "Got", "Status"]);
+    let mut statuses = vec![];
+    solve_in_order(&days, |day| solve(day, &run_args), |day, solved| {
+        // Without an input the other days are still verified
+        let rows: Vec<(Part, String, Status)> = match solved {
+            Ok(solved) => solved
+                .into_iter()
+                .map(|(part, got)| {
+                    let status = Status::new(answers.get(day.name, part), &got);
+                    (part, got.map_or_else(|err| err, |answer| cell(&answer)), status)
+                })
+                .collect(),
+            Err(err) => {
+                eprintln!("{err}");
+                run_args.parts().into_iter().map(|part| (part, String::from("no input"), Status::NoInput)).collect()
+            }
+        };
+        for (part, got, status) in rows {
+            let expected = answers.get(day.name, part).map_or_else(|| String::from("-"), cell);
+            print_row([day.name, &part.to_string(), &expected, &got, status.label()]);
+            statuses.push(status);
+        }
+    });
+
+    let count = |status: &Status| statuses.iter().filter(|s| *s == status).count();
+    let (n_mismatches, n_regressions) = (count(&Status::Mismatch), count(&Status::Regression));
+    println!(
+        "\n{} ok, {n_mismatches} mismatches, {n_regressions} regressions, {} missing, {} unsolved, {} without input",
+        count(&Status::Ok),
+        count(&Status::Missing),
+        count(&Status::Unsolved),
+        count(&Status::NoInput)
+    );
+    if n_mismatches + n_regressions > 0 {
+        process::exit(1)
+    }
+    Ok(())
+}
+
+// A day that doesn't parse or panics still gets a row per part. Why the input can't be read if it can't.
+#[allow(clippy::type_complexity)]
+fn solve(day: &Day, args: &RunArgs) -> Result<Vec<(Part, Result<Answer, String>)>, String> {
+    let input = try_read_input(day, args)?;
+    let parts = args.parts();
+    Ok(match panic::catch_unwind(|| day.solve(&input, &parts)) {
+        Ok(Ok(answers)) => answers.into_iter().map(|(part, answer)| (part, Ok(answer))).collect(),
+        Ok(Err(err)) => parts.iter().map(|part| (*part, Err(format!("parse error: {err}")))).collect(),
+        Err(_) => parts.iter().map(|part| (*part, Err(String::from("panicked")))).collect(),
+    })
+}
+
+// Multi-line answers don't fit in a table
+fn cell(answer: &Answer) -> String {
+    let answer = answer.to_string();
+    match answer.lines().count() {
+        0 | 1 => answer,
+        n_lines => format!("<{n_lines} lines>"),
+    }
+}
+
+// Wide enough for the answers so far; longer ones just push the status to the right
+fn print_row([day, part, expected, got, status]: [&str; 5]) {
+    println!("{day:8}  {part:4}  {expected:22}  {got:22}  {status}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status() {
+        let expected = Answer::Number(42);
+        assert_eq!(Status::new(Some(&expected), &Ok(Answer::Number(42))), Status::Ok);
+        assert_eq!(Status::new(Some(&expected), &Ok(Answer::Number(41))), Status::Mismatch);
+        assert_eq!(Status::new(Some(&expected), &Ok(Answer::Missing)), Status::Regression);
+        assert_eq!(Status::new(Some(&expected), &Err(String::from("panicked"))), Status::Regression);
+        assert_eq!(Status::new(None, &Ok(Answer::Number(42))), Status::Missing);
+        assert_eq!(Status::new(None, &Ok(Answer::Missing)), Status::Unsolved)
+    }
+}
@@ -0,0 +1,200 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use aoc_common::args::{Format, RunArgs, UsageError};
+use aoc_common::frames::{self, Frame, Palette, Recorder};
+use aoc_common::runner::{read_input, start_logging};
+use aoc_common::{Day, Part};
+
+use crate::bench::flag_value;
+use crate::days::find_day;
+use crate::tui;
+
+pub const VIZ_USAGE: &str = "<day> [--part 1|2] [--input <path> | --example] [--every N | --last] \
+    [--tui | --out <path.gif|path.ppm>] [--scale N] [--palette SYMBOL=RRGGBB,...] [--delay MS]";
+
+struct VizArgs {
+    run_args: RunArgs,
+    /// Keep every Nth step of the simulation
+    every: usize,
+    /// Only keep the end result
+    last: bool,
+    /// Text on stdout without one
+    out: Option<PathBuf>,
+    /// Show the frames in the interactive viewer instead
+    tui: bool,
+    /// Pixels per cell in the images
+    scale: usize,
+    /// Colours that replace the day's own
+    palette: Option<String>,
+    /// How long each frame is shown. Text frames are only animated, in place, if it is given.
+    delay_ms: Option<u16>,
+}
+
+impl VizArgs {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, UsageError> {
+        let mut viz_args =
+            VizArgs { run_args: RunArgs::parse([])?, every: 1, last: false, out: None, tui: false, scale: 4, palette: None, delay_ms: None };
+        let mut run_args = vec![];
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--every" => viz_args.every = flag_value(&arg, args.next())?,
+                "--last" => viz_args.last = true,
+                "--tui" => viz_args.tui = true,
+                "--scale" => viz_args.scale = flag_value(&arg, args.next())?,
+                "--delay" => viz_args.delay_ms = Some(flag_value(&arg, args.next())?),
+                "--out" | "-o" => {
+                    viz_args.out = Some(PathBuf::from(args.next().ok_or_else(|| UsageError(format!("{arg} needs a path")))?))
+                }
+                "--palette" => {
+                    viz_args.palette = Some(args.next().ok_or_else(|| UsageError(format!("{arg} needs colours")))?)
+                }
+                _ => run_args.push(arg),
+            }
+        }
+        viz_args.run_args = RunArgs::parse(run_args)?;
+        if viz_args.run_args.format != Format::Text {
+            return Err(UsageError(String::from("viz writes frames, not answers")));
+        }
+        if viz_args.every == 0 || viz_args.scale == 0 {
+            return Err(UsageError(String::from("--every and --scale must be at least 1")));
+        }
+        if viz_args.tui && viz_args.out.is_some() {
+            return Err(UsageError(String::from("Only one of --tui and --out can be given")));
+        }
+        if let Some(out) = &viz_args.out {
+            if !matches!(extension(out), Some("gif" | "ppm")) {
+                return Err(UsageError(format!("Can only write .gif or .ppm, not {}", out.display())));
+            }
+        }
+        Ok(viz_args)
+    }
+
+    fn part(&self) -> Part {
+        self.run_args.part.unwrap_or(Part::One)
+    }
+
+    fn recorder(&self) -> Recorder {
+        if self.last {
+            Recorder::last_only()
+        } else {
+            Recorder::every(self.every)
+        }
+    }
+}
+
+fn extension(path: &Path) -> Option<&str> {
+    path.extension().and_then(|extension| extension.to_str())
+}
+
+/// `aoc viz <day> ...`: runs the simulation of a day and writes its frames.
+pub fn viz(args: Vec<String>) -> Result<(), UsageError> {
+    let mut args = args.into_iter();
+    let name = args.next().ok_or_else(|| UsageError(String::from("Which day? Give a day number")))?;
+    let day = find_day(&name).ok_or_else(|| UsageError(format!("No such day: {name}")))?;
+    if !day.has_frames() {
+        return Err(UsageError(format!("{} has nothing to watch", day.name)));
+    }
+    let args = VizArgs::parse(args)?;
+    start_logging(&args.run_args);
+    let palette = day.palette().unwrap();
+    let palette = match &args.palette {
+        Some(overrides) => palette.with_overrides(overrides).map_err(UsageError)?,
+        None => palette,
+    };
+    let frames = record(day, &args);
+    if args.tui {
+        tui::show(&frames, &palette, args.delay_ms.unwrap_or(100));
+        return Ok(());
+    }
+    let written = match &args.out {
+        None => write_text(&frames, args.delay_ms),
+        Some(out) if extension(out) == Some("gif") => {
+            File::create(out).and_then(|file| {
+                frames::write_gif(&frames, &palette, args.scale, args.delay_ms.unwrap_or(100), &mut BufWriter::new(file))
+            })
+        }
+        Some(out) => write_stills(&frames, &palette, args.scale, out),
+    };
+    written.unwrap_or_else(|err| {
+        eprintln!("Could not write the frames: {err}");
+        process::exit(1)
+    });
+    Ok(())
+}
+
+/// Runs the simulation, exiting with a message if the input can't be read or parsed.
+fn record(day: &Day, args: &VizArgs) -> Vec<Frame> {
+    let input = read_input(day, &args.run_args);
+    let mut recorder = args.recorder();
+    if let Some(Err(err)) = day.simulate(&input, args.part(), &mut recorder) {
+        eprintln!("Could not parse {}: {err}", args.run_args.input.name(Path::new(day.dir)));
+        process::exit(1)
+    }
+    recorder.into_frames()
+}
+
+fn write_text(frames: &[Frame], delay_ms: Option<u16>) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    let Some(delay_ms) = delay_ms else {
+        return frames::write_ascii(frames, &mut out);
+    };
+    for frame in frames {
+        // Clear the screen and draw the next frame from the top left
+        write!(out, "\x1b[2J\x1b[H{frame}")?;
+        out.flush()?;
+        thread::sleep(Duration::from_millis(delay_ms.into()));
+    }
+    Ok(())
+}
+
+/// A single frame goes to `out`, several to `out` numbered, e.g. `sand-001.ppm`, `sand-002.ppm`.
+fn write_stills(frames: &[Frame], palette: &Palette, scale: usize, out: &Path) -> io::Result<()> {
+    let canvas = frames::canvas(frames).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "There is nothing to draw"))?;
+    let width = frames.len().to_string().len();
+    for (ind, frame) in frames.iter().enumerate() {
+        let path = if frames.len() == 1 { out.to_path_buf() } else { numbered(out, ind + 1, width) };
+        let mut file = BufWriter::new(File::create(path)?);
+        frames::write_ppm(frame, canvas, palette, scale, &mut file)?;
+        file.flush()?;
+    }
+    Ok(())
+}
+
+fn numbered(path: &Path, number: usize, width: usize) -> PathBuf {
+    let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("frame");
+    path.with_file_name(format!("{stem}-{number:0width$}.ppm"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<VizArgs, UsageError> {
+        VizArgs::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_viz_args() {
+        let viz_args = args(&["--example", "--part", "2", "--every", "5", "--out", "sand.gif", "--scale", "2"]).unwrap();
+        assert_eq!((viz_args.part(), viz_args.every, viz_args.scale), (Part::Two, 5, 2));
+        assert_eq!(viz_args.out, Some(PathBuf::from("sand.gif")));
+        assert_eq!(args(&[]).unwrap().part(), Part::One);
+        assert!(args(&["--out", "sand.png"]).is_err());
+        assert!(args(&["--every", "0"]).is_err());
+        assert!(args(&["--format", "json"]).is_err());
+        assert!(args(&["--delay"]).is_err());
+        assert!(args(&["--tui"]).unwrap().tui);
+        assert!(args(&["--tui", "--out", "sand.gif"]).is_err())
+    }
+
+    #[test]
+    fn test_numbered() {
+        assert_eq!(numbered(Path::new("out/sand.ppm"), 7, 3), PathBuf::from("out/sand-007.ppm"))
+    }
+}
@@ -0,0 +1,173 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::input::InputSource;
+use crate::solution::Part;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UsageError(pub String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for UsageError {}
+
+pub const USAGE: &str = "[--part 1|2] [--input <path> | --input - | --example] [--format text|json] [-v|-vv|-vvv] [--log-file <path>] [--jobs N]";
+
+/// How the answers are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// `Part N: answer` lines
+    Text,
+    /// One JSON object per part, see `json::answer_objects`
+    Json,
+}
+
+/// Options for running a single day.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RunArgs {
+    pub input: InputSource,
+    /// Only run this part, both if `None`
+    pub part: Option<Part>,
+    pub format: Format,
+    /// Number of `-v`, see `logging::level`
+    pub verbosity: u8,
+    /// Where to also write the log, as JSON lines
+    pub log_file: Option<PathBuf>,
+    /// Threads for running days and the work within them, as many as there are cores if `None`
+    pub jobs: Option<usize>,
+}
+
+impl RunArgs {
+    /// Parses the arguments after the program (or subcommand) name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, UsageError> {
+        let mut input: Option<InputSource> = None;
+        let mut part = None;
+        let mut format = Format::Text;
+        let mut verbosity = 0;
+        let mut log_file = None;
+        let mut jobs = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" | "-i" => {
+                    let source = match args.next().as_deref() {
+                        Some("-") => InputSource::Stdin,
+                        Some(path) => InputSource::File(PathBuf::from(path)),
+                        None => return Err(UsageError(format!("{arg} needs a path, or - for stdin"))),
+                    };
+                    set_input(&mut input, source)?
+                }
+                "--example" | "-e" => set_input(&mut input, InputSource::Example)?,
+                "--part" | "-p" => {
+                    part = match args.next().as_deref() {
+                        Some("1") => Some(Part::One),
+                        Some("2") => Some(Part::Two),
+                        _ => return Err(UsageError(format!("{arg} needs to be 1 or 2"))),
+                    }
+                }
+                "--format" | "-f" => {
+                    format = match args.next().as_deref() {
+                        Some("text") => Format::Text,
+                        Some("json") => Format::Json,
+                        _ => return Err(UsageError(format!("{arg} needs to be text or json"))),
+                    }
+                }
+                "--log-file" => match args.next() {
+                    Some(path) => log_file = Some(PathBuf::from(path)),
+                    None => return Err(UsageError(format!("{arg} needs a path"))),
+                },
+                "--jobs" | "-j" => match args.next().and_then(|n| n.parse().ok()) {
+                    Some(n) if n > 0 => jobs = Some(n),
+                    _ => return Err(UsageError(format!("{arg} needs a number of threads, at least 1"))),
+                },
+                // -v, -vv or -vvv, which can also be given one at a time
+                _ if arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v') => {
+                    verbosity += (arg.len() - 1) as u8
+                }
+                _ => return Err(UsageError(format!("Unexpected argument: {arg}"))),
+            }
+        }
+        Ok(RunArgs {
+            input: input.unwrap_or(InputSource::Bundled),
+            part,
+            format,
+            verbosity,
+            log_file,
+            jobs,
+        })
+    }
+
+    pub fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec![Part::One, Part::Two],
+        }
+    }
+}
+
+fn set_input(input: &mut Option<InputSource>, source: InputSource) -> Result<(), UsageError> {
+    if input.is_some() {
+        return Err(UsageError(String::from("Only one of --input and --example can be given")));
+    }
+    *input = Some(source);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<RunArgs, UsageError> {
+        RunArgs::parse(args.iter().map(|s| String::from(*s)))
+    }
+
+    #[test]
+    fn test_input() {
+        assert_eq!(parse(&[]).unwrap().input, InputSource::Bundled);
+        assert_eq!(parse(&["--example"]).unwrap().input, InputSource::Example);
+        assert_eq!(parse(&["--input", "-"]).unwrap().input, InputSource::Stdin);
+        assert_eq!(parse(&["-i", "my.txt"]).unwrap().input, InputSource::File(PathBuf::from("my.txt")));
+        assert!(parse(&["--input"]).is_err());
+        assert!(parse(&["--example", "--input", "my.txt"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+    }
+
+    #[test]
+    fn test_part() {
+        assert_eq!(parse(&[]).unwrap().parts(), vec![Part::One, Part::Two]);
+        assert_eq!(parse(&["--part", "2", "-e"]).unwrap().parts(), vec![Part::Two]);
+        assert!(parse(&["--part", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(parse(&[]).unwrap().format, Format::Text);
+        assert_eq!(parse(&["-e", "--format", "json"]).unwrap().format, Format::Json);
+        assert!(parse(&["--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_logging() {
+        assert_eq!(parse(&[]).unwrap().verbosity, 0);
+        assert_eq!(parse(&["-v"]).unwrap().verbosity, 1);
+        assert_eq!(parse(&["-vv", "-e"]).unwrap().verbosity, 2);
+        assert_eq!(parse(&["-v", "-vv"]).unwrap().verbosity, 3);
+        assert!(parse(&["-vx"]).is_err());
+        assert_eq!(parse(&[]).unwrap().log_file, None);
+        assert_eq!(parse(&["--log-file", "log.json"]).unwrap().log_file, Some(PathBuf::from("log.json")));
+        assert!(parse(&["--log-file"]).is_err());
+    }
+
+    #[test]
+    fn test_jobs() {
+        assert_eq!(parse(&[]).unwrap().jobs, None);
+        assert_eq!(parse(&["--jobs", "4"]).unwrap().jobs, Some(4));
+        assert_eq!(parse(&["-j", "1", "-e"]).unwrap().jobs, Some(1));
+        assert!(parse(&["--jobs", "0"]).is_err());
+        assert!(parse(&["--jobs"]).is_err());
+    }
+}
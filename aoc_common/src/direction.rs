@@ -0,0 +1,69 @@
+/// The four grid directions, with rows growing downwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+    pub fn turn_left(&self) -> Direction {
+        match self {
+            Direction::Right => Direction::Up,
+            Direction::Left => Direction::Down,
+            Direction::Up => Direction::Left,
+            Direction::Down => Direction::Right,
+        }
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        match self {
+            Direction::Right => Direction::Down,
+            Direction::Left => Direction::Up,
+            Direction::Up => Direction::Right,
+            Direction::Down => Direction::Left,
+        }
+    }
+
+    pub fn reverse(&self) -> Direction {
+        self.turn_left().turn_left()
+    }
+
+    /// `(delta_row, delta_col)` of one step in this direction.
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+
+    pub fn step(&self, (row, col): (isize, isize)) -> (isize, isize) {
+        let (delta_row, delta_col) = self.delta();
+        (row + delta_row, col + delta_col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.reverse().reverse(), direction);
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right)
+    }
+
+    #[test]
+    fn test_step() {
+        assert_eq!(Direction::Up.step((3, 3)), (2, 3));
+        assert_eq!(Direction::Left.step((3, 3)), (3, 2))
+    }
+}
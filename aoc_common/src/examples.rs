@@ -0,0 +1,84 @@
+//! Expected answers for the examples, kept in `examples.toml` in the day's directory
+//! so that tests don't need to repeat them:
+//!
+//! ```toml
+//! ["test.txt"]
+//! part_1 = 24000
+//! part_2 = 45000
+//! ```
+
+use std::path::Path;
+
+use crate::solution::{Answer, Day, Part};
+
+/// A number or a string from a TOML answer registry, `None` for anything else.
+pub fn answer_from_toml(value: &toml::Value) -> Option<Answer> {
+    match value {
+        toml::Value::Integer(number) => Some(Answer::Number(*number)),
+        toml::Value::String(text) => Some(Answer::Text(text.clone())),
+        _ => None,
+    }
+}
+
+/// Whether `got` is the `expected` answer. Multi-line answers (the CRT of day 10) are
+/// compared line by line, ignoring trailing spaces that editors like to strip.
+pub fn same_answer(expected: &Answer, got: &Answer) -> bool {
+    match (expected, got) {
+        (Answer::Text(expected), Answer::Text(got)) => {
+            expected.trim_end().lines().map(str::trim_end).eq(got.trim_end().lines().map(str::trim_end))
+        }
+        _ => expected == got,
+    }
+}
+
+/// The expected answer to one part of `example`, if `examples.toml` has it.
+pub fn example_answer(examples_toml: &str, example: &str, part: Part) -> Result<Option<Answer>, String> {
+    let table: toml::Table = examples_toml.parse().map_err(|err: toml::de::Error| err.message().to_string())?;
+    let key = match part {
+        Part::One => "part_1",
+        Part::Two => "part_2",
+    };
+    match table.get(example).and_then(|answers| answers.get(key)) {
+        None => Ok(None),
+        Some(value) => answer_from_toml(value)
+            .map(Some)
+            .ok_or_else(|| format!("{example}.{key} should be a number or a string")),
+    }
+}
+
+/// Solves one part of an example and checks it against `examples.toml`, for the tests of a day.
+/// A part that has no answer there yet is skipped, so that a new day's tests pass until it does.
+pub fn check_example(day: &Day, example: &str, part: Part) {
+    let day_dir = Path::new(day.dir);
+    let examples_toml = std::fs::read_to_string(day_dir.join("examples.toml")).unwrap();
+    let Some(expected) = example_answer(&examples_toml, example, part).unwrap() else {
+        eprintln!("examples.toml has no answer to part {part} of {example} yet, not checking it");
+        return
+    };
+    let input = std::fs::read_to_string(day_dir.join(example)).unwrap();
+    let answers = day.solve(&input, &[part]).unwrap();
+    let got = &answers[0].1;
+    assert!(same_answer(&expected, got), "part {part} of {example}: expected {expected}, got {got}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_answer() {
+        let examples_toml = "[\"test.txt\"]\npart_1 = 24000\n\n[\"test2.txt\"]\npart_2 = \"CMZ\"\n";
+        assert_eq!(example_answer(examples_toml, "test.txt", Part::One), Ok(Some(Answer::Number(24000))));
+        assert_eq!(example_answer(examples_toml, "test.txt", Part::Two), Ok(None));
+        assert_eq!(example_answer(examples_toml, "test2.txt", Part::Two), Ok(Some(Answer::Text(String::from("CMZ")))));
+        assert!(example_answer("[\"test.txt\"]\npart_1 = 1.5\n", "test.txt", Part::One).is_err())
+    }
+
+    #[test]
+    fn test_same_answer() {
+        let expected = Answer::Text(String::from("##\n# #"));
+        assert!(same_answer(&expected, &Answer::Text(String::from("## \n# #  "))));
+        assert!(!same_answer(&expected, &Answer::Text(String::from("##\n###"))));
+        assert!(!same_answer(&Answer::Number(1), &Answer::Text(String::from("1"))))
+    }
+}
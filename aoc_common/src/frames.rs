@@ -0,0 +1,355 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::grid::Grid;
+use crate::solution::{Part, Solution};
+use crate::sparse_grid::{Bounds, Legend, SparseGrid};
+
+/// One picture of a simulation, a symbol per cell, with the position of its upper left corner
+/// so that frames of different sizes can be lined up. Frames that are views of a moving
+/// window, rather than of a part of the plane, simply all start at `(0, 0)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub origin: (isize, isize),
+    pub cells: Grid<char>,
+}
+
+impl Frame {
+    pub fn new(cells: Grid<char>) -> Self {
+        Frame { origin: (0, 0), cells }
+    }
+
+    /// The bounding box of `grid` drawn with `legend`.
+    pub fn from_sparse<T: PartialEq>(grid: &SparseGrid<T>, legend: &Legend<T>) -> Self {
+        match grid.bounds() {
+            None => Frame::new(Grid::new(0, 0, ' ')),
+            Some(bounds) => Frame::from_viewport(grid, bounds, legend),
+        }
+    }
+
+    /// The part of `grid` inside `viewport` drawn with `legend`.
+    pub fn from_viewport<T: PartialEq>(grid: &SparseGrid<T>, viewport: Bounds, legend: &Legend<T>) -> Self {
+        let ((min_row, min_col), (max_row, max_col)) = viewport;
+        let n_rows = (max_row - min_row + 1).max(0) as usize;
+        let n_cols = (max_col - min_col + 1).max(0) as usize;
+        let cells = Grid::from_fn(n_rows, n_cols, |(row, col)| {
+            legend.symbol(grid.get((min_row + row as isize, min_col + col as isize)))
+        });
+        Frame { origin: (min_row, min_col), cells }
+    }
+
+    /// The symbol at `pos` on the plane, `None` outside the frame.
+    pub fn get(&self, (row, col): (isize, isize)) -> Option<char> {
+        let (row, col) = (row - self.origin.0, col - self.origin.1);
+        if row < 0 || col < 0 {
+            return None;
+        }
+        self.cells.get((row as usize, col as usize)).copied()
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        (self.cells.n_rows() > 0 && self.cells.n_cols() > 0).then(|| {
+            let (row, col) = self.origin;
+            ((row, col), (row + self.cells.n_rows() as isize - 1, col + self.cells.n_cols() as isize - 1))
+        })
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.cells)
+    }
+}
+
+/// The smallest part of the plane that holds all of the frames, `None` if they are all empty.
+pub fn canvas(frames: &[Frame]) -> Option<Bounds> {
+    frames.iter().filter_map(Frame::bounds).reduce(|((min_row, min_col), (max_row, max_col)), ((r0, c0), (r1, c1))| {
+        ((min_row.min(r0), min_col.min(c0)), (max_row.max(r1), max_col.max(c1)))
+    })
+}
+
+/// Collects the frames of a simulation as it runs. Solving a puzzle uses one that is off, which
+/// never draws anything, so the drawing only costs when someone is watching.
+#[derive(Debug)]
+pub struct Recorder {
+    keep: Keep,
+    n_steps: usize,
+    // Whether the frame of the latest step was kept, so that `finish` doesn't repeat it
+    kept_latest: bool,
+    frames: Vec<Frame>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Keep {
+    Nothing,
+    Every(usize),
+    Last,
+}
+
+impl Recorder {
+    pub fn off() -> Self {
+        Recorder::keeping(Keep::Nothing)
+    }
+
+    /// Keeps the first frame and every `n`th after it, as well as the last one.
+    pub fn every(n: usize) -> Self {
+        Recorder::keeping(Keep::Every(n.max(1)))
+    }
+
+    /// Only keeps the last frame, for a still of the end result.
+    pub fn last_only() -> Self {
+        Recorder::keeping(Keep::Last)
+    }
+
+    fn keeping(keep: Keep) -> Self {
+        Recorder { keep, n_steps: 0, kept_latest: false, frames: vec![] }
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.keep != Keep::Nothing
+    }
+
+    /// One step of the simulation, drawn by `draw` if the frame is kept.
+    pub fn record(&mut self, draw: impl FnOnce() -> Frame) {
+        self.kept_latest = matches!(self.keep, Keep::Every(n) if self.n_steps.is_multiple_of(n));
+        if self.kept_latest {
+            self.frames.push(draw());
+        }
+        self.n_steps += 1;
+    }
+
+    /// The end of the simulation, which is always kept unless the recorder is off.
+    pub fn finish(&mut self, draw: impl FnOnce() -> Frame) {
+        if self.is_on() && !self.kept_latest {
+            self.frames.push(draw());
+            self.kept_latest = true;
+        }
+    }
+
+    pub fn into_frames(self) -> Vec<Frame> {
+        self.frames
+    }
+}
+
+/// A `Solution` whose simulation can be watched, frame by frame.
+pub trait Visualize: Solution {
+    /// Runs the simulation of `part`, recording its steps with `recorder`.
+    fn simulate(input: &Self::Input, part: Part, recorder: &mut Recorder);
+
+    /// The colours of the symbols the frames are drawn with.
+    fn palette() -> Palette;
+}
+
+pub type Rgb = [u8; 3];
+
+/// Colours for the symbols of the frames, when they are written as images. Positions outside a
+/// frame get the background colour, and symbols without one of their own are light grey.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    background: Rgb,
+    colours: Vec<(char, Rgb)>,
+}
+
+const UNKNOWN: Rgb = [0xc0, 0xc0, 0xc0];
+
+impl Palette {
+    pub fn new(background: Rgb) -> Self {
+        Palette { background, colours: vec![] }
+    }
+
+    /// Gives `symbol` the colour `colour`, instead of the one it had.
+    pub fn with(mut self, symbol: char, colour: Rgb) -> Self {
+        self.colours.retain(|(known, _)| *known != symbol);
+        self.colours.push((symbol, colour));
+        self
+    }
+
+    pub fn colour(&self, symbol: Option<char>) -> Rgb {
+        match symbol {
+            None => self.background,
+            Some(symbol) => self.colours.iter().find(|(known, _)| *known == symbol).map_or(UNKNOWN, |(_, colour)| *colour),
+        }
+    }
+
+    /// The symbols that have a colour of their own, with it.
+    pub fn colours(&self) -> impl Iterator<Item = (char, Rgb)> + '_ {
+        self.colours.iter().copied()
+    }
+
+    /// Changes the colours given as `symbol=rrggbb` separated by commas, e.g. `#=ffffff,o=c2b280`.
+    pub fn with_overrides(mut self, overrides: &str) -> Result<Self, String> {
+        for item in overrides.split(',').filter(|item| !item.is_empty()) {
+            let mut chars = item.chars();
+            let (Some(symbol), Some('=')) = (chars.next(), chars.next()) else {
+                return Err(format!("Expected symbol=rrggbb, found {item:?}"));
+            };
+            let hex = chars.as_str();
+            let colour = (hex.len() == 6 && hex.is_ascii())
+                .then(|| [0, 2, 4].map(|ind| u8::from_str_radix(&hex[ind..ind + 2], 16).ok()))
+                .and_then(|[r, g, b]| Some([r?, g?, b?]))
+                .ok_or_else(|| format!("Expected a colour as rrggbb, found {hex:?}"))?;
+            self = self.with(symbol, colour);
+        }
+        Ok(self)
+    }
+
+    // Every colour a picture can have, the background first
+    fn all_colours(&self) -> Vec<Rgb> {
+        let mut all = vec![self.background, UNKNOWN];
+        all.extend(self.colours.iter().map(|(_, colour)| *colour));
+        all
+    }
+}
+
+/// Writes the frames as text, one after the other with a blank line between them.
+pub fn write_ascii(frames: &[Frame], out: &mut impl Write) -> io::Result<()> {
+    for (ind, frame) in frames.iter().enumerate() {
+        if ind > 0 {
+            writeln!(out)?;
+        }
+        write!(out, "{frame}")?;
+    }
+    Ok(())
+}
+
+// The colour of every pixel of `frame` placed on `canvas`, row by row, with each cell `scale`
+// pixels wide and high
+fn pixels<T: Copy>(frame: &Frame, canvas: Bounds, scale: usize, colour: impl Fn(Option<char>) -> T) -> Vec<T> {
+    let ((min_row, min_col), (max_row, max_col)) = canvas;
+    let mut pixels = vec![];
+    for row in min_row..=max_row {
+        let line: Vec<T> = (min_col..=max_col)
+            .flat_map(|col| std::iter::repeat_n(colour(frame.get((row, col))), scale))
+            .collect();
+        for _ in 0..scale {
+            pixels.extend_from_slice(&line);
+        }
+    }
+    pixels
+}
+
+fn size(canvas: Bounds, scale: usize) -> (usize, usize) {
+    let ((min_row, min_col), (max_row, max_col)) = canvas;
+    (((max_col - min_col + 1) as usize) * scale, ((max_row - min_row + 1) as usize) * scale)
+}
+
+/// Writes `frame`, placed on `canvas`, as a binary PPM image.
+pub fn write_ppm(frame: &Frame, canvas: Bounds, palette: &Palette, scale: usize, out: &mut impl Write) -> io::Result<()> {
+    let (width, height) = size(canvas, scale);
+    write!(out, "P6\n{width} {height}\n255\n")?;
+    let rgb: Vec<u8> = pixels(frame, canvas, scale, |symbol| palette.colour(symbol)).concat();
+    out.write_all(&rgb)
+}
+
+/// Writes the frames as an animated GIF that loops forever, showing each for `delay_ms`.
+pub fn write_gif(frames: &[Frame], palette: &Palette, scale: usize, delay_ms: u16, out: &mut impl Write) -> io::Result<()> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidInput, message);
+    let canvas = canvas(frames).ok_or_else(|| invalid(String::from("There is nothing to draw")))?;
+    let (width, height) = size(canvas, scale);
+    let (Ok(gif_width), Ok(gif_height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(invalid(format!("A GIF can be at most 65535 pixels wide and high, not {width}x{height}")));
+    };
+    let colours = palette.all_colours();
+    if colours.len() > 256 {
+        return Err(invalid(String::from("A GIF can have at most 256 colours")));
+    }
+    let index = |symbol: Option<char>| {
+        let colour = palette.colour(symbol);
+        colours.iter().position(|known| *known == colour).unwrap() as u8
+    };
+    let mut encoder = gif::Encoder::new(out, gif_width, gif_height, &colours.concat()).map_err(io::Error::other)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+    for frame in frames {
+        let mut gif_frame = gif::Frame::from_indexed_pixels(gif_width, gif_height, pixels(frame, canvas, scale, index), None);
+        // The delay is in hundredths of a second
+        gif_frame.delay = delay_ms / 10;
+        encoder.write_frame(&gif_frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(origin: (isize, isize), text: &str) -> Frame {
+        Frame { origin, cells: Grid::parse(text, "", Some).unwrap() }
+    }
+
+    #[test]
+    fn test_recorder() {
+        let mut recorder = Recorder::off();
+        recorder.record(|| panic!("An idle recorder doesn't draw"));
+        recorder.finish(|| panic!("An idle recorder doesn't draw"));
+        assert!(recorder.into_frames().is_empty());
+
+        let steps = |mut recorder: Recorder| {
+            for step in 0..5 {
+                recorder.record(|| frame((0, 0), &step.to_string()));
+            }
+            recorder.finish(|| frame((0, 0), "4"));
+            recorder.into_frames().iter().map(Frame::to_string).collect::<String>()
+        };
+        assert_eq!(steps(Recorder::every(1)), "0\n1\n2\n3\n4\n");
+        assert_eq!(steps(Recorder::every(3)), "0\n3\n4\n");
+        assert_eq!(steps(Recorder::every(2)), "0\n2\n4\n");
+        assert_eq!(steps(Recorder::last_only()), "4\n")
+    }
+
+    #[test]
+    fn test_frames() {
+        let sparse: SparseGrid<char> = [((-1, 2), '#'), ((0, 4), 'o')].into_iter().collect();
+        let legend = Legend::new('.', "air").with('#', '#', "rock");
+        let sparse_frame = Frame::from_sparse(&sparse, &legend);
+        assert_eq!(sparse_frame, frame((-1, 2), "#..\n..?"));
+        assert_eq!(sparse_frame.get((0, 4)), Some('?'));
+        assert_eq!(sparse_frame.get((0, 5)), None);
+        let frames = [sparse_frame, frame((1, 0), "ab")];
+        assert_eq!(canvas(&frames), Some(((-1, 0), (1, 4))));
+        assert_eq!(canvas(&[Frame::from_sparse(&SparseGrid::new(), &legend)]), None);
+        let mut ascii = vec![];
+        write_ascii(&frames, &mut ascii).unwrap();
+        assert_eq!(String::from_utf8(ascii).unwrap(), "#..\n..?\n\nab\n")
+    }
+
+    #[test]
+    fn test_palette() {
+        let palette = Palette::new([0, 0, 0]).with('#', [1, 2, 3]);
+        assert_eq!(palette.colour(None), [0, 0, 0]);
+        assert_eq!(palette.colour(Some('#')), [1, 2, 3]);
+        assert_eq!(palette.colour(Some('o')), UNKNOWN);
+        let palette = palette.with_overrides("#=ffffff,o=C2b280").unwrap();
+        assert_eq!(palette.colour(Some('#')), [255, 255, 255]);
+        assert_eq!(palette.colour(Some('o')), [0xc2, 0xb2, 0x80]);
+        assert!(Palette::new([0, 0, 0]).with_overrides("#ffffff").is_err());
+        assert!(Palette::new([0, 0, 0]).with_overrides("#=fffff").is_err());
+        assert!(Palette::new([0, 0, 0]).with_overrides("#=gggggg").is_err())
+    }
+
+    #[test]
+    fn test_images() {
+        let palette = Palette::new([0, 0, 0]).with('#', [255, 0, 0]);
+        let frames = [frame((0, 0), "#."), frame((1, 1), "#")];
+        let canvas = canvas(&frames).unwrap();
+        let mut ppm = vec![];
+        write_ppm(&frames[1], canvas, &palette, 2, &mut ppm).unwrap();
+        let (header, rgb) = ppm.split_at(11);
+        assert_eq!(header, b"P6\n4 4\n255\n");
+        // The frame only covers the lower right quarter, the rest is background
+        assert_eq!(rgb.len(), 4 * 4 * 3);
+        assert_eq!(&rgb[..3], &[0, 0, 0]);
+        assert_eq!(&rgb[rgb.len() - 3..], &[255, 0, 0]);
+
+        let mut gif = vec![];
+        write_gif(&frames, &palette, 3, 100, &mut gif).unwrap();
+        let mut decoder = gif::DecodeOptions::new().read_info(gif.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (6, 6));
+        let mut n_frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            n_frames += 1;
+        }
+        assert_eq!(n_frames, 2);
+        assert!(write_gif(&[], &palette, 1, 100, &mut vec![]).is_err())
+    }
+}
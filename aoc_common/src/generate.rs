@@ -0,0 +1,59 @@
+//! Random puzzle inputs, for trying the days on other inputs than our own. The same seed and size
+//! always give the same input.
+
+pub use rand::rngs::StdRng;
+pub use rand::seq::SliceRandom;
+pub use rand::Rng;
+
+use rand::SeedableRng;
+
+pub const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+pub const UPPERCASE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Writes an input of about `size` lines, elves, monkeys or whatever the day's input is made of.
+pub type Generator = fn(&mut StdRng, usize) -> String;
+
+pub fn rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// `n` lines written by `line`, each ending in a newline.
+pub fn lines(rng: &mut StdRng, n: usize, mut line: impl FnMut(&mut StdRng, usize) -> String) -> String {
+    (0..n).map(|ind| line(rng, ind) + "\n").collect()
+}
+
+/// A rectangle of `n_rows` by `n_cols` characters, each written by `cell` from its `(row, col)`.
+pub fn grid(rng: &mut StdRng, n_rows: usize, n_cols: usize, mut cell: impl FnMut(&mut StdRng, (usize, usize)) -> char) -> String {
+    lines(rng, n_rows, |rng, row| (0..n_cols).map(|col| cell(rng, (row, col))).collect())
+}
+
+/// `n` different names of `len` characters from `alphabet`, none of them in `taken`.
+pub fn names(rng: &mut StdRng, n: usize, len: u32, alphabet: &[u8], taken: &[&str]) -> Vec<String> {
+    let n_possible = (alphabet.len() as u64).pow(len) as usize;
+    assert!(n + taken.len() <= n_possible, "There are only {n_possible} names of {len} characters");
+    let mut names: Vec<String> = vec![];
+    while names.len() < n {
+        let name: String = (0..len).map(|_| *alphabet.choose(rng).unwrap() as char).collect();
+        if !taken.contains(&name.as_str()) && !names.contains(&name) {
+            names.push(name)
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let dice = |seed| lines(&mut rng(seed), 3, |rng, _| rng.gen_range(1..=6).to_string());
+        assert_eq!(dice(7), dice(7));
+        assert_eq!(dice(7).lines().count(), 3);
+        let checkers = grid(&mut rng(1), 2, 3, |_, (row, col)| if (row + col) % 2 == 0 { '#' } else { '.' });
+        assert_eq!(checkers, "#.#\n.#.\n");
+        let names = names(&mut rng(1), 3, 1, b"abcd", &["a"]);
+        assert_eq!(names.len(), 3);
+        assert!(!names.contains(&String::from("a")))
+    }
+}
@@ -0,0 +1,338 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::direction::Direction;
+
+/// Inclusive `((min_row, min_col), (max_row, max_col))` of the points, `None` if there are none.
+pub fn bounding_box<'a>(
+    points: impl IntoIterator<Item = &'a (isize, isize)>,
+) -> Option<((isize, isize), (isize, isize))> {
+    points.into_iter().fold(None, |acc, (row, col)| match acc {
+        None => Some(((*row, *col), (*row, *col))),
+        Some(((min_row, min_col), (max_row, max_col))) => Some((
+            (min_row.min(*row), min_col.min(*col)),
+            (max_row.max(*row), max_col.max(*col)),
+        )),
+    })
+}
+
+/// Number of cells covered by the inclusive bounding box of the points.
+pub fn bounding_box_area<'a>(points: impl IntoIterator<Item = &'a (isize, isize)>) -> usize {
+    match bounding_box(points) {
+        Some(((min_row, min_col), (max_row, max_col))) => {
+            ((max_row - min_row + 1) * (max_col - min_col + 1)) as usize
+        }
+        None => 0,
+    }
+}
+
+/// A position in the plane. Tuples convert in order, so grid code can use `(row, col)` as `(x, y)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// The difference between two `Point2`s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Vec2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// A position in space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+/// The difference between two `Point3`s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Vec3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+// Point + Vec, Point - Vec, Point - Point, Vec + Vec, Vec - Vec, -Vec and Vec * scalar,
+// coordinate by coordinate
+macro_rules! impl_ops {
+    ($point:ident, $vec:ident, $($coord:ident),+) => {
+        impl Add<$vec> for $point {
+            type Output = $point;
+
+            fn add(self, other: $vec) -> $point {
+                $point { $($coord: self.$coord + other.$coord),+ }
+            }
+        }
+
+        impl AddAssign<$vec> for $point {
+            fn add_assign(&mut self, other: $vec) {
+                *self = *self + other
+            }
+        }
+
+        impl Sub<$vec> for $point {
+            type Output = $point;
+
+            fn sub(self, other: $vec) -> $point {
+                $point { $($coord: self.$coord - other.$coord),+ }
+            }
+        }
+
+        impl SubAssign<$vec> for $point {
+            fn sub_assign(&mut self, other: $vec) {
+                *self = *self - other
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $vec;
+
+            fn sub(self, other: $point) -> $vec {
+                $vec { $($coord: self.$coord - other.$coord),+ }
+            }
+        }
+
+        impl Add for $vec {
+            type Output = $vec;
+
+            fn add(self, other: $vec) -> $vec {
+                $vec { $($coord: self.$coord + other.$coord),+ }
+            }
+        }
+
+        impl Sub for $vec {
+            type Output = $vec;
+
+            fn sub(self, other: $vec) -> $vec {
+                $vec { $($coord: self.$coord - other.$coord),+ }
+            }
+        }
+
+        impl Neg for $vec {
+            type Output = $vec;
+
+            fn neg(self) -> $vec {
+                $vec { $($coord: -self.$coord),+ }
+            }
+        }
+
+        impl Mul<isize> for $vec {
+            type Output = $vec;
+
+            fn mul(self, factor: isize) -> $vec {
+                $vec { $($coord: self.$coord * factor),+ }
+            }
+        }
+
+        impl $point {
+            pub fn manhattan(self, other: $point) -> isize {
+                (other - self).manhattan()
+            }
+
+            pub fn chebyshev(self, other: $point) -> isize {
+                (other - self).chebyshev()
+            }
+
+            /// One step towards `target`, diagonally unless they are in line, as the knots of a rope move.
+            pub fn step_towards(self, target: $point) -> $point {
+                self + (target - self).signum()
+            }
+        }
+
+        impl $vec {
+            pub fn manhattan(self) -> isize {
+                0 $(+ self.$coord.abs())+
+            }
+
+            pub fn chebyshev(self) -> isize {
+                0 $(.max(self.$coord.abs()))+
+            }
+
+            /// Every coordinate replaced by -1, 0 or 1.
+            pub fn signum(self) -> $vec {
+                $vec { $($coord: self.$coord.signum()),+ }
+            }
+        }
+    };
+}
+
+impl_ops!(Point2, Vec2, x, y);
+impl_ops!(Point3, Vec3, x, y, z);
+
+impl Point2 {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Point2 { x, y }
+    }
+
+    /// The 4 points next to this one: -x, +x, -y, +y.
+    pub fn neighbours_4(self) -> [Point2; 4] {
+        Vec2::UNITS.map(|delta| self + delta)
+    }
+
+    /// The 8 points around this one, diagonals included.
+    pub fn neighbours_8(self) -> [Point2; 8] {
+        [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)].map(|delta| self + Vec2::from(delta))
+    }
+}
+
+impl Vec2 {
+    /// One step along each axis, in both directions.
+    pub const UNITS: [Vec2; 4] = [Vec2::new(-1, 0), Vec2::new(1, 0), Vec2::new(0, -1), Vec2::new(0, 1)];
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Vec2 { x, y }
+    }
+
+    /// Rotated a quarter turn counterclockwise with y up, which is also a left turn on a
+    /// `(row, col)` grid with rows growing downwards, like `Direction::turn_left`.
+    pub fn turn_left(self) -> Vec2 {
+        Vec2::new(-self.y, self.x)
+    }
+
+    pub fn turn_right(self) -> Vec2 {
+        Vec2::new(self.y, -self.x)
+    }
+}
+
+impl Point3 {
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Point3 { x, y, z }
+    }
+
+    /// The 6 points that share a face with this one.
+    pub fn neighbours_6(self) -> [Point3; 6] {
+        Vec3::UNITS.map(|delta| self + delta)
+    }
+}
+
+impl Vec3 {
+    /// One step along each axis, in both directions.
+    pub const UNITS: [Vec3; 6] = [
+        Vec3::new(-1, 0, 0),
+        Vec3::new(1, 0, 0),
+        Vec3::new(0, -1, 0),
+        Vec3::new(0, 1, 0),
+        Vec3::new(0, 0, -1),
+        Vec3::new(0, 0, 1),
+    ];
+
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    /// Quarter turns counterclockwise, seen from the positive end of the axis looking at the origin.
+    pub fn rotate_x(self) -> Vec3 {
+        Vec3::new(self.x, -self.z, self.y)
+    }
+
+    pub fn rotate_y(self) -> Vec3 {
+        Vec3::new(self.z, self.y, -self.x)
+    }
+
+    pub fn rotate_z(self) -> Vec3 {
+        Vec3::new(-self.y, self.x, self.z)
+    }
+}
+
+impl From<(isize, isize)> for Point2 {
+    fn from((x, y): (isize, isize)) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl From<Point2> for (isize, isize) {
+    fn from(point: Point2) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl From<(isize, isize)> for Vec2 {
+    fn from((x, y): (isize, isize)) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+/// One step as `(delta_row, delta_col)`.
+impl From<Direction> for Vec2 {
+    fn from(direction: Direction) -> Self {
+        direction.delta().into()
+    }
+}
+
+impl From<(isize, isize, isize)> for Point3 {
+    fn from((x, y, z): (isize, isize, isize)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounding_box() {
+        let points = [(1, 5), (-2, 3), (4, -1)];
+        assert_eq!(bounding_box(&points), Some(((-2, -1), (4, 5))));
+        assert_eq!(bounding_box_area(&points), 7 * 7);
+        assert_eq!(bounding_box(&[]), None)
+    }
+
+    #[test]
+    fn test_distances() {
+        let (a, b) = (Point2::new(8, 7), Point2::new(2, 10));
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(Point3::new(1, 2, 3).manhattan(Point3::new(-1, 2, 4)), 3);
+        assert_eq!(Point3::new(1, 2, 3).chebyshev(Point3::new(-1, 2, 4)), 2)
+    }
+
+    #[test]
+    fn test_ops() {
+        let point = Point2::new(3, -2);
+        let delta = Vec2::new(1, 4);
+        assert_eq!(point + delta, Point2::new(4, 2));
+        assert_eq!(point + delta - point, delta);
+        assert_eq!(point - delta, point + -delta);
+        assert_eq!(delta * 3 - delta, delta + delta);
+        let mut moved = Point3::new(0, 0, 0);
+        moved += Vec3::new(1, 2, 3);
+        moved -= Vec3::new(0, 0, 3);
+        assert_eq!(moved, Point3::new(1, 2, 0))
+    }
+
+    #[test]
+    fn test_step_towards() {
+        let tail = Point2::new(0, 0);
+        assert_eq!(tail.step_towards(Point2::new(2, 1)), Point2::new(1, 1));
+        assert_eq!(tail.step_towards(Point2::new(0, -5)), Point2::new(0, -1));
+        assert_eq!(tail.step_towards(tail), tail);
+        assert_eq!(Point3::new(1, 1, 1).step_towards(Point3::new(3, 1, 0)), Point3::new(2, 1, 0))
+    }
+
+    #[test]
+    fn test_turns() {
+        for direction in Direction::ALL {
+            assert_eq!(Vec2::from(direction).turn_left(), Vec2::from(direction.turn_left()));
+            assert_eq!(Vec2::from(direction).turn_right(), Vec2::from(direction.turn_right()));
+        }
+        let v = Vec3::new(1, 2, 3);
+        assert_eq!(Vec3::new(1, 0, 0).rotate_z(), Vec3::new(0, 1, 0));
+        assert_eq!(Vec3::new(0, 1, 0).rotate_x(), Vec3::new(0, 0, 1));
+        assert_eq!(Vec3::new(0, 0, 1).rotate_y(), Vec3::new(1, 0, 0));
+        assert_eq!(v.rotate_x().rotate_x().rotate_x().rotate_x(), v);
+        assert_eq!(v.rotate_y().rotate_y(), Vec3::new(-1, 2, -3))
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let point = Point2::new(5, 5);
+        assert!(point.neighbours_4().iter().all(|next| next.manhattan(point) == 1));
+        assert!(point.neighbours_8().iter().all(|next| next.chebyshev(point) == 1));
+        let cube = Point3::new(0, 0, 0);
+        assert!(cube.neighbours_6().iter().all(|next| next.manhattan(cube) == 1));
+        assert_eq!(cube.neighbours_6().len(), 6)
+    }
+}
@@ -0,0 +1,226 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::direction::Direction;
+use crate::parse::{grid_of, ParseError};
+
+/// A rectangular grid indexed by `(row, col)`, with row 0 at the top.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    n_rows: usize,
+    n_cols: usize,
+    // Row by row
+    cells: Vec<T>,
+}
+
+const DELTAS_4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const DELTAS_8: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+impl<T> Grid<T> {
+    pub fn new(n_rows: usize, n_cols: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid { n_rows, n_cols, cells: vec![fill; n_rows * n_cols] }
+    }
+
+    pub fn from_fn(n_rows: usize, n_cols: usize, mut cell: impl FnMut((usize, usize)) -> T) -> Self {
+        let cells = (0..n_rows).flat_map(|row| (0..n_cols).map(move |col| (row, col))).map(&mut cell).collect();
+        Grid { n_rows, n_cols, cells }
+    }
+
+    /// Panics unless all rows are as long.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let n_rows = rows.len();
+        let n_cols = rows.first().map_or(0, Vec::len);
+        assert!(rows.iter().all(|row| row.len() == n_cols), "the rows of a grid must be as long");
+        Grid { n_rows, n_cols, cells: rows.into_iter().flatten().collect() }
+    }
+
+    /// Parses a map of characters, where `cell` returns `None` for characters that don't belong.
+    /// All rows must be as long as the first.
+    pub fn parse(input: &str, expected: &str, cell: impl Fn(char) -> Option<T>) -> Result<Self, ParseError> {
+        let rows = grid_of(input, expected, cell)?;
+        let n_cols = rows.first().map_or(0, Vec::len);
+        if let Some(ind) = rows.iter().position(|row| row.len() != n_cols) {
+            let column = rows[ind].len().min(n_cols) + 1;
+            return Err(ParseError::new(ind + 1, column, "", &format!("a row of {n_cols} cells like the first")));
+        }
+        if n_cols == 0 {
+            return Err(ParseError::new(1, 1, "", expected));
+        }
+        Ok(Grid::from_rows(rows))
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.n_rows
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.n_cols
+    }
+
+    pub fn contains(&self, (row, col): (usize, usize)) -> bool {
+        row < self.n_rows && col < self.n_cols
+    }
+
+    /// The cell at `pos`, `None` outside the grid.
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[pos.0 * self.n_cols + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.n_cols + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// `pos` moved by `(delta_row, delta_col)`, if that is still in the grid.
+    pub fn offset(&self, (row, col): (usize, usize), (delta_row, delta_col): (isize, isize)) -> Option<(usize, usize)> {
+        let pos = (row.checked_add_signed(delta_row)?, col.checked_add_signed(delta_col)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// One step from `pos` in `direction`, if that is still in the grid.
+    pub fn step(&self, pos: (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        self.offset(pos, direction.delta())
+    }
+
+    /// The up to 4 positions next to `pos`: up, down, left, right.
+    pub fn neighbours_4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DELTAS_4.into_iter().filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    /// The up to 8 positions around `pos`, diagonals included, row by row.
+    pub fn neighbours_8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DELTAS_8.into_iter().filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.n_cols..(row + 1) * self.n_cols]
+    }
+
+    pub fn col(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(col < self.n_cols, "column {col} is outside the grid");
+        self.cells.iter().skip(col).step_by(self.n_cols)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on 0, and a grid without columns has no cells anyway
+        self.cells.chunks(self.n_cols.max(1))
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.n_cols).map(|col| self.col(col))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let n_cols = self.n_cols;
+        (0..self.n_rows).flat_map(move |row| (0..n_cols).map(move |col| (row, col)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The first position, row by row, of a cell that satisfies `pred`.
+    pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter().find(|(_, cell)| pred(cell)).map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { n_rows: self.n_rows, n_cols: self.n_cols, cells: self.cells.iter().map(f).collect() }
+    }
+
+    /// Rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.n_cols, self.n_rows, |(row, col)| self[(col, row)].clone())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        self.get(pos).unwrap_or_else(|| panic!("{pos:?} is outside the {}x{} grid", self.n_rows, self.n_cols))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        let (n_rows, n_cols) = (self.n_rows, self.n_cols);
+        self.get_mut(pos).unwrap_or_else(|| panic!("{pos:?} is outside the {n_rows}x{n_cols} grid"))
+    }
+}
+
+/// One line per row, the cells written next to each other.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse("123\n456\n", "a digit", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits();
+        assert_eq!((grid.n_rows(), grid.n_cols()), (2, 3));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.to_string(), "123\n456\n");
+        let err = Grid::parse("123\n45\n", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(err, ParseError::new(2, 3, "", "a row of 3 cells like the first"));
+        assert!(Grid::parse("12x\n", "a digit", |c| c.to_digit(10)).is_err())
+    }
+
+    #[test]
+    fn test_get() {
+        let grid = digits();
+        assert_eq!(grid.get((0, 2)), Some(&3));
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.step((0, 0), Direction::Right), Some((0, 1)));
+        assert_eq!(grid.find(|cell| *cell > 4), Some((1, 1)))
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits();
+        assert_eq!(grid.neighbours_4((0, 0)).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbours_4((1, 1)).count(), 3);
+        assert_eq!(grid.neighbours_8((0, 1)).collect::<Vec<_>>(), vec![(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)])
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = digits();
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.col(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(grid.col(0).rev().copied().collect::<Vec<_>>(), vec![4, 1]);
+        assert_eq!(grid.cols().count(), 3);
+        let transposed = grid.transpose();
+        assert_eq!(transposed.to_string(), "14\n25\n36\n");
+        assert_eq!(transposed.transpose(), grid);
+        assert_eq!(grid.map(|digit| digit * 2).row(0), &[2, 4, 6])
+    }
+}
@@ -0,0 +1,67 @@
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Where a day reads its puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `input.txt` in the day's directory
+    Bundled,
+    /// The example from the puzzle text, `test.txt` in the day's directory
+    Example,
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn read(&self, day_dir: &Path) -> io::Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            _ => std::fs::read_to_string(self.path(day_dir).unwrap()),
+        }
+    }
+
+    /// For messages: the file the input is read from, or stdin.
+    pub fn name(&self, day_dir: &Path) -> String {
+        match self.path(day_dir) {
+            Some(path) => path.display().to_string(),
+            None => String::from("stdin"),
+        }
+    }
+
+    /// The file the input is read from, `None` for stdin.
+    pub fn path(&self, day_dir: &Path) -> Option<PathBuf> {
+        match self {
+            InputSource::Bundled => Some(day_dir.join("input.txt")),
+            InputSource::Example => Some(example_path(day_dir)),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+}
+
+// Days with several examples have them numbered from test1.txt
+fn example_path(day_dir: &Path) -> PathBuf {
+    let path = day_dir.join("test.txt");
+    if path.exists() {
+        path
+    } else {
+        day_dir.join("test1.txt")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        let day_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../d9");
+        assert_eq!(InputSource::Bundled.path(&day_dir), Some(day_dir.join("input.txt")));
+        assert_eq!(InputSource::Example.path(&day_dir), Some(day_dir.join("test1.txt")));
+        assert_eq!(InputSource::Stdin.path(&day_dir), None);
+    }
+}
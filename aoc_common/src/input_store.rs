@@ -0,0 +1,205 @@
+//! Puzzle inputs kept in a per-user cache instead of in the repo, fetched from
+//! Advent of Code the first time they are needed.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// The year the puzzles in this repo are from.
+pub const YEAR: u16 = 2022;
+
+/// Where missing inputs come from.
+pub trait Fetch {
+    fn fetch(&self, year: u16, day: u8) -> io::Result<String>;
+}
+
+/// Downloads inputs from `<base_url>/<year>/day/<day>/input`, the way adventofcode.com serves
+/// them to a logged in user.
+pub struct HttpFetch {
+    base_url: String,
+    /// The value of the `session` cookie
+    session: String,
+}
+
+impl HttpFetch {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        HttpFetch { base_url: base_url.trim_end_matches('/').to_string(), session: session.to_string() }
+    }
+
+    /// adventofcode.com (or `$AOC_URL`) with the session cookie in `$AOC_SESSION`, if that is set.
+    pub fn from_env() -> Option<Self> {
+        let session = std::env::var("AOC_SESSION").ok()?;
+        let base_url = std::env::var("AOC_URL").unwrap_or_else(|_| String::from("https://adventofcode.com"));
+        Some(HttpFetch::new(&base_url, session.trim()))
+    }
+}
+
+impl Fetch for HttpFetch {
+    fn fetch(&self, year: u16, day: u8) -> io::Result<String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", "github.com/svantelidman/AoC2022")
+            .call()
+            .map_err(|err| io::Error::other(format!("{url}: {err}")))?;
+        response.into_string()
+    }
+}
+
+/// Inputs cached as `<dir>/<year>/<day>/input.txt`. Inputs made up of sections separated by
+/// blank lines (like d22's map and instructions) also get a file per section next to it.
+pub struct InputStore {
+    dir: PathBuf,
+    fetch: Option<Box<dyn Fetch>>,
+}
+
+impl InputStore {
+    pub fn new(dir: PathBuf, fetch: Option<Box<dyn Fetch>>) -> Self {
+        InputStore { dir, fetch }
+    }
+
+    /// `$AOC_CACHE_DIR`, or `aoc` in the user's cache directory, fetching with `HttpFetch::from_env`.
+    pub fn from_env() -> Self {
+        let dir = std::env::var_os("AOC_CACHE_DIR").map(PathBuf::from).unwrap_or_else(|| {
+            dirs::cache_dir().unwrap_or_else(std::env::temp_dir).join("aoc")
+        });
+        let fetch = HttpFetch::from_env().map(|fetch| Box::new(fetch) as Box<dyn Fetch>);
+        InputStore::new(dir, fetch)
+    }
+
+    pub fn day_dir(&self, year: u16, day: u8) -> PathBuf {
+        self.dir.join(year.to_string()).join(day.to_string())
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.day_dir(year, day).join("input.txt")
+    }
+
+    /// The cached input, fetched and stored first if it isn't cached yet. `sections` names the
+    /// section files to split it into, if any.
+    pub fn input(&self, year: u16, day: u8, sections: &[&str]) -> io::Result<String> {
+        let path = self.path(year, day);
+        if path.exists() {
+            return fs::read_to_string(path);
+        }
+        let fetch = self.fetch.as_ref().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "not cached, and AOC_SESSION isn't set to fetch it")
+        })?;
+        let input = fetch.fetch(year, day)?;
+        self.insert(year, day, &input, sections)?;
+        Ok(input)
+    }
+
+    /// Caches `input`, e.g. to move an `input.txt` from the repo into the cache.
+    pub fn insert(&self, year: u16, day: u8, input: &str, sections: &[&str]) -> io::Result<()> {
+        let day_dir = self.day_dir(year, day);
+        fs::create_dir_all(&day_dir)?;
+        if !sections.is_empty() {
+            let texts = split_sections(input, sections.len());
+            if texts.len() != sections.len() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("expected {} sections separated by blank lines, found {}", sections.len(), texts.len()),
+                ));
+            }
+            for (name, text) in sections.iter().zip(texts) {
+                fs::write(day_dir.join(format!("{name}.txt")), text)?;
+            }
+        }
+        // Written last, so that a failed split doesn't leave a cached input without its sections
+        fs::write(self.path(year, day), input)
+    }
+
+    /// One of the section files written by `insert`.
+    pub fn section(&self, year: u16, day: u8, name: &str) -> io::Result<String> {
+        fs::read_to_string(self.day_dir(year, day).join(format!("{name}.txt")))
+    }
+}
+
+/// Splits `input` at the first `n - 1` blank lines, so the last section may contain blank lines of its own.
+pub fn split_sections(input: &str, n: usize) -> Vec<&str> {
+    let mut sections = vec![];
+    let mut rest = input;
+    while sections.len() + 1 < n {
+        let Some(end) = blank_line(rest) else { break };
+        sections.push(&rest[..end.0]);
+        rest = &rest[end.1..];
+    }
+    sections.push(rest);
+    sections
+}
+
+// Where the first blank line starts and ends, keeping the newline that ends the section before it
+fn blank_line(text: &str) -> Option<(usize, usize)> {
+    let start = text.find("\n\n").map(|ind| ind + 1)?;
+    Some((start, start + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    // A fresh directory under the system temp directory
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc_input_store_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    struct CountingFetch(Rc<Cell<usize>>);
+
+    impl Fetch for CountingFetch {
+        fn fetch(&self, year: u16, day: u8) -> io::Result<String> {
+            self.0.set(self.0.get() + 1);
+            Ok(format!("{year} {day}\n\nmore\n"))
+        }
+    }
+
+    #[test]
+    fn test_split_sections() {
+        assert_eq!(split_sections("a\nb\n\nc\n", 2), vec!["a\nb\n", "c\n"]);
+        assert_eq!(split_sections("a\n\nb\n\nc\n", 2), vec!["a\n", "b\n\nc\n"]);
+        assert_eq!(split_sections("a\n", 2), vec!["a\n"]);
+        assert_eq!(split_sections("a\n\nb\n", 1), vec!["a\n\nb\n"])
+    }
+
+    #[test]
+    fn test_cache() {
+        let dir = temp_dir("cache");
+        let n_fetches = Rc::new(Cell::new(0));
+        let store = InputStore::new(dir.clone(), Some(Box::new(CountingFetch(n_fetches.clone()))));
+        assert_eq!(store.input(2022, 5, &["stacks", "moves"]).unwrap(), "2022 5\n\nmore\n");
+        assert_eq!(store.input(2022, 5, &["stacks", "moves"]).unwrap(), "2022 5\n\nmore\n");
+        assert_eq!(n_fetches.get(), 1);
+        assert_eq!(store.path(2022, 5), dir.join("2022/5/input.txt"));
+        assert_eq!(store.section(2022, 5, "moves").unwrap(), "more\n");
+        assert!(store.insert(2022, 6, "no sections\n", &["a", "b"]).is_err());
+        assert!(!store.path(2022, 6).exists());
+
+        let offline = InputStore::new(dir.clone(), None);
+        assert!(offline.input(2022, 5, &[]).is_ok());
+        assert_eq!(offline.input(2022, 7, &[]).unwrap_err().kind(), io::ErrorKind::NotFound);
+        fs::remove_dir_all(dir).unwrap()
+    }
+
+    #[test]
+    fn test_http_fetch() {
+        // A stand-in for adventofcode.com that answers one request
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/", server.server_addr().to_ip().unwrap());
+        let handle = std::thread::spawn(move || {
+            let request = server.recv().unwrap();
+            let cookie = request.headers().iter().find(|header| header.field.equiv("Cookie")).unwrap();
+            let seen = (request.url().to_string(), cookie.value.to_string());
+            request.respond(tiny_http::Response::from_string("1\n2\n")).unwrap();
+            seen
+        });
+        let fetch = HttpFetch::new(&base_url, "secret");
+        assert_eq!(fetch.fetch(2022, 1).unwrap(), "1\n2\n");
+        let (url, cookie) = handle.join().unwrap();
+        assert_eq!(url, "/2022/day/1/input");
+        assert_eq!(cookie, "session=secret")
+    }
+}
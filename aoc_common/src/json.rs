@@ -0,0 +1,94 @@
+//! Answers as JSON, for scripts and dashboards.
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::solution::{Answer, Part, Solved};
+
+/// One object per solved part:
+///
+/// ```json
+/// {"day": "d10", "part": 2, "answer": ["##..##..", ...], "parse_seconds": 0.0001,
+///  "solve_seconds": 0.00002, "input_sha256": "6f1c..."}
+/// ```
+///
+/// The answer is a number or a string, `null` for a part that isn't solved, and
+/// multi-line answers (the d10 CRT screen) are an array with one string per row.
+pub fn answer_objects(day_name: &str, input: &str, solved: &Solved) -> Vec<Value> {
+    let input_sha256 = input_hash(input);
+    solved
+        .answers
+        .iter()
+        .map(|(part, answer, solve_time)| {
+            json!({
+                "day": day_name,
+                "part": match part {
+                    Part::One => 1,
+                    Part::Two => 2,
+                },
+                "answer": answer_value(answer),
+                "parse_seconds": solved.parse_time.as_secs_f64(),
+                "solve_seconds": solve_time.as_secs_f64(),
+                "input_sha256": input_sha256,
+            })
+        })
+        .collect()
+}
+
+/// Prints `answer_objects` as JSON lines.
+pub fn print_answers(day_name: &str, input: &str, solved: &Solved) {
+    for object in answer_objects(day_name, input, solved) {
+        println!("{object}")
+    }
+}
+
+/// An answer as it is in the objects: a number, a string, an array of rows or `null`.
+pub fn answer_value(answer: &Answer) -> Value {
+    match answer {
+        Answer::Number(number) => json!(number),
+        Answer::Text(text) if text.contains('\n') => json!(text.lines().collect::<Vec<_>>()),
+        Answer::Text(text) => json!(text),
+        Answer::Missing => Value::Null,
+    }
+}
+
+/// Hex SHA-256 of the input, to tell which input the answers are for.
+pub fn input_hash(input: &str) -> String {
+    Sha256::digest(input.as_bytes()).iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_answer_objects() {
+        let solved = Solved {
+            parse_time: Duration::from_millis(2),
+            answers: vec![
+                (Part::One, Answer::Number(42), Duration::from_millis(500)),
+                (Part::Two, Answer::Text(String::from("#.\n.#\n")), Duration::ZERO),
+            ],
+        };
+        let objects = answer_objects("d10", "abc", &solved);
+        assert_eq!(
+            objects[0],
+            json!({
+                "day": "d10",
+                "part": 1,
+                "answer": 42,
+                "parse_seconds": 0.002,
+                "solve_seconds": 0.5,
+                "input_sha256": "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            })
+        );
+        assert_eq!(objects[1]["answer"], json!(["#.", ".#"]))
+    }
+
+    #[test]
+    fn test_answer_value() {
+        assert_eq!(answer_value(&Answer::Text(String::from("CMZ"))), json!("CMZ"));
+        assert_eq!(answer_value(&Answer::Missing), Value::Null)
+    }
+}
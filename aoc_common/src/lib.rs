@@ -0,0 +1,29 @@
+//! Code shared between the days: the `Solution` trait and running days from the
+//! command line (with JSON output), input parsing helpers, a 2D `Grid` and a `SparseGrid` for
+//! unbounded planes, frames for watching simulations (as text, PPM or GIF), random inputs, directions, simple
+//! geometry (bounding boxes of `(row, col)` coordinates, 2D/3D points and vectors) and searches (BFS, Dijkstra, A*, branch-and-bound) over a
+//! `SearchProblem`, and logging their diagnostics with `tracing`.
+
+pub mod args;
+pub mod direction;
+pub mod examples;
+pub mod frames;
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod input_store;
+pub mod json;
+pub mod logging;
+pub mod parse;
+pub mod runner;
+pub mod search;
+pub mod solution;
+pub mod sparse_grid;
+
+pub use direction::Direction;
+pub use geometry::{Point2, Point3, Vec2, Vec3};
+pub use grid::Grid;
+pub use parse::ParseError;
+pub use solution::{Answer, Day, Part, Solution, Solved, Stats};
+pub use sparse_grid::SparseGrid;
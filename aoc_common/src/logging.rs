@@ -0,0 +1,50 @@
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, Layer};
+
+/// The most detailed events that are logged for a number of `-v`: only warnings without any,
+/// `-v` for the search statistics and the like, `-vv` for what happens along the way (such as
+/// each layer of a search) and `-vvv` for everything, down to single states.
+pub fn level(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
+/// Logs the `tracing` events to stderr, so that they don't get mixed up with the answers, and
+/// as JSON lines to `log_file` if given. Both get the same events, see `level`.
+pub fn init(verbosity: u8, log_file: Option<&Path>) -> io::Result<()> {
+    let level = level(verbosity);
+    let file_layer = match log_file {
+        Some(path) => Some(fmt::layer().json().with_writer(Mutex::new(File::create(path)?)).with_filter(level)),
+        None => None,
+    };
+    // Only fails if there is a subscriber already, and then that one is used
+    let _ = tracing_subscriber::registry()
+        .with(fmt::layer().with_writer(io::stderr).with_filter(level))
+        .with(file_layer)
+        .try_init();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level() {
+        assert_eq!(level(0), LevelFilter::WARN);
+        assert_eq!(level(1), LevelFilter::INFO);
+        assert_eq!(level(2), LevelFilter::DEBUG);
+        assert_eq!(level(5), LevelFilter::TRACE)
+    }
+}
@@ -0,0 +1,309 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// Where and why the puzzle input couldn't be parsed. Lines and columns count from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The offending text, empty if the line or input ended too soon
+    pub text: String,
+    /// What should have been there
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, expected: &str) -> Self {
+        ParseError { line, column, text: String::from(text), expected: String::from(expected) }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: expected {}, ", self.line, self.column, self.expected)?;
+        if self.text.is_empty() {
+            write!(f, "found nothing")
+        } else {
+            write!(f, "found {:?}", self.text)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The input split into numbered lines, for parsers that read several lines per record.
+pub struct Lines<'a> {
+    lines: std::str::Lines<'a>,
+    line: usize,
+}
+
+impl<'a> Lines<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Lines { lines: input.lines(), line: 0 }
+    }
+
+    /// The lines of a section of the input that comes after `n_lines` other lines, numbered as
+    /// they are in the whole input.
+    pub fn after(n_lines: usize, section: &'a str) -> Self {
+        Lines { lines: section.lines(), line: n_lines }
+    }
+
+    /// The next line, or an error saying what was missing if the input ends here.
+    pub fn expect_line(&mut self, expected: &str) -> Result<LineParser<'a>, ParseError> {
+        self.next().ok_or_else(|| ParseError::new(self.line + 1, 1, "", expected))
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = LineParser<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let text = self.lines.next()?;
+        self.line += 1;
+        Some(LineParser::new(self.line, text))
+    }
+}
+
+/// Parses every line with `parse_line`, which has to consume the whole line.
+pub fn parse_lines<T>(
+    input: &str,
+    mut parse_line: impl FnMut(&mut LineParser) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    Lines::new(input)
+        .map(|mut line| {
+            let value = parse_line(&mut line)?;
+            line.end()?;
+            Ok(value)
+        })
+        .collect()
+}
+
+/// Reads one line token by token, keeping track of the column for error messages.
+pub struct LineParser<'a> {
+    line: usize,
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> LineParser<'a> {
+    pub fn new(line: usize, text: &'a str) -> Self {
+        LineParser { line, text, pos: 0 }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column (from 1) of the next character to be read.
+    pub fn column(&self) -> usize {
+        self.text[..self.pos].chars().count() + 1
+    }
+
+    /// Everything not read yet, without consuming it.
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.pos == self.text.len()
+    }
+
+    /// An error at the current position, showing the next word as the offending text.
+    pub fn error(&self, expected: &str) -> ParseError {
+        let found = self.rest().split_whitespace().next().unwrap_or("");
+        ParseError::new(self.line, self.column(), found, expected)
+    }
+
+    /// An error for the `text` that was just read, e.g. a well-formed number that is out of range.
+    pub fn error_at(&self, text: &str, expected: &str) -> ParseError {
+        let column = self.column() - text.chars().count();
+        ParseError::new(self.line, column, text, expected)
+    }
+
+    /// Skips `literal`, which has to come next.
+    pub fn literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.rest().starts_with(literal) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("{literal:?}")))
+        }
+    }
+
+    /// Skips `literal` if it comes next, telling whether it did.
+    pub fn skip(&mut self, literal: &str) -> bool {
+        self.literal(literal).is_ok()
+    }
+
+    /// A (possibly negative) integer.
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let rest = self.rest();
+        let sign = usize::from(rest.starts_with('-'));
+        let len = sign + rest[sign..].find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len() - sign);
+        let digits = &rest[..len];
+        match digits.parse() {
+            Ok(value) if len > sign => {
+                self.pos += len;
+                Ok(value)
+            }
+            _ => Err(self.error("a number")),
+        }
+    }
+
+    /// The next character.
+    pub fn char(&mut self, expected: &str) -> Result<char, ParseError> {
+        let c = self.rest().chars().next().ok_or_else(|| self.error(expected))?;
+        self.pos += c.len_utf8();
+        Ok(c)
+    }
+
+    /// Characters up to (not including) the next one for which `is_end` holds, or
+    /// to the end of the line. Fails if that is nothing.
+    pub fn take_until(&mut self, is_end: impl Fn(char) -> bool, expected: &str) -> Result<&'a str, ParseError> {
+        let rest = self.rest();
+        let len = rest.find(is_end).unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error(expected));
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    /// The characters up to the next whitespace.
+    pub fn word(&mut self, expected: &str) -> Result<&'a str, ParseError> {
+        self.take_until(char::is_whitespace, expected)
+    }
+
+    /// Whichever of the `options` comes next, e.g. `[("A", Rock), ("B", Paper)]`.
+    pub fn one_of<T: Clone>(&mut self, options: &[(&str, T)], expected: &str) -> Result<T, ParseError> {
+        for (literal, value) in options {
+            if self.skip(literal) {
+                return Ok(value.clone());
+            }
+        }
+        Err(self.error(expected))
+    }
+
+    /// Checks that the whole line has been read.
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.is_done() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
+
+/// One `Vec<char>` per input line, as-is (rows may have different lengths).
+pub fn char_grid(input: &str) -> Vec<Vec<char>> {
+    input.lines().map(|line| line.chars().collect()).collect()
+}
+
+/// Like `char_grid` but every row is padded with `pad` to the length of the longest row.
+pub fn padded_char_grid(input: &str, pad: char) -> Vec<Vec<char>> {
+    let mut grid = char_grid(input);
+    let n_cols = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+    grid.iter_mut().for_each(|row| row.resize(n_cols, pad));
+    grid
+}
+
+/// A grid where `cell` maps every character, returning `None` for characters that don't belong.
+pub fn grid_of<T>(
+    input: &str,
+    expected: &str,
+    cell: impl Fn(char) -> Option<T>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    parse_lines(input, |line| {
+        let mut row = vec![];
+        while !line.is_done() {
+            let c = line.char(expected)?;
+            row.push(cell(c).ok_or_else(|| line.error_at(&c.to_string(), expected))?);
+        }
+        Ok(row)
+    })
+}
+
+/// A grid of single digits such as `30373`.
+pub fn digit_grid(input: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    grid_of(input, "a digit", |c| c.to_digit(10).map(|d| d as usize))
+}
+
+/// All `(row, col)` positions in the input where `c` occurs.
+pub fn positions_of(input: &str, c: char) -> HashSet<(isize, isize)> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .filter(move |(_, ch)| *ch == c)
+                .map(move |(col, _)| (row as isize, col as isize))
+        })
+        .collect()
+}
+
+/// The first `(row, col)` in the grid holding `c`.
+pub fn find_in_grid(grid: &[Vec<char>], c: char) -> Option<(usize, usize)> {
+    grid.iter()
+        .enumerate()
+        .find_map(|(row, line)| line.iter().position(|ch| *ch == c).map(|col| (row, col)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_parser() {
+        let mut line = LineParser::new(3, "move 12 from -3");
+        line.literal("move ").unwrap();
+        assert_eq!(line.number::<usize>(), Ok(12));
+        assert_eq!(line.column(), 8);
+        assert_eq!(line.word("a word"), Err(ParseError::new(3, 8, "from", "a word")));
+        line.literal(" from ").unwrap();
+        assert_eq!(line.number::<usize>(), Err(ParseError::new(3, 14, "-3", "a number")));
+        assert_eq!(line.number::<isize>(), Ok(-3));
+        assert!(line.end().is_ok())
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let numbers = parse_lines("1\n2x\n", |line| line.number::<usize>());
+        let err = numbers.unwrap_err();
+        assert_eq!(err, ParseError::new(2, 2, "x", "end of line"));
+        assert_eq!(err.to_string(), "line 2, column 2: expected end of line, found \"x\"");
+        let mut lines = Lines::new("a");
+        lines.next();
+        let err = lines.expect_line("another line").err().unwrap();
+        assert_eq!(err.to_string(), "line 2, column 1: expected another line, found nothing");
+        let mut section = Lines::after(3, "x\n");
+        assert_eq!(section.expect_line("x").unwrap().line(), 4);
+        assert_eq!(section.expect_line("y").err(), Some(ParseError::new(5, 1, "", "y")))
+    }
+
+    #[test]
+    fn test_digit_grid() {
+        assert_eq!(digit_grid("12\n34"), Ok(vec![vec![1, 2], vec![3, 4]]));
+        assert_eq!(digit_grid("12\n3a").unwrap_err(), ParseError::new(2, 2, "a", "a digit"))
+    }
+
+    #[test]
+    fn test_padded_char_grid() {
+        let grid = padded_char_grid("  ..\n...\n", ' ');
+        assert_eq!(grid, vec![vec![' ', ' ', '.', '.'], vec!['.', '.', '.', ' ']])
+    }
+
+    #[test]
+    fn test_positions_of() {
+        let positions = positions_of(".#\n#.", '#');
+        assert_eq!(positions, HashSet::from([(0, 1), (1, 0)]))
+    }
+
+    #[test]
+    fn test_find_in_grid() {
+        let grid = char_grid("abc\ndSe");
+        assert_eq!(find_in_grid(&grid, 'S'), Some((1, 1)));
+        assert_eq!(find_in_grid(&grid, 'E'), None)
+    }
+}
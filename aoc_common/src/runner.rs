@@ -0,0 +1,97 @@
+use std::path::Path;
+use std::process;
+
+use crate::args::{Format, RunArgs, USAGE};
+use crate::input::InputSource;
+use crate::input_store::{InputStore, YEAR};
+use crate::json;
+use crate::logging;
+use crate::solution::{Answer, Day, Part, Solved};
+
+/// `main` of a day binary: solve the input picked on the command line and print the answers.
+pub fn day_main(day: &Day) {
+    let program = std::env::args().next().unwrap_or_else(|| String::from(day.name));
+    let args = RunArgs::parse(std::env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{err}\nUsage: {program} {USAGE}");
+        process::exit(2)
+    });
+    start_logging(&args);
+    start_pool(&args);
+    let input = read_input(day, &args);
+    match args.format {
+        Format::Text => print_answers(&solve(day, &input, &args), ""),
+        Format::Json => json::print_answers(day.name, &input, &solve_timed(day, &input, &args)),
+    }
+}
+
+/// Sets up logging as asked for in `args`, exiting with a message if the log file can't be created.
+pub fn start_logging(args: &RunArgs) {
+    logging::init(args.verbosity, args.log_file.as_deref()).unwrap_or_else(|err| {
+        let path = args.log_file.as_deref().unwrap_or(Path::new(""));
+        eprintln!("Could not create the log file {}: {err}", path.display());
+        process::exit(1)
+    })
+}
+
+/// Limits the rayon pool, that days and the work within them (such as the d19 blueprints) run on,
+/// to `--jobs` threads.
+pub fn start_pool(args: &RunArgs) {
+    if let Some(jobs) = args.jobs {
+        // Only fails if the pool is running already, and then that one is used
+        let _ = rayon::ThreadPoolBuilder::new().num_threads(jobs).build_global();
+    }
+}
+
+/// Reads the input selected in `args`, exiting with a message if it can't be read.
+/// Without an `input.txt` in the day's directory the input cache is used instead.
+pub fn read_input(day: &Day, args: &RunArgs) -> String {
+    try_read_input(day, args).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1)
+    })
+}
+
+/// Like `read_input`, but telling why the input can't be read instead of exiting.
+pub fn try_read_input(day: &Day, args: &RunArgs) -> Result<String, String> {
+    let day_dir = Path::new(day.dir);
+    if args.input == InputSource::Bundled && !day_dir.join("input.txt").exists() {
+        let store = InputStore::from_env();
+        return store
+            .input(YEAR, day.number(), day.sections)
+            .map_err(|err| format!("Could not read input from {}: {err}", store.path(YEAR, day.number()).display()));
+    }
+    args.input.read(day_dir).map_err(|err| format!("Could not read input from {}: {err}", args.input.name(day_dir)))
+}
+
+/// Solves the parts selected in `args`, exiting with a message if the input doesn't parse.
+pub fn solve(day: &Day, input: &str, args: &RunArgs) -> Vec<(Part, Answer)> {
+    let solved = solve_timed(day, input, args);
+    solved.answers.into_iter().map(|(part, answer, _)| (part, answer)).collect()
+}
+
+/// Like `solve`, also telling how long parsing and each part took.
+pub fn solve_timed(day: &Day, input: &str, args: &RunArgs) -> Solved {
+    try_solve_timed(day, input, args).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1)
+    })
+}
+
+/// Like `solve_timed`, but telling why the input doesn't parse instead of exiting.
+pub fn try_solve_timed(day: &Day, input: &str, args: &RunArgs) -> Result<Solved, String> {
+    day.solve_timed(input, &args.parts())
+        .map_err(|err| format!("Could not parse {}: {err}", args.input.name(Path::new(day.dir))))
+}
+
+/// Prints `Part N: answer` lines, putting multi-line answers (like a CRT screen) below the label.
+pub fn print_answers(answers: &[(Part, Answer)], indent: &str) {
+    for (part, answer) in answers {
+        let answer = answer.to_string();
+        if answer.contains('\n') {
+            println!("{indent}Part {part}:");
+            answer.lines().for_each(|line| println!("{indent}{line}"));
+        } else {
+            println!("{indent}Part {part}: {answer}");
+        }
+    }
+}
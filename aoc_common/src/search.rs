@@ -0,0 +1,426 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::RandomState;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+/// A search from a start state to a goal, one step at a time. Every step has a cost, and the
+/// searches look for the cheapest way to a goal (`bfs` for the one with the fewest steps).
+pub trait SearchProblem {
+    type State: Clone + Eq + Hash;
+
+    fn start(&self) -> Self::State;
+
+    /// The states one step away from `state`, with what each step costs.
+    fn successors(&self, state: &Self::State) -> Vec<(Self::State, usize)>;
+
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    /// A lower bound on the cost from `state` to a goal. Used by `a_star` and `branch_and_bound`,
+    /// which only find the cheapest way if it never overestimates.
+    fn heuristic(&self, _state: &Self::State) -> usize {
+        0
+    }
+
+    /// Lets `bfs` drop states from a layer, before it is expanded, that can't do better than
+    /// others in it, and returns the rest of them. Keeps them all unless overridden. A dropped
+    /// state is forgotten, so it is new again if it is reached in a later layer.
+    fn prune(&self, layer: Vec<Self::State>) -> Vec<Self::State> {
+        layer
+    }
+}
+
+/// A way to a goal: the states from the start to the goal, both included, and what it costs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found<S> {
+    pub cost: usize,
+    pub path: Vec<S>,
+}
+
+impl<S> Found<S> {
+    pub fn n_steps(&self) -> usize {
+        self.path.len() - 1
+    }
+
+    pub fn goal(&self) -> &S {
+        self.path.last().unwrap()
+    }
+}
+
+/// How much work a search did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// States whose successors were generated
+    pub expanded: usize,
+    /// Successors generated, including the ones already seen
+    pub generated: usize,
+    /// States dropped by `SearchProblem::prune` or, in `branch_and_bound`, by the bound
+    pub pruned: usize,
+    /// The most states waiting to be expanded at any time
+    pub max_frontier: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Search<S> {
+    /// `None` if no goal can be reached
+    pub found: Option<Found<S>>,
+    pub stats: Stats,
+}
+
+// The states seen so far, each with the cost of the cheapest known way to it and the state it
+// was reached from, so that the path can be put together at the end. Each state is only stored
+// once: they are found by their hash, which leads to the last of them with that hash, and from
+// each of them to the one before it with the same hash.
+struct Seen<S> {
+    states: Vec<S>,
+    hasher: RandomState,
+    by_hash: HashMap<u64, usize>,
+    same_hash: Vec<Option<usize>>,
+    cost: Vec<usize>,
+    parent: Vec<Option<usize>>,
+}
+
+impl<S: Clone + Eq + Hash> Seen<S> {
+    fn new(start: S) -> Self {
+        let mut seen =
+            Seen { states: vec![], hasher: RandomState::new(), by_hash: HashMap::new(), same_hash: vec![], cost: vec![], parent: vec![] };
+        seen.insert(start, 0, None);
+        seen
+    }
+
+    fn find(&self, state: &S) -> Option<usize> {
+        let mut candidate = self.by_hash.get(&self.hasher.hash_one(state)).copied();
+        while let Some(ind) = candidate {
+            if self.states[ind] == *state {
+                return Some(ind);
+            }
+            candidate = self.same_hash[ind]
+        }
+        None
+    }
+
+    // The index of `state`, and whether it was new or got cheaper
+    fn reach(&mut self, state: S, cost: usize, parent: usize) -> (usize, bool) {
+        match self.find(&state) {
+            Some(ind) if self.cost[ind] <= cost => (ind, false),
+            Some(ind) => {
+                self.cost[ind] = cost;
+                self.parent[ind] = Some(parent);
+                (ind, true)
+            }
+            None => (self.insert(state, cost, Some(parent)), true),
+        }
+    }
+
+    fn insert(&mut self, state: S, cost: usize, parent: Option<usize>) -> usize {
+        let ind = self.states.len();
+        self.same_hash.push(self.by_hash.insert(self.hasher.hash_one(&state), ind));
+        self.states.push(state);
+        self.cost.push(cost);
+        self.parent.push(parent);
+        ind
+    }
+
+    // Forgets the states from `start` on, but for the ones at `kept`, which are seen again in
+    // that order. Their new indices.
+    fn keep(&mut self, start: usize, kept: &[usize]) -> Vec<usize> {
+        let mut states: Vec<Option<S>> = self.states.drain(start..).map(Some).collect();
+        let (cost, parent) = (self.cost.split_off(start), self.parent.split_off(start));
+        let same_hash = &self.same_hash;
+        self.by_hash.retain(|_, last| {
+            while *last >= start {
+                match same_hash[*last] {
+                    Some(before) => *last = before,
+                    None => return false,
+                }
+            }
+            true
+        });
+        self.same_hash.truncate(start);
+        kept.iter().map(|&ind| self.insert(states[ind - start].take().unwrap(), cost[ind - start], parent[ind - start])).collect()
+    }
+
+    fn found(&self, goal: usize) -> Found<S> {
+        let mut path = vec![self.states[goal].clone()];
+        let mut ind = goal;
+        while let Some(parent) = self.parent[ind] {
+            path.push(self.states[parent].clone());
+            ind = parent;
+        }
+        path.reverse();
+        Found { cost: self.cost[goal], path }
+    }
+}
+
+/// Breadth-first, layer by layer: the way to a goal with the fewest steps, and the cheapest of
+/// those if there are several. A state is only visited the first time it is reached.
+pub fn bfs<P: SearchProblem>(problem: &P) -> Search<P::State> {
+    let mut seen = Seen::new(problem.start());
+    let mut stats = Stats::default();
+    let mut layer = vec![0];
+    while !layer.is_empty() {
+        stats.max_frontier = stats.max_frontier.max(layer.len());
+        if let Some(goal) = layer.iter().filter(|ind| problem.is_goal(&seen.states[**ind])).min_by_key(|ind| seen.cost[**ind]) {
+            return Search { found: Some(seen.found(*goal)), stats };
+        }
+        // The new states go at the end of `seen`
+        let layer_start = seen.states.len();
+        for ind in layer {
+            stats.expanded += 1;
+            for (next, step_cost) in problem.successors(&seen.states[ind]) {
+                stats.generated += 1;
+                if seen.find(&next).is_none() {
+                    seen.reach(next, seen.cost[ind] + step_cost, ind);
+                }
+            }
+        }
+        // Only the states that are kept are remembered, so that the dropped ones take no memory
+        // from here on
+        let next_layer = seen.states[layer_start..].to_vec();
+        let n_states = next_layer.len();
+        let kept: Vec<usize> = problem.prune(next_layer).iter().map(|state| seen.find(state).unwrap()).collect();
+        stats.pruned += n_states - kept.len();
+        layer = seen.keep(layer_start, &kept);
+    }
+    Search { found: None, stats }
+}
+
+/// The cheapest way to a goal.
+pub fn dijkstra<P: SearchProblem>(problem: &P) -> Search<P::State> {
+    best_first(problem, |_| 0)
+}
+
+/// The cheapest way to a goal, guided by `SearchProblem::heuristic`.
+pub fn a_star<P: SearchProblem>(problem: &P) -> Search<P::State> {
+    best_first(problem, |state| problem.heuristic(state))
+}
+
+fn best_first<P: SearchProblem>(problem: &P, heuristic: impl Fn(&P::State) -> usize) -> Search<P::State> {
+    let start = problem.start();
+    // Lowest estimate first, and of those the one that got furthest, that is the most expensive so far
+    let mut frontier = BinaryHeap::from([(Reverse(heuristic(&start)), 0, 0)]);
+    let mut seen = Seen::new(start);
+    let mut stats = Stats::default();
+    while let Some((_, cost, ind)) = frontier.pop() {
+        if cost > seen.cost[ind] {
+            // There turned out to be a cheaper way here after this one was queued
+            continue;
+        }
+        if problem.is_goal(&seen.states[ind]) {
+            return Search { found: Some(seen.found(ind)), stats };
+        }
+        stats.expanded += 1;
+        for (next, step_cost) in problem.successors(&seen.states[ind]) {
+            stats.generated += 1;
+            let next_cost = cost + step_cost;
+            if let (next_ind, true) = seen.reach(next, next_cost, ind) {
+                frontier.push((Reverse(next_cost + heuristic(&seen.states[next_ind])), next_cost, next_ind));
+            }
+        }
+        stats.max_frontier = stats.max_frontier.max(frontier.len());
+    }
+    Search { found: None, stats }
+}
+
+/// Depth first, taking the successors in the order they are given, and leaving out every state
+/// whose cost so far plus `SearchProblem::heuristic` is no better than the cheapest goal found.
+/// Uses little memory as it doesn't remember the states it has seen, so it is meant for problems
+/// where a state can't come back, such as ones that count the time.
+pub fn branch_and_bound<P: SearchProblem>(problem: &P) -> Search<P::State> {
+    let mut search = BranchAndBound { problem, path: vec![problem.start()], best: None, stats: Stats::default() };
+    search.descend(0);
+    Search { found: search.best, stats: search.stats }
+}
+
+struct BranchAndBound<'a, P: SearchProblem> {
+    problem: &'a P,
+    path: Vec<P::State>,
+    best: Option<Found<P::State>>,
+    stats: Stats,
+}
+
+impl<P: SearchProblem> BranchAndBound<'_, P> {
+    fn bound(&self) -> usize {
+        self.best.as_ref().map_or(usize::MAX, |best| best.cost)
+    }
+
+    fn descend(&mut self, cost: usize) {
+        let state = self.path.last().unwrap();
+        if self.problem.is_goal(state) {
+            if cost < self.bound() {
+                self.best = Some(Found { cost, path: self.path.clone() });
+            }
+            return;
+        }
+        self.stats.expanded += 1;
+        self.stats.max_frontier = self.stats.max_frontier.max(self.path.len());
+        for (next, step_cost) in self.problem.successors(state) {
+            self.stats.generated += 1;
+            let next_cost = cost + step_cost;
+            if next_cost + self.problem.heuristic(&next) >= self.bound() {
+                self.stats.pruned += 1;
+                continue;
+            }
+            self.path.push(next);
+            self.descend(next_cost);
+            self.path.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Walking along a line from 0 to `target` with steps of +1 (cost 3) or +2 (cost 5), or
+    // taking the jump from 1 to `target` (cost 100)
+    struct Line {
+        target: usize,
+    }
+
+    impl SearchProblem for Line {
+        type State = usize;
+
+        fn start(&self) -> usize {
+            0
+        }
+
+        fn successors(&self, state: &usize) -> Vec<(usize, usize)> {
+            let mut next = vec![(state + 1, 3), (state + 2, 5)];
+            if *state == 1 {
+                next.push((self.target, 100))
+            }
+            next.retain(|(next, _)| *next <= self.target);
+            next
+        }
+
+        fn is_goal(&self, state: &usize) -> bool {
+            *state == self.target
+        }
+
+        fn heuristic(&self, state: &usize) -> usize {
+            // Every unit of distance costs at least 2.5
+            (self.target - state) * 5 / 2
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let search = bfs(&Line { target: 10 });
+        let found = search.found.unwrap();
+        assert_eq!(found.path, vec![0, 1, 10]);
+        assert_eq!((found.cost, found.n_steps()), (103, 2));
+        assert_eq!(search.stats.expanded, 3)
+    }
+
+    #[test]
+    fn test_cheapest() {
+        let line = Line { target: 10 };
+        for search in [dijkstra(&line), a_star(&line), branch_and_bound(&line)] {
+            let found = search.found.unwrap();
+            assert_eq!(found.cost, 25);
+            assert_eq!(found.path, vec![0, 2, 4, 6, 8, 10]);
+            assert_eq!(*found.goal(), 10)
+        }
+        // The heuristic saves work
+        assert!(a_star(&line).stats.expanded < dijkstra(&line).stats.expanded);
+        assert!(branch_and_bound(&line).stats.pruned > 0)
+    }
+
+    #[test]
+    fn test_unreachable() {
+        struct Stuck;
+
+        impl SearchProblem for Stuck {
+            type State = u8;
+
+            fn start(&self) -> u8 {
+                0
+            }
+
+            fn successors(&self, state: &u8) -> Vec<(u8, usize)> {
+                if *state < 3 {
+                    vec![(state + 1, 1)]
+                } else {
+                    vec![]
+                }
+            }
+
+            fn is_goal(&self, state: &u8) -> bool {
+                *state == 9
+            }
+        }
+
+        for search in [bfs(&Stuck), dijkstra(&Stuck), a_star(&Stuck), branch_and_bound(&Stuck)] {
+            assert_eq!(search.found, None);
+            assert_eq!(search.stats.expanded, 4)
+        }
+    }
+
+    #[test]
+    fn test_prune() {
+        // Only the even states are kept in each layer, so the odd target can't be reached
+        struct Evens(Line);
+
+        impl SearchProblem for Evens {
+            type State = usize;
+
+            fn start(&self) -> usize {
+                0
+            }
+
+            fn successors(&self, state: &usize) -> Vec<(usize, usize)> {
+                self.0.successors(state)
+            }
+
+            fn is_goal(&self, state: &usize) -> bool {
+                self.0.is_goal(state)
+            }
+
+            fn prune(&self, layer: Vec<usize>) -> Vec<usize> {
+                layer.into_iter().filter(|state| state % 2 == 0).collect()
+            }
+        }
+
+        let search = bfs(&Evens(Line { target: 9 }));
+        assert_eq!(search.found, None);
+        assert!(search.stats.pruned > 0);
+        assert_eq!(bfs(&Evens(Line { target: 8 })).found.unwrap().path, vec![0, 2, 4, 6, 8])
+    }
+
+    #[test]
+    fn test_prune_forgets() {
+        // 0 leads to 1 and 2, and 2 to 1 again. Only 1 leads on to the goal, 3, but it is dropped
+        // from the first layer in favour of 2.
+        struct Detour;
+
+        impl SearchProblem for Detour {
+            type State = u8;
+
+            fn start(&self) -> u8 {
+                0
+            }
+
+            fn successors(&self, state: &u8) -> Vec<(u8, usize)> {
+                match state {
+                    0 => vec![(1, 1), (2, 1)],
+                    2 => vec![(1, 1)],
+                    1 => vec![(3, 1)],
+                    _ => vec![],
+                }
+            }
+
+            fn is_goal(&self, state: &u8) -> bool {
+                *state == 3
+            }
+
+            fn prune(&self, layer: Vec<u8>) -> Vec<u8> {
+                let drop_1 = layer.contains(&2);
+                layer.into_iter().filter(|state| !(drop_1 && *state == 1)).collect()
+            }
+        }
+
+        let search = bfs(&Detour);
+        assert_eq!(search.found.unwrap().path, vec![0, 2, 1, 3]);
+        assert_eq!(search.stats.pruned, 1)
+    }
+}
@@ -0,0 +1,288 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::frames::{Palette, Recorder, Visualize};
+use crate::generate::{self, Generator};
+use crate::parse::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    /// The part isn't solved (or, for day 25, doesn't exist)
+    Missing,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{number}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Missing => write!(f, "-"),
+        }
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<u16> for Answer {
+    fn from(value: u16) -> Self {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+/// A day's puzzle: parse the input once, then solve both parts from it.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part_1(input: &Self::Input) -> Answer;
+
+    fn part_2(_input: &Self::Input) -> Answer {
+        Answer::Missing
+    }
+}
+
+/// A `Solution` that can tell more about its input than the answers, for the dashboard.
+pub trait Stats: Solution {
+    /// Named figures about the input, in the order they are shown.
+    fn stats(input: &Self::Input) -> Vec<(&'static str, String)>;
+}
+
+/// The answers to the parts that were asked for, with how long parsing and each part took.
+#[derive(Debug)]
+pub struct Solved {
+    pub parse_time: Duration,
+    pub answers: Vec<(Part, Answer, Duration)>,
+}
+
+fn solve<S: Solution>(input: &str, parts: &[Part]) -> Result<Solved, ParseError> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse_time = start.elapsed();
+    let answers = parts
+        .iter()
+        .map(|part| {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => S::part_1(&input),
+                Part::Two => S::part_2(&input),
+            };
+            (*part, answer, start.elapsed())
+        })
+        .collect();
+    Ok(Solved { parse_time, answers })
+}
+
+fn parse<S: Solution>(input: &str) -> Result<(), ParseError> {
+    S::parse(input).map(|_| ())
+}
+
+fn stats<S: Stats>(input: &str) -> Result<Vec<(&'static str, String)>, ParseError> {
+    S::parse(input).map(|input| S::stats(&input))
+}
+
+fn simulate<V: Visualize>(input: &str, part: Part, recorder: &mut Recorder) -> Result<(), ParseError> {
+    V::simulate(&V::parse(input)?, part, recorder);
+    Ok(())
+}
+
+// The `Stats` function of a day, if it has one
+type StatsFn = fn(&str) -> Result<Vec<(&'static str, String)>, ParseError>;
+
+// The `Visualize` functions of a day, if it has them
+struct Frames {
+    simulate: fn(&str, Part, &mut Recorder) -> Result<(), ParseError>,
+    palette: fn() -> Palette,
+}
+
+/// A `Solution` together with where its crate lives, so that it can be run
+/// without knowing its type.
+pub struct Day {
+    /// The crate name, `d1` ... `d25` or `d19_bfs`
+    pub name: &'static str,
+    /// The crate directory holding `input.txt` and the examples
+    pub dir: &'static str,
+    /// Names of the blank line separated sections of the input, if it has several kinds of them
+    pub sections: &'static [&'static str],
+    solve: fn(&str, &[Part]) -> Result<Solved, ParseError>,
+    parse: fn(&str) -> Result<(), ParseError>,
+    frames: Option<Frames>,
+    generate: Option<Generator>,
+    stats: Option<StatsFn>,
+}
+
+impl Day {
+    pub const fn new<S: Solution>(name: &'static str, dir: &'static str) -> Self {
+        Day { name, dir, sections: &[], solve: solve::<S>, parse: parse::<S>, frames: None, generate: None, stats: None }
+    }
+
+    /// Lets the simulation of the day be watched. `V` is normally the day's `Solution` itself.
+    pub const fn with_frames<V: Visualize>(self) -> Self {
+        Day { frames: Some(Frames { simulate: simulate::<V>, palette: V::palette }), ..self }
+    }
+
+    /// Lets `aoc gen` write random inputs for the day.
+    pub const fn with_generator(self, generate: Generator) -> Self {
+        Day { generate: Some(generate), ..self }
+    }
+
+    /// Lets the dashboard show more about the input than the answers. `S` is normally the day's `Solution` itself.
+    pub const fn with_stats<S: Stats>(self) -> Self {
+        Day { stats: Some(stats::<S>), ..self }
+    }
+
+    /// Lets the input cache split the input into files named after the sections.
+    pub const fn with_sections(self, sections: &'static [&'static str]) -> Self {
+        Day { sections, ..self }
+    }
+
+    /// The day of the puzzle, 19 for both `d19` and `d19_bfs`.
+    pub fn number(&self) -> u8 {
+        let digits: String = self.name.chars().skip(1).take_while(char::is_ascii_digit).collect();
+        digits.parse().unwrap()
+    }
+
+    pub fn solve(&self, input: &str, parts: &[Part]) -> Result<Vec<(Part, Answer)>, ParseError> {
+        let solved = self.solve_timed(input, parts)?;
+        Ok(solved.answers.into_iter().map(|(part, answer, _)| (part, answer)).collect())
+    }
+
+    pub fn solve_timed(&self, input: &str, parts: &[Part]) -> Result<Solved, ParseError> {
+        (self.solve)(input, parts)
+    }
+
+    /// Only parses the input, to check it without solving.
+    pub fn parse(&self, input: &str) -> Result<(), ParseError> {
+        (self.parse)(input)
+    }
+
+    /// A random input of about `size` parts, the same for the same seed. `None` if the day has no generator.
+    pub fn generate(&self, size: usize, seed: u64) -> Option<String> {
+        self.generate.map(|generate| generate(&mut generate::rng(seed), size))
+    }
+
+    /// The figures about the input, `None` if the day has none.
+    pub fn stats(&self, input: &str) -> Option<Result<Vec<(&'static str, String)>, ParseError>> {
+        self.stats.map(|stats| stats(input))
+    }
+
+    pub fn has_frames(&self) -> bool {
+        self.frames.is_some()
+    }
+
+    /// Runs the simulation of `part` with `recorder`, `None` if the day has none.
+    pub fn simulate(&self, input: &str, part: Part, recorder: &mut Recorder) -> Option<Result<(), ParseError>> {
+        self.frames.as_ref().map(|frames| (frames.simulate)(input, part, recorder))
+    }
+
+    /// The colours of the day's frames, `None` if it has none.
+    pub fn palette(&self) -> Option<Palette> {
+        self.frames.as_ref().map(|frames| (frames.palette)())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct LineCount;
+
+    impl Solution for LineCount {
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            Ok(input.lines().map(String::from).collect())
+        }
+
+        fn part_1(input: &Self::Input) -> Answer {
+            input.len().into()
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let day = Day::new::<LineCount>("d0", "");
+        assert_eq!(
+            day.solve("a\nb\nc", &[Part::One, Part::Two]),
+            Ok(vec![(Part::One, Answer::Number(3)), (Part::Two, Answer::Missing)])
+        );
+        assert_eq!(day.solve("a", &[Part::Two]), Ok(vec![(Part::Two, Answer::Missing)]))
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(Day::new::<LineCount>("d7", "").number(), 7);
+        assert_eq!(Day::new::<LineCount>("d19_bfs", "").number(), 19)
+    }
+
+    #[test]
+    fn test_solve_timed() {
+        let day = Day::new::<LineCount>("d0", "");
+        let solved = day.solve_timed("a\nb", &[Part::Two, Part::One]).unwrap();
+        let parts: Vec<_> = solved.answers.iter().map(|(part, _, _)| *part).collect();
+        assert_eq!(parts, vec![Part::Two, Part::One])
+    }
+
+    #[test]
+    fn test_generate() {
+        let day = Day::new::<LineCount>("d0", "").with_generator(|rng, size| generate::lines(rng, size, |_, ind| ind.to_string()));
+        assert_eq!(day.generate(3, 1), Some(String::from("0\n1\n2\n")));
+        assert!(day.parse("a").is_ok());
+        assert_eq!(Day::new::<LineCount>("d0", "").generate(3, 1), None)
+    }
+
+    impl Stats for LineCount {
+        fn stats(input: &Self::Input) -> Vec<(&'static str, String)> {
+            vec![("longest line", input.iter().map(String::len).max().unwrap_or(0).to_string())]
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let day = Day::new::<LineCount>("d0", "").with_stats::<LineCount>();
+        assert_eq!(day.stats("ab
+abc"), Some(Ok(vec![("longest line", String::from("3"))])));
+        assert_eq!(Day::new::<LineCount>("d0", "").stats("ab"), None)
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(42usize).to_string(), "42");
+        assert_eq!(Answer::from(String::from("CMZ")).to_string(), "CMZ");
+        assert_eq!(Answer::Missing.to_string(), "-")
+    }
+}
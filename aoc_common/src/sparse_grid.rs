@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use crate::geometry::bounding_box;
+use crate::grid::Grid;
+
+/// Inclusive `((min_row, min_col), (max_row, max_col))`, as returned by `geometry::bounding_box`.
+pub type Bounds = ((isize, isize), (isize, isize));
+
+/// Cells on an unbounded plane, indexed by `(row, col)`, that keeps track of its bounding box.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<(isize, isize), T>,
+    bounds: Option<Bounds>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid { cells: HashMap::new(), bounds: None }
+    }
+
+    /// Returns the cell that was at `pos` before, if any.
+    pub fn insert(&mut self, pos: (isize, isize), cell: T) -> Option<T> {
+        let (row, col) = pos;
+        self.bounds = Some(match self.bounds {
+            None => (pos, pos),
+            Some(((min_row, min_col), (max_row, max_col))) => {
+                ((min_row.min(row), min_col.min(col)), (max_row.max(row), max_col.max(col)))
+            }
+        });
+        self.cells.insert(pos, cell)
+    }
+
+    pub fn remove(&mut self, pos: (isize, isize)) -> Option<T> {
+        let cell = self.cells.remove(&pos)?;
+        // Only a cell on the edge of the bounding box can make it shrink
+        if let Some(((min_row, min_col), (max_row, max_col))) = self.bounds {
+            if pos.0 == min_row || pos.0 == max_row || pos.1 == min_col || pos.1 == max_col {
+                self.bounds = bounding_box(self.cells.keys());
+            }
+        }
+        Some(cell)
+    }
+
+    pub fn get(&self, pos: (isize, isize)) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn contains(&self, pos: (isize, isize)) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The bounding box of the cells, `None` if there are none.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    /// Number of positions, with or without a cell, in the bounding box.
+    pub fn area(&self) -> usize {
+        match self.bounds {
+            Some(((min_row, min_col), (max_row, max_col))) => {
+                ((max_row - min_row + 1) * (max_col - min_col + 1)) as usize
+            }
+            None => 0,
+        }
+    }
+
+    /// Every cell with its position, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = ((isize, isize), &T)> {
+        self.cells.iter().map(|(pos, cell)| (*pos, cell))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (isize, isize)> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The part of the plane inside `viewport` drawn with `legend`, one line per row, top row first.
+    pub fn render(&self, viewport: Bounds, legend: &Legend<T>) -> String
+    where
+        T: PartialEq,
+    {
+        let ((min_row, min_col), (max_row, max_col)) = viewport;
+        let mut picture = String::new();
+        for row in min_row..=max_row {
+            picture.extend((min_col..=max_col).map(|col| legend.symbol(self.get((row, col)))));
+            picture.push('\n');
+        }
+        picture
+    }
+
+    /// The bounding box drawn with `legend`, empty if there are no cells.
+    pub fn render_all(&self, legend: &Legend<T>) -> String
+    where
+        T: PartialEq,
+    {
+        self.bounds.map_or_else(String::new, |bounds| self.render(bounds, legend))
+    }
+
+    /// The bounding box as a dense grid, with `empty` where there is no cell, and the position of
+    /// its upper left corner on the plane.
+    pub fn to_dense(&self, empty: T) -> (Grid<T>, (isize, isize))
+    where
+        T: Clone,
+    {
+        match self.bounds {
+            None => (Grid::new(0, 0, empty), (0, 0)),
+            Some(((min_row, min_col), (max_row, max_col))) => {
+                let n_rows = (max_row - min_row + 1) as usize;
+                let n_cols = (max_col - min_col + 1) as usize;
+                let grid = Grid::from_fn(n_rows, n_cols, |(row, col)| {
+                    let pos = (min_row + row as isize, min_col + col as isize);
+                    self.get(pos).unwrap_or(&empty).clone()
+                });
+                (grid, (min_row, min_col))
+            }
+        }
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+impl<T> FromIterator<((isize, isize), T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = ((isize, isize), T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        for (pos, cell) in iter {
+            grid.insert(pos, cell);
+        }
+        grid
+    }
+}
+
+/// How a `SparseGrid` is drawn: a symbol for each kind of cell and one for the empty positions.
+/// Cells it has no symbol for are drawn as `?`.
+#[derive(Debug, Clone)]
+pub struct Legend<T> {
+    empty: char,
+    empty_name: String,
+    symbols: Vec<(T, char, String)>,
+}
+
+impl<T: PartialEq> Legend<T> {
+    pub fn new(empty: char, empty_name: &str) -> Self {
+        Legend { empty, empty_name: String::from(empty_name), symbols: vec![] }
+    }
+
+    pub fn with(mut self, cell: T, symbol: char, name: &str) -> Self {
+        self.symbols.push((cell, symbol, String::from(name)));
+        self
+    }
+
+    pub fn symbol(&self, cell: Option<&T>) -> char {
+        match cell {
+            None => self.empty,
+            Some(cell) => self.symbols.iter().find(|(known, _, _)| known == cell).map_or('?', |(_, symbol, _)| *symbol),
+        }
+    }
+
+    /// What the symbols mean, to print under a picture, e.g. `# rock, o sand, . air`.
+    pub fn key(&self) -> String {
+        self.symbols
+            .iter()
+            .map(|(_, symbol, name)| format!("{symbol} {name}"))
+            .chain(std::iter::once(format!("{} {}", self.empty, self.empty_name)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cave() -> SparseGrid<char> {
+        [((0, 0), '#'), ((2, -1), 'o'), ((1, 3), '#')].into_iter().collect()
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = cave();
+        assert_eq!(grid.bounds(), Some(((0, -1), (2, 3))));
+        assert_eq!(grid.area(), 3 * 5);
+        grid.insert((-4, 1), '#');
+        assert_eq!(grid.bounds(), Some(((-4, -1), (2, 3))));
+        assert_eq!(grid.remove((1, 1)), None);
+        assert_eq!(grid.remove((2, -1)), Some('o'));
+        assert_eq!(grid.bounds(), Some(((-4, 0), (1, 3))));
+        grid.remove((-4, 1));
+        grid.remove((0, 0));
+        grid.remove((1, 3));
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.area(), 0)
+    }
+
+    #[test]
+    fn test_render() {
+        let grid = cave();
+        let legend = Legend::new('.', "air").with('#', '#', "rock").with('o', 'o', "sand");
+        assert_eq!(grid.render_all(&legend), ".#...\n....#\no....\n");
+        assert_eq!(grid.render(((-1, 0), (0, 1)), &legend), "..\n#.\n");
+        assert_eq!(legend.key(), "# rock, o sand, . air");
+        assert_eq!(Legend::new(' ', "nothing").symbol(Some(&'#')), '?');
+        assert_eq!(SparseGrid::new().render_all(&legend), "")
+    }
+
+    #[test]
+    fn test_to_dense() {
+        let (grid, origin) = cave().to_dense('.');
+        assert_eq!(origin, (0, -1));
+        assert_eq!(grid.to_string(), ".#...\n....#\no....\n");
+        let (empty, _) = SparseGrid::<char>::new().to_dense('.');
+        assert_eq!((empty.n_rows(), empty.n_cols()), (0, 0))
+    }
+}
@@ -0,0 +1,325 @@
+use aoc_common::args::{RunArgs, UsageError, USAGE};
+use aoc_common::generate::{self, Rng, StdRng};
+use aoc_common::parse::LineParser;
+use aoc_common::runner::read_input;
+use aoc_common::{Answer, Day, ParseError, Solution, Stats};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::process;
+
+pub struct Day1;
+
+pub const DAY: Day = Day::new::<Day1>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR"))
+    .with_stats::<Day1>()
+    .with_generator(generate);
+
+impl Solution for Day1 {
+    type Input = Elves;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_calories(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input).into()
+    }
+}
+
+impl Stats for Day1 {
+    fn stats(elves: &Self::Input) -> Vec<(&'static str, String)> {
+        let top: Vec<String> = elves.top().iter().map(|(ind, total)| format!("elf {} ({total})", ind + 1)).collect();
+        vec![
+            ("elves", elves.count().to_string()),
+            ("mean", format!("{:.1}", elves.mean())),
+            ("median", elves.median().to_string()),
+            ("top 3", top.join(", ")),
+        ]
+    }
+}
+
+fn part_1(elves: &Elves) -> usize {
+    elves.top()[0].1
+}
+
+fn part_2(elves: &Elves) -> usize {
+    elves.top().iter().map(|(_, total)| total).sum()
+}
+
+fn parse_calories(input: &str) -> Result<Elves, ParseError> {
+    let mut elves = Elves::new(3);
+    for (ind, line) in input.lines().enumerate() {
+        elves.read_line(ind + 1, line)?
+    }
+    Ok(elves.done())
+}
+
+/// The calories the elves carry, gathered a line at a time: the elves carrying the most and what
+/// it takes for statistics over all of them. It takes as much memory for a huge input as for a
+/// small one, apart from a count for each different total.
+#[derive(Debug, Clone)]
+pub struct Elves {
+    n_top: usize,
+    // The `n_top` largest totals so far, smallest first, with the elf that carries it (the first of
+    // them, on ties)
+    top: BinaryHeap<Reverse<(usize, Reverse<usize>)>>,
+    // How many elves carry each total
+    totals: BTreeMap<usize, usize>,
+    count: usize,
+    sum: u128,
+    // The total of the elf whose snacks are being read
+    carrying: usize,
+}
+
+impl Elves {
+    /// Nobody yet, keeping the `n_top` elves that carry the most.
+    pub fn new(n_top: usize) -> Self {
+        Elves { n_top, top: BinaryHeap::new(), totals: BTreeMap::new(), count: 0, sum: 0, carrying: 0 }
+    }
+
+    /// Reads the input, a line at a time.
+    pub fn read(reader: impl BufRead, n_top: usize) -> io::Result<Self> {
+        let mut elves = Elves::new(n_top);
+        for (ind, line) in reader.lines().enumerate() {
+            elves.read_line(ind + 1, &line?).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+        }
+        Ok(elves.done())
+    }
+
+    // A snack, or the blank line between two elves
+    fn read_line(&mut self, line_number: usize, text: &str) -> Result<(), ParseError> {
+        let mut line = LineParser::new(line_number, text);
+        if line.is_done() {
+            self.add(self.carrying);
+            self.carrying = 0;
+        } else {
+            let calories = line.number::<usize>()?;
+            line.end()?;
+            self.carrying = self.carrying.checked_add(calories).ok_or_else(|| line.error_at(text, "fewer calories"))?;
+        }
+        Ok(())
+    }
+
+    // The last elf has no blank line after it
+    fn done(mut self) -> Self {
+        self.add(self.carrying);
+        self.carrying = 0;
+        self
+    }
+
+    fn add(&mut self, total: usize) {
+        let elf = self.count;
+        self.count += 1;
+        self.sum += total as u128;
+        *self.totals.entry(total).or_default() += 1;
+        self.top.push(Reverse((total, Reverse(elf))));
+        if self.top.len() > self.n_top {
+            self.top.pop();
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The elves carrying the most, as (index from 0, total), the most first.
+    pub fn top(&self) -> Vec<(usize, usize)> {
+        let mut top: Vec<_> = self.top.iter().map(|Reverse((total, Reverse(elf)))| (*elf, *total)).collect();
+        top.sort_by_key(|(elf, total)| (Reverse(*total), *elf));
+        top
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.sum as f64 / self.count as f64
+    }
+
+    /// The middle total, or halfway between the two middle ones for an even number of elves.
+    pub fn median(&self) -> f64 {
+        let (below, above) = ((self.count - 1) / 2, self.count / 2);
+        let (mut low, mut high) = (None, None);
+        let mut seen = 0;
+        for (total, n_elves) in &self.totals {
+            seen += n_elves;
+            if low.is_none() && seen > below {
+                low = Some(*total)
+            }
+            if seen > above {
+                high = Some(*total);
+                break
+            }
+        }
+        (low.unwrap() + high.unwrap()) as f64 / 2.0
+    }
+
+    /// How many elves carry totals in each of `n_buckets` equally wide ranges, from the smallest
+    /// total to the largest, as (first total of the range, last total, elves).
+    pub fn histogram(&self, n_buckets: usize) -> Vec<(usize, usize, usize)> {
+        let (min, max) = (*self.totals.keys().next().unwrap(), *self.totals.keys().next_back().unwrap());
+        let width = (max - min) / n_buckets + 1;
+        let mut buckets: Vec<_> = (0..n_buckets).map(|ind| (min + ind * width, min + (ind + 1) * width - 1, 0)).collect();
+        for (total, n_elves) in &self.totals {
+            buckets[(total - min) / width].2 += n_elves
+        }
+        // Wide buckets can end before they are all used
+        while buckets.len() > 1 && buckets.last().unwrap().0 > max {
+            buckets.pop();
+        }
+        buckets
+    }
+}
+
+impl fmt::Display for Elves {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Elves: {}", self.count)?;
+        writeln!(f, "Mean: {:.1}", self.mean())?;
+        writeln!(f, "Median: {}", self.median())?;
+        writeln!(f, "Top {}:", self.n_top)?;
+        for (elf, total) in self.top() {
+            writeln!(f, "  elf {}: {total}", elf + 1)?;
+        }
+        writeln!(f, "Totals:")?;
+        let histogram = self.histogram(10);
+        let most = histogram.iter().map(|(_, _, n_elves)| *n_elves).max().unwrap();
+        for (first, last, n_elves) in histogram {
+            let bar = "#".repeat((n_elves * 50).div_ceil(most));
+            writeln!(f, "  {first:>8} - {last:<8} {bar} {n_elves}")?;
+        }
+        Ok(())
+    }
+}
+
+/// `d1 --top K [--input <path> | --example]`: statistics of the elves, the K carrying the most
+/// first, reading the input a line at a time so that inputs of any size fit in memory.
+pub fn statistics_main(args: Vec<String>) {
+    let program = std::env::args().next().unwrap_or_else(|| String::from(DAY.name));
+    let (n_top, run_args) = statistics_args(args).unwrap_or_else(|err| {
+        eprintln!("{err}\nUsage: {program} --top K {USAGE}");
+        process::exit(2)
+    });
+    let day_dir = Path::new(DAY.dir);
+    let elves = match run_args.input.path(day_dir) {
+        None => Elves::read(io::stdin().lock(), n_top),
+        Some(path) if path.exists() => File::open(path).and_then(|file| Elves::read(BufReader::new(file), n_top)),
+        // From the input cache
+        Some(_) => Elves::read(read_input(&DAY, &run_args).as_bytes(), n_top),
+    };
+    match elves {
+        // Fine if whoever reads it has stopped, e.g. `head`
+        Ok(elves) => {
+            let _ = write!(io::stdout().lock(), "{elves}");
+        }
+        Err(err) => {
+            eprintln!("Could not read {}: {err}", run_args.input.name(day_dir));
+            process::exit(1)
+        }
+    }
+}
+
+fn statistics_args(args: Vec<String>) -> Result<(usize, RunArgs), UsageError> {
+    let mut n_top = None;
+    let mut run_args = vec![];
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--top" {
+            n_top = args.next().and_then(|value| value.parse().ok()).filter(|n_top| *n_top > 0);
+            if n_top.is_none() {
+                return Err(UsageError(format!("{arg} needs a number of elves, at least 1")));
+            }
+        } else {
+            run_args.push(arg)
+        }
+    }
+    let n_top = n_top.ok_or_else(|| UsageError(String::from("--top is needed")))?;
+    Ok((n_top, RunArgs::parse(run_args)?))
+}
+
+/// `size` elves, carrying a few snacks each
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let elves: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let n_snacks = rng.gen_range(1..=8);
+            generate::lines(rng, n_snacks, |rng, _| rng.gen_range(1_000..=60_000).to_string())
+        })
+        .collect();
+    elves.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn elves() -> impl Strategy<Value = Vec<Vec<usize>>> {
+        prop::collection::vec(prop::collection::vec(1..100_000usize, 0..8), 1..50)
+    }
+
+    fn input_text(elves: &[Vec<usize>]) -> String {
+        let groups: Vec<String> = elves.iter().map(|items| items.iter().map(|cal| format!("{cal}\n")).collect()).collect();
+        groups.join("\n")
+    }
+
+    proptest! {
+        #[test]
+        fn parts_match_brute_force(elves in elves()) {
+            let cals = parse_calories(&input_text(&elves)).unwrap();
+            let mut totals: Vec<usize> = elves.iter().map(|items| items.iter().sum()).collect();
+            prop_assert_eq!(cals.count(), totals.len());
+            prop_assert!(totals.iter().all(|total| *total <= part_1(&cals)));
+            // Take the largest three, one at a time
+            let mut top_3 = 0;
+            for _ in 0..3.min(totals.len()) {
+                let ind = (0..totals.len()).max_by_key(|ind| totals[*ind]).unwrap();
+                top_3 += totals.remove(ind);
+            }
+            prop_assert_eq!(part_2(&cals), top_3);
+        }
+
+        #[test]
+        fn statistics_match_sorting(elves in elves(), n_top in 1..6usize) {
+            let read = Elves::read(input_text(&elves).as_bytes(), n_top).unwrap();
+            let totals: Vec<usize> = elves.iter().map(|items| items.iter().sum()).collect();
+            let mut sorted: Vec<(usize, usize)> = totals.iter().copied().enumerate().collect();
+            sorted.sort_by_key(|(elf, total)| (Reverse(*total), *elf));
+            sorted.truncate(n_top);
+            prop_assert_eq!(read.top(), sorted);
+            let mut sorted: Vec<usize> = totals.clone();
+            sorted.sort();
+            let n = sorted.len();
+            prop_assert_eq!(read.median(), (sorted[(n - 1) / 2] + sorted[n / 2]) as f64 / 2.0);
+            prop_assert!((read.mean() - totals.iter().sum::<usize>() as f64 / n as f64).abs() < 1e-6);
+            let histogram = read.histogram(4);
+            prop_assert_eq!(histogram.iter().map(|(_, _, n_elves)| n_elves).sum::<usize>(), n);
+            prop_assert_eq!(histogram[0].0, sorted[0]);
+            prop_assert!(histogram.last().unwrap().1 >= sorted[n - 1]);
+        }
+    }
+
+    #[test]
+    fn test_statistics() {
+        let elves = Elves::read(include_str!("../test.txt").as_bytes(), 2).unwrap();
+        assert_eq!(elves.top(), vec![(3, 24000), (2, 11000)]);
+        assert_eq!((elves.count(), elves.mean(), elves.median()), (5, 11000.0, 10000.0));
+        assert_eq!(elves.histogram(2), vec![(4000, 14000, 4), (14001, 24001, 1)]);
+        let err = Elves::read("100\n\nx\n".as_bytes(), 1).unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 1: expected a number, found \"x\"");
+        assert!(statistics_args(vec![String::from("--top"), String::from("0")]).is_err());
+        assert_eq!(statistics_args(vec![String::from("--top"), String::from("5")]).unwrap().0, 5)
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(&parse_calories(include_str!("../test.txt")).unwrap()), 24000)
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(&parse_calories(include_str!("../test.txt")).unwrap()), 45000)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,168 @@
+use aoc_common::generate::{self, Rng, StdRng};
+use aoc_common::parse::{parse_lines, LineParser};
+use aoc_common::{Answer, Day, ParseError, Solution};
+
+pub struct Day10;
+
+pub const DAY: Day = Day::new::<Day10>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day10 {
+    type Input = Vec<Operation>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_prog(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1_and_2(input).0.into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_1_and_2(input).1.into_iter().collect::<String>().trim_end().to_string().into()
+    }
+}
+
+#[derive(Debug)]
+enum OpCode {
+    Addx {val: isize},
+    Noop
+}
+#[derive(Debug)]
+pub struct Operation {
+    op: OpCode,
+    cycles: isize
+}
+
+
+impl Operation {
+    fn parse(line: &mut LineParser) -> Result<Self, ParseError> {
+        if line.skip("addx ") {
+            let val = line.number::<isize>()?;
+            Ok(Operation{op: OpCode::Addx{val}, cycles: 2})
+        } else if line.skip("noop") {
+            Ok(Operation{op: OpCode::Noop, cycles: 1})
+        } else {
+            Err(line.error("addx or noop"))
+        }
+    }
+}
+
+fn parse_prog(input: &str) -> Result<Vec<Operation>, ParseError> {
+    let prog = parse_lines(input, Operation::parse)?;
+    if prog.is_empty() {
+        return Err(ParseError::new(1, 1, "", "addx or noop"))
+    }
+    Ok(prog)
+}
+
+fn part_1_and_2(prog: &[Operation]) -> (isize, Vec<char>)  {
+    let check_points: [isize; 6]= [20, 60,  100, 140, 180, 220];
+    let mut screen: Vec<char> = vec![];
+    let mut result: isize = 0;
+    let mut x: isize = 1;
+    let mut ins_ptr = 0;
+    let mut remaining_op_cycles = prog[ins_ptr].cycles;
+    for clock in 1..=240 {
+        
+        // Part 1 stuff
+        if check_points.contains(&clock) {
+            result += x * clock;
+        }
+
+
+        // Part 2 stuff
+        let sprite_positions = [x -1, x, x +1];
+        let scan_position = (clock - 1) % 40;
+        screen.push(if sprite_positions.contains(&scan_position) {
+            '#'
+        } else {
+            ' '
+        });
+        if scan_position == 39 {
+            screen.push('\n')
+        }
+
+        // Common stuff
+        remaining_op_cycles -= 1;
+        if remaining_op_cycles == 0 {
+            if let Operation{op: OpCode::Addx { val }, cycles: _} = prog[ins_ptr] {
+                x += val
+            }
+            ins_ptr += 1;
+            if ins_ptr < prog.len() {
+                remaining_op_cycles = prog[ins_ptr].cycles;
+            }
+        }
+    }
+    (result, screen)
+}
+
+/// A program of `size` instructions, about a third of them noop
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    generate::lines(rng, size.max(1), |rng, _| {
+        if rng.gen_ratio(1, 3) {
+            String::from("noop")
+        } else {
+            format!("addx {}", rng.gen_range(-20..=20))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn program() -> impl Strategy<Value = Vec<Option<isize>>> {
+        prop::collection::vec(prop::option::of(-20..=20isize), 1..150)
+    }
+
+    // The value of X during each cycle, and what it is once the program is done
+    fn reference_x(program: &[Option<isize>]) -> (Vec<isize>, isize) {
+        let mut x = 1;
+        let mut during = vec![];
+        for op in program {
+            during.push(x);
+            if let Some(val) = op {
+                during.push(x);
+                x += val
+            }
+        }
+        (during, x)
+    }
+
+    proptest! {
+        #[test]
+        fn signal_and_screen_match_reference(program in program()) {
+            let text: String = program.iter().map(|op| op.map_or(String::from("noop\n"), |val| format!("addx {val}\n"))).collect();
+            let (during, last) = reference_x(&program);
+            let x = |cycle: usize| during.get(cycle - 1).copied().unwrap_or(last);
+            let strength: isize = [20, 60, 100, 140, 180, 220].iter().map(|cycle| x(*cycle) * *cycle as isize).sum();
+            let screen: String = (1..=240)
+                .map(|cycle| {
+                    let pixel = if (x(cycle) - ((cycle - 1) % 40) as isize).abs() <= 1 { "#" } else { " " };
+                    if cycle % 40 == 0 { format!("{pixel}\n") } else { String::from(pixel) }
+                })
+                .collect();
+            let (signal, lit) = part_1_and_2(&parse_prog(&text).unwrap());
+            prop_assert_eq!(signal, strength);
+            prop_assert_eq!(lit.into_iter().collect::<String>(), screen);
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let prog = parse_prog(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_1_and_2(&prog).0, 13140)
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_prog("noop\naddx x3\n").err().unwrap();
+        assert_eq!(err, ParseError::new(2, 6, "x3", "a number"));
+        let err = parse_prog("noop\nmul 3\n").err().unwrap();
+        assert_eq!(err, ParseError::new(2, 1, "mul", "addx or noop"));
+        let err = parse_prog("").err().unwrap();
+        assert_eq!(err, ParseError::new(1, 1, "", "addx or noop"))
+    }
+}
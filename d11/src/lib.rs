@@ -0,0 +1,280 @@
+use aoc_common::generate::{Rng, SliceRandom, StdRng};
+use aoc_common::parse::Lines;
+use aoc_common::{Answer, Day, ParseError, Solution};
+
+pub struct Day11;
+
+pub const DAY: Day = Day::new::<Day11>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_monkeys(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1_and_2(input.clone(), 1).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_1_and_2(input.clone(), 2).into()
+    }
+}
+
+fn part_1_and_2(mut monkeys: Vec<Monkey>, part: usize) -> usize {
+    let n_rounds = if part == 1 { 20 } else { 10_000 };
+    let part_2_divisor = monkeys.iter().map(|m| m.test_value).product();
+    for _ in 0..n_rounds {
+        monkeys = monkey_round(monkeys, part, part_2_divisor)
+    }
+    let mut inspections: Vec<usize> = monkeys.iter().map(|m| m.n_inspections).collect();
+    inspections.sort();
+    inspections.into_iter().rev().take(2).product()
+}
+
+fn monkey_round(mut monkeys: Vec<Monkey>, part: usize, part_2_divisor: usize) -> Vec<Monkey> {
+    for ind in 0..monkeys.len() {
+        for (new_item_value, target) in monkeys[ind].new_items_with_targets(part, part_2_divisor) {
+            monkeys[target].items.push(new_item_value)
+        }
+        monkeys[ind].n_inspections += monkeys[ind].items.len();
+        monkeys[ind].items = vec![]
+}
+    monkeys
+}
+
+#[derive(Clone)]
+pub struct Monkey {
+    items: Vec<usize>,
+    operation: Operation,
+    test_value: usize,
+    true_target: usize,
+    false_target: usize,
+    n_inspections: usize,
+}
+
+impl Monkey {
+    fn new_items_with_targets(&self, part: usize, part_2_divisor: usize) -> Vec<(usize, usize)> {
+        self.items
+            .iter()
+            .map(|item| {
+                let new_item_value = self.operation.apply(*item);
+                if part == 1 {
+                    let new_item_value = new_item_value / 3;
+                    let target = self.get_target(new_item_value);
+                    (new_item_value, target)
+                } else {
+                    let target = self.get_target(new_item_value);
+                    let new_item_value = new_item_value % part_2_divisor;
+                    (new_item_value, target)
+                }
+            })
+            .collect()
+    }
+
+    fn get_target(&self, item: usize) -> usize {
+        if item.is_multiple_of(self.test_value) {
+            self.true_target
+        } else {
+            self.false_target
+        }
+    }
+
+    fn parse(lines: &mut Lines) -> Result<Self, ParseError> {
+        parse_number_line(lines, "Monkey ", ":")?;
+
+        let mut items_line = lines.expect_line("the starting items")?;
+        items_line.literal("  Starting items: ")?;
+        let mut items = vec![items_line.number::<usize>()?];
+        while items_line.skip(", ") {
+            items.push(items_line.number::<usize>()?)
+        }
+        items_line.end()?;
+
+        let mut operation_line = lines.expect_line("the operation")?;
+        operation_line.literal("  Operation: new = old ")?;
+        let operator = operation_line.one_of(&[("+ ", '+'), ("* ", '*')], "+ or *")?;
+        let operation = if operation_line.skip("old") {
+            match operator {
+                '+' => Operation::Multiply { value: 2 },
+                _ => Operation::Square,
+            }
+        } else {
+            let value = operation_line.number::<usize>()?;
+            match operator {
+                '+' => Operation::Add { value },
+                _ => Operation::Multiply { value },
+            }
+        };
+        operation_line.end()?;
+
+        let test_value = parse_number_line(lines, "  Test: divisible by ", "")?;
+        let true_target = parse_number_line(lines, "    If true: throw to monkey ", "")?;
+        let false_target = parse_number_line(lines, "    If false: throw to monkey ", "")?;
+
+        let n_inspections = 0;
+        Ok(Monkey {
+            items,
+            operation,
+            test_value,
+            true_target,
+            false_target,
+            n_inspections,
+        })
+    }
+}
+
+#[derive(Clone)]
+enum Operation {
+    Add { value: usize },
+    Multiply { value: usize },
+    Square,
+}
+
+impl Operation {
+    fn apply(&self, item: usize) -> usize {
+        match &self {
+            Operation::Add { value } => item + value,
+            Operation::Multiply { value } => item * value,
+            Operation::Square => item * item,
+        }
+    }
+}
+
+fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let mut lines = Lines::new(input);
+    let mut monkeys = vec![Monkey::parse(&mut lines)?];
+    // Monkeys are separated by blank lines
+    while let Some(line) = lines.next() {
+        line.end()?;
+        monkeys.push(Monkey::parse(&mut lines)?)
+    }
+    Ok(monkeys)
+}
+
+// A line like `  Test: divisible by 23`
+fn parse_number_line(lines: &mut Lines, prefix: &str, suffix: &str) -> Result<usize, ParseError> {
+    let mut line = lines.expect_line(&format!("{:?}", prefix.trim_start()))?;
+    line.literal(prefix)?;
+    let number = line.number::<usize>()?;
+    line.literal(suffix)?;
+    line.end()?;
+    Ok(number)
+}
+
+/// Up to eight monkeys holding `size` items between them, each at least one. The tests are for
+/// different primes, as in the puzzle, which keeps the worry levels of part 2 from overflowing. Part 1
+/// only divides them by three, so the operations never do more than triple them.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let n_monkeys = rng.gen_range(2..=8).min(size.max(2));
+    let mut items: Vec<Vec<usize>> = vec![vec![]; n_monkeys];
+    for ind in 0..size.max(n_monkeys) {
+        let monkey = if ind < n_monkeys { ind } else { rng.gen_range(0..n_monkeys) };
+        items[monkey].push(rng.gen_range(50..=99))
+    }
+    let mut primes = [2, 3, 5, 7, 11, 13, 17, 19];
+    primes.shuffle(rng);
+    let monkeys: Vec<String> = items
+        .into_iter()
+        .enumerate()
+        .map(|(ind, items)| {
+            let items: Vec<String> = items.iter().map(usize::to_string).collect();
+            let operation = match rng.gen_range(0..5) {
+                0 => String::from("+ old"),
+                1 | 2 => format!("+ {}", rng.gen_range(1..=8)),
+                _ => format!("* {}", rng.gen_range(2..=3)),
+            };
+            let others: Vec<usize> = (0..n_monkeys).filter(|other| *other != ind).collect();
+            format!(
+                "Monkey {ind}:\n  Starting items: {}\n  Operation: new = old {operation}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                items.join(", "),
+                primes[ind],
+                others.choose(rng).unwrap(),
+                others.choose(rng).unwrap()
+            )
+        })
+        .collect();
+    monkeys.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::generate;
+    use proptest::prelude::*;
+
+    // The inspections of each monkey, following every item as its worry level modulo each monkey's
+    // test instead of reducing it by their product (or dividing it by three, as in part 1)
+    fn reference_inspections(monkeys: &[Monkey], n_rounds: usize, relief: bool) -> Vec<usize> {
+        let tests: Vec<usize> = monkeys.iter().map(|monkey| monkey.test_value).collect();
+        // Each item as its worry level, if there is relief, or its remainders
+        let mut items: Vec<Vec<Vec<usize>>> = monkeys
+            .iter()
+            .map(|monkey| monkey.items.iter().map(|item| if relief { vec![*item] } else { tests.iter().map(|test| item % test).collect() }).collect())
+            .collect();
+        let mut inspections = vec![0; monkeys.len()];
+        for _ in 0..n_rounds {
+            for (ind, monkey) in monkeys.iter().enumerate() {
+                for item in std::mem::take(&mut items[ind]) {
+                    inspections[ind] += 1;
+                    let (item, divisible) = if relief {
+                        let worry = monkey.operation.apply(item[0]) / 3;
+                        (vec![worry], worry % monkey.test_value == 0)
+                    } else {
+                        let item: Vec<usize> = item.iter().zip(&tests).map(|(remainder, test)| monkey.operation.apply(*remainder) % test).collect();
+                        let divisible = item[ind] == 0;
+                        (item, divisible)
+                    };
+                    items[if divisible { monkey.true_target } else { monkey.false_target }].push(item)
+                }
+            }
+        }
+        inspections
+    }
+
+    fn monkey_business(inspections: &[usize]) -> usize {
+        let mut inspections = inspections.to_vec();
+        inspections.sort();
+        inspections.iter().rev().take(2).product()
+    }
+
+    proptest! {
+        #[test]
+        fn part_1_matches_reference(seed in any::<u64>(), size in 2..30usize) {
+            let monkeys = parse_monkeys(&generate(&mut generate::rng(seed), size)).unwrap();
+            prop_assert_eq!(part_1_and_2(monkeys.clone(), 1), monkey_business(&reference_inspections(&monkeys, 20, true)));
+        }
+
+        #[test]
+        fn reduced_worry_matches_remainders(seed in any::<u64>(), size in 2..30usize) {
+            let monkeys = parse_monkeys(&generate(&mut generate::rng(seed), size)).unwrap();
+            let divisor = monkeys.iter().map(|monkey| monkey.test_value).product();
+            let after = (0..200).fold(monkeys.clone(), |monkeys, _| monkey_round(monkeys, 2, divisor));
+            let inspections: Vec<usize> = after.iter().map(|monkey| monkey.n_inspections).collect();
+            prop_assert_eq!(inspections, reference_inspections(&monkeys, 200, false));
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let monkeys = parse_monkeys(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_1_and_2(monkeys, 1), 10605)
+    }
+
+    #[test]
+    fn test_part_2() {
+        let monkeys = parse_monkeys(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_1_and_2(monkeys, 2), 2713310158)
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = include_str!("../test.txt");
+        let err = parse_monkeys(&input.replace("new = old * 19", "new = old / 19")).err().unwrap();
+        assert_eq!(err, ParseError::new(3, 24, "/", "+ or *"));
+        let truncated: String = input.lines().take(4).map(|line| format!("{line}\n")).collect();
+        let err = parse_monkeys(&truncated).err().unwrap();
+        assert_eq!(err.to_string(), "line 5, column 1: expected \"If true: throw to monkey \", found nothing")
+    }
+}
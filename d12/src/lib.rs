@@ -0,0 +1,189 @@
+use aoc_common::generate::{self, Rng, StdRng};
+use aoc_common::search::{bfs, SearchProblem};
+use aoc_common::{Answer, Day, Grid, ParseError, Solution};
+
+pub struct Day12;
+
+pub const DAY: Day = Day::new::<Day12>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day12 {
+    type Input = HeightMap;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_grid(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).map_or(Answer::Missing, Answer::from)
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input).map_or(Answer::Missing, Answer::from)
+    }
+}
+
+pub struct HeightMap {
+    grid: Grid<char>,
+    start: (usize, usize),
+    target: (usize, usize),
+}
+
+fn part_1(height_map: &HeightMap) -> Option<usize> {
+    bfs(&Hike { height_map, downwards: false }).found.map(|found| found.n_steps())
+}
+
+fn part_2(height_map: &HeightMap) -> Option<usize> {
+    // The shortest way from any a to E is the shortest way back down from E to an a
+    bfs(&Hike { height_map, downwards: true }).found.map(|found| found.n_steps())
+}
+
+fn elevation(square: char) -> u8 {
+    match square {
+        'S' => b'a',
+        'E' => b'z',
+        _ => square as u8,
+    }
+}
+
+// Climbing at most one step up at a time from S to E, or walking it backwards from E down to any
+// square at elevation a
+struct Hike<'a> {
+    height_map: &'a HeightMap,
+    downwards: bool,
+}
+
+impl SearchProblem for Hike<'_> {
+    type State = (usize, usize);
+
+    fn start(&self) -> (usize, usize) {
+        if self.downwards {
+            self.height_map.target
+        } else {
+            self.height_map.start
+        }
+    }
+
+    fn successors(&self, current: &(usize, usize)) -> Vec<((usize, usize), usize)> {
+        let grid = &self.height_map.grid;
+        let height = elevation(grid[*current]);
+        grid.neighbours_4(*current)
+            .filter(|next| {
+                let next_height = elevation(grid[*next]);
+                if self.downwards {
+                    height <= next_height + 1
+                } else {
+                    next_height <= height + 1
+                }
+            })
+            .map(|next| (next, 1))
+            .collect()
+    }
+
+    fn is_goal(&self, current: &(usize, usize)) -> bool {
+        if self.downwards {
+            elevation(self.height_map.grid[*current]) == b'a'
+        } else {
+            *current == self.height_map.target
+        }
+    }
+}
+
+fn load_grid(input: &str) -> Result<HeightMap, ParseError> {
+    let grid = Grid::parse(input, "a height (a-z), S or E", |c| {
+        (c.is_ascii_lowercase() || c == 'S' || c == 'E').then_some(c)
+    })?;
+    let find = |c: char| {
+        grid.find(|cell| *cell == c)
+            .ok_or_else(|| ParseError::new(grid.n_rows() + 1, 1, "", &format!("a square marked {c}")))
+    };
+    let start = find('S')?;
+    let target = find('E')?;
+    Ok(HeightMap { grid, start, target })
+}
+
+/// A map of `size` rows, and three times as many columns, climbing steadily from S in the upper left
+/// corner to E in the lower right. A winding path keeps its elevation; the squares beside it may be
+/// a bit lower, and the map is made large enough that the path can get all the way up to z.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let n_rows = size.max(5);
+    let n_cols = (3 * n_rows).max(27_usize.saturating_sub(n_rows));
+    let distance = n_rows + n_cols - 2;
+    let mut path = vec![(0, 0)];
+    let (mut row, mut col) = (0, 0);
+    while (row, col) != (n_rows - 1, n_cols - 1) {
+        if col == n_cols - 1 || (row < n_rows - 1 && rng.gen_bool(0.5)) {
+            row += 1
+        } else {
+            col += 1
+        }
+        path.push((row, col))
+    }
+    generate::grid(rng, n_rows, n_cols, |rng, (row, col)| {
+        let elevation = ((row + col) * 25 / distance) as u8;
+        match (row, col) {
+            (0, 0) => 'S',
+            _ if (row, col) == (n_rows - 1, n_cols - 1) => 'E',
+            _ if path.contains(&(row, col)) => (b'a' + elevation) as char,
+            _ => (b'a' + elevation - rng.gen_range(0..=elevation.min(3))) as char,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // The fewest steps from each square to E, lowering them until nothing changes
+    fn reference_distances(rows: &[Vec<char>]) -> Vec<Vec<usize>> {
+        let (n_rows, n_cols) = (rows.len(), rows[0].len());
+        let mut distances: Vec<Vec<usize>> = rows.iter().map(|row| row.iter().map(|square| if *square == 'E' { 0 } else { usize::MAX }).collect()).collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (row, col) in (0..n_rows).flat_map(|row| (0..n_cols).map(move |col| (row, col))) {
+                let neighbours = [(row.wrapping_sub(1), col), (row + 1, col), (row, col.wrapping_sub(1)), (row, col + 1)];
+                for (next_row, next_col) in neighbours.into_iter().filter(|(r, c)| *r < n_rows && *c < n_cols) {
+                    let climbable = elevation(rows[next_row][next_col]) <= elevation(rows[row][col]) + 1;
+                    let through_next = distances[next_row][next_col].saturating_add(1);
+                    if climbable && through_next < distances[row][col] {
+                        distances[row][col] = through_next;
+                        changed = true
+                    }
+                }
+            }
+        }
+        distances
+    }
+
+    proptest! {
+        #[test]
+        fn hikes_match_reference(seed in any::<u64>(), size in 5..10usize) {
+            let input = generate(&mut generate::rng(seed), size);
+            let rows: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+            let distances = reference_distances(&rows);
+            let from = |at_start: &dyn Fn(char) -> bool| {
+                rows.iter().flatten().zip(distances.iter().flatten()).filter(|(square, _)| at_start(**square)).map(|(_, distance)| *distance).min().filter(|distance| *distance != usize::MAX)
+            };
+            let height_map = load_grid(&input).unwrap();
+            prop_assert_eq!(part_1(&height_map), from(&|square| square == 'S'));
+            prop_assert_eq!(part_2(&height_map), from(&|square| elevation(square) == b'a'));
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(&load_grid(include_str!("../test.txt")).unwrap()), Some(31))
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(&load_grid(include_str!("../test.txt")).unwrap()), Some(29))
+    }
+
+    #[test]
+    fn test_no_route() {
+        let height_map = load_grid("SE\n").unwrap();
+        assert_eq!((Day12::part_1(&height_map), Day12::part_2(&height_map)), (Answer::Missing, Answer::Missing))
+    }
+}
@@ -0,0 +1,273 @@
+use aoc_common::generate::{Rng, StdRng};
+use aoc_common::parse::{LineParser, Lines};
+use aoc_common::{Answer, Day, ParseError, Solution};
+use std::cmp::Ordering;
+
+pub struct Day13;
+
+pub const DAY: Day = Day::new::<Day13>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day13 {
+    type Input = Vec<(PacketValue, PacketValue)>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_packet_pairs(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input.clone()).into()
+    }
+}
+
+fn part_1(packet_pairs: &[(PacketValue, PacketValue)]) -> usize {
+    packet_pairs
+        .iter()
+        .enumerate()
+        .map(|(ind, (p1, p2))| if p1 <= p2 { ind + 1 } else { 0 })
+        .sum()
+}
+
+fn part_2(packet_pairs: Vec<(PacketValue, PacketValue)>) -> usize {
+    let mut packets: Vec<_> = packet_pairs
+        .into_iter()
+        .flat_map(|(p1, p2)| vec![p1, p2])
+        .collect();
+    let divider_1 = PacketValue::divider(2);
+    let divider_2 = PacketValue::divider(6);
+    packets.push(divider_1.clone());
+    packets.push(divider_2.clone());
+    packets.sort();
+    let div_1_index = packets.iter().position(|p| p == &divider_1).unwrap();
+    let div_2_index = packets.iter().position(|p| p == &divider_2).unwrap();
+    (div_1_index + 1) * (div_2_index + 1)
+}
+
+#[derive(Eq, Debug, Clone)]
+pub enum PacketValue {
+    Integer { value: usize },
+    List { value: Vec<PacketValue> },
+}
+
+impl Ord for PacketValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self {
+            PacketValue::Integer { value: my_value } => match other {
+                PacketValue::Integer { value: other_value } => my_value.cmp(other_value),
+                PacketValue::List { value: _other_list } => PacketValue::List {
+                    value: vec![PacketValue::Integer { value: *my_value }],
+                }
+                .cmp(other),
+            },
+            PacketValue::List { value: my_list } => match other {
+                PacketValue::Integer { value: other_value } => self.cmp(&PacketValue::List {
+                    value: vec![PacketValue::Integer {
+                        value: *other_value,
+                    }],
+                }),
+                PacketValue::List { value: other_list } => {
+                    let min_length = my_list.len().min(other_list.len());
+                    for ind in 0..min_length {
+                        if my_list[ind] != other_list[ind] {
+                            return my_list[ind].cmp(&other_list[ind]);
+                        } else {
+                            continue;
+                        }
+                    }
+                    my_list.len().cmp(&other_list.len())
+                }
+            }
+        }
+    }
+}
+
+impl PartialOrd for PacketValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for PacketValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PacketValue {
+    // [[value]]
+    fn divider(value: usize) -> Self {
+        let inner = PacketValue::List { value: vec![PacketValue::Integer { value }] };
+        PacketValue::List { value: vec![inner] }
+    }
+
+    fn parse(line: &mut LineParser) -> Result<Self, ParseError> {
+        if line.skip("[") {
+            Self::parse_list(line)
+        } else {
+            Ok(PacketValue::Integer { value: line.number()? })
+        }
+    }
+
+    // After the opening bracket
+    fn parse_list(line: &mut LineParser) -> Result<PacketValue, ParseError> {
+        let mut value: Vec<PacketValue> = vec![];
+        if !line.skip("]") {
+            loop {
+                value.push(PacketValue::parse(line)?);
+                if line.skip("]") {
+                    break;
+                }
+                if !line.skip(",") {
+                    return Err(line.error(", or ]"));
+                }
+            }
+        }
+        Ok(PacketValue::List { value })
+    }
+}
+
+fn load_packet_pairs(input: &str) -> Result<Vec<(PacketValue, PacketValue)>, ParseError> {
+    let mut lines = Lines::new(input);
+    let mut packet_pairs = vec![];
+    loop {
+        let first = load_packet(&mut lines)?;
+        let second = load_packet(&mut lines)?;
+        packet_pairs.push((first, second));
+        // Pairs are separated by blank lines
+        match lines.next() {
+            Some(line) => line.end()?,
+            None => break,
+        }
+    }
+    Ok(packet_pairs)
+}
+
+fn load_packet(lines: &mut Lines) -> Result<PacketValue, ParseError> {
+    let mut line = lines.expect_line("a packet")?;
+    let packet = PacketValue::parse(&mut line)?;
+    line.end()?;
+    Ok(packet)
+}
+
+/// `size` pairs of packets
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let pairs: Vec<String> =
+        (0..size.max(1)).map(|_| format!("{}\n{}\n", generate_packet(rng, 0), generate_packet(rng, 0))).collect();
+    pairs.join("\n")
+}
+
+// A list of up to four values, lists themselves down to a depth of three
+fn generate_packet(rng: &mut StdRng, depth: usize) -> String {
+    let values: Vec<String> = (0..rng.gen_range(0..=4))
+        .map(|_| if depth < 3 && rng.gen_ratio(1, 3) { generate_packet(rng, depth + 1) } else { rng.gen_range(0..=10).to_string() })
+        .collect();
+    format!("[{}]", values.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // A packet as a plain tree, separate from `PacketValue` so the comparison can be checked against it
+    #[derive(Debug, Clone)]
+    enum Tree {
+        Integer(usize),
+        List(Vec<Tree>),
+    }
+
+    impl Tree {
+        fn text(&self) -> String {
+            match self {
+                Tree::Integer(value) => value.to_string(),
+                Tree::List(items) => format!("[{}]", items.iter().map(Tree::text).collect::<Vec<_>>().join(",")),
+            }
+        }
+    }
+
+    // Packets are always lists
+    fn packet() -> impl Strategy<Value = Tree> {
+        let leaf = (0..11usize).prop_map(Tree::Integer);
+        let tree = leaf.prop_recursive(4, 32, 5, |inner| prop::collection::vec(inner, 0..5).prop_map(Tree::List));
+        prop::collection::vec(tree, 0..5).prop_map(Tree::List)
+    }
+
+    // The rules from the puzzle, step by step
+    fn reference_cmp(left: &Tree, right: &Tree) -> Ordering {
+        match (left, right) {
+            (Tree::Integer(left), Tree::Integer(right)) => left.cmp(right),
+            (Tree::Integer(_), Tree::List(_)) => reference_cmp(&Tree::List(vec![left.clone()]), right),
+            (Tree::List(_), Tree::Integer(_)) => reference_cmp(left, &Tree::List(vec![right.clone()])),
+            (Tree::List(left), Tree::List(right)) => {
+                for (left, right) in left.iter().zip(right) {
+                    match reference_cmp(left, right) {
+                        Ordering::Equal => continue,
+                        unequal => return unequal,
+                    }
+                }
+                left.len().cmp(&right.len())
+            }
+        }
+    }
+
+    fn parse_packet(tree: &Tree) -> PacketValue {
+        let text = tree.text();
+        PacketValue::parse(&mut LineParser::new(1, &text)).unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn cmp_matches_reference(left in packet(), right in packet()) {
+            let (left_packet, right_packet) = (parse_packet(&left), parse_packet(&right));
+            prop_assert_eq!(left_packet.cmp(&right_packet), reference_cmp(&left, &right));
+            prop_assert_eq!(right_packet.cmp(&left_packet), left_packet.cmp(&right_packet).reverse());
+            prop_assert_eq!(left_packet.cmp(&left_packet), Ordering::Equal);
+        }
+
+        #[test]
+        fn cmp_is_transitive(a in packet(), b in packet(), c in packet()) {
+            let mut packets = [parse_packet(&a), parse_packet(&b), parse_packet(&c)];
+            packets.sort();
+            prop_assert!(packets[0] <= packets[2]);
+        }
+
+        #[test]
+        fn parts_match_brute_force(pairs in prop::collection::vec((packet(), packet()), 1..20)) {
+            let input = pairs
+                .iter()
+                .map(|(left, right)| format!("{}\n{}\n", left.text(), right.text()))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let packet_pairs = load_packet_pairs(&input).unwrap();
+            let right_order: usize = pairs
+                .iter()
+                .enumerate()
+                .filter(|(_, (left, right))| reference_cmp(left, right) != Ordering::Greater)
+                .map(|(ind, _)| ind + 1)
+                .sum();
+            prop_assert_eq!(part_1(&packet_pairs), right_order);
+
+            // Each divider ends up after every packet that is smaller than it
+            let dividers = [Tree::List(vec![Tree::List(vec![Tree::Integer(2)])]), Tree::List(vec![Tree::List(vec![Tree::Integer(6)])])];
+            let packets: Vec<&Tree> = pairs.iter().flat_map(|(left, right)| [left, right]).collect();
+            let n_smaller = |divider: &Tree| packets.iter().filter(|packet| reference_cmp(packet, divider) == Ordering::Less).count();
+            let decoder_key = (n_smaller(&dividers[0]) + 1) * (n_smaller(&dividers[1]) + 2);
+            prop_assert_eq!(part_2(packet_pairs), decoder_key);
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let packet_pairs = load_packet_pairs(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_1(&packet_pairs), 13)
+    }
+
+    #[test]
+    fn test_part_2() {
+        let packet_pairs = load_packet_pairs(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_2(packet_pairs), 140)
+    }
+}
@@ -0,0 +1,298 @@
+use aoc_common::frames::{Frame, Palette, Recorder, Visualize};
+use aoc_common::generate::{self, Rng, StdRng};
+use aoc_common::parse::{parse_lines, LineParser};
+use aoc_common::sparse_grid::Legend;
+use aoc_common::{Answer, Day, ParseError, Part, Point2, Solution, SparseGrid, Vec2};
+use std::collections::HashMap;
+
+pub struct Day14;
+
+pub const DAY: Day = Day::new::<Day14>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR"))
+    .with_frames::<Day14>()
+    .with_generator(generate);
+
+impl Solution for Day14 {
+    type Input = SparseGrid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_cave(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input.clone(), &mut Recorder::off()).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input.clone(), &mut Recorder::off()).into()
+    }
+}
+
+// A frame for every unit of sand that comes to rest
+impl Visualize for Day14 {
+    fn simulate(input: &Self::Input, part: Part, recorder: &mut Recorder) {
+        match part {
+            Part::One => part_1(input.clone(), recorder),
+            Part::Two => part_2(input.clone(), recorder),
+        };
+    }
+
+    fn palette() -> Palette {
+        Palette::new([0x0f, 0x0f, 0x23]).with('#', [0x80, 0x80, 0x80]).with('o', [0xc2, 0xb2, 0x80])
+    }
+}
+
+fn legend() -> Legend<char> {
+    Legend::new('.', "air").with('#', '#', "rock").with('o', 'o', "sand")
+}
+
+fn rock_bottoms(cave: &SparseGrid<char>) -> HashMap<isize, isize> {
+    let mut bottoms: HashMap<isize, isize> = HashMap::new();
+    for (rock_y, rock_x) in cave.positions() {
+        if let Some(bottom_y) = bottoms.get(&rock_x) {
+            if rock_y > *bottom_y {
+                bottoms.insert(rock_x, rock_y);
+            }
+        } else {
+            bottoms.insert(rock_x, rock_y);
+        }
+    }
+    bottoms
+}
+
+// Where a unit of sand at `sand` goes next: down, down to the left or down to the right,
+// `None` if it comes to rest
+fn next_position(sand: Point2, cave: &SparseGrid<char>) -> Option<Point2> {
+    [Vec2::new(0, 1), Vec2::new(-1, 1), Vec2::new(1, 1)]
+        .into_iter()
+        .map(|delta| sand + delta)
+        .find(|next| !cave.contains(cell(*next)))
+}
+
+// The cave is kept as (row, col), that is (y, x), so that it can be drawn
+fn cell(point: Point2) -> (isize, isize) {
+    (point.y, point.x)
+}
+
+const SOURCE: Point2 = Point2::new(500, 0);
+
+fn part_2(mut cave: SparseGrid<char>, recorder: &mut Recorder) -> usize {
+    let ((_, _), (max_y, _)) = cave.bounds().unwrap();
+    let floor = max_y + 2;
+    // The sand piles up at most 45 degrees to the sides, so that is as wide as the floor needs to be
+    for x in (SOURCE.x - floor)..=(SOURCE.x + floor) {
+        cave.insert((floor, x), '#');
+    }
+    let legend = legend();
+    let mut n_sands = 0;
+    let mut full = false;
+    loop {
+        let mut sand = SOURCE;
+        loop {
+            if let Some(next) = next_position(sand, &cave) {
+                sand = next
+            } else {
+                cave.insert(cell(sand), 'o');
+                recorder.record(|| Frame::from_sparse(&cave, &legend));
+                if sand == SOURCE {
+                    n_sands += 1;
+                    full = true;
+                }
+                break;
+            }
+        }
+        if !full {
+            n_sands += 1;
+        } else {
+            break;
+        }
+    }
+    recorder.finish(|| Frame::from_sparse(&cave, &legend));
+    n_sands
+}
+
+fn part_1(mut cave: SparseGrid<char>, recorder: &mut Recorder) -> usize {
+    let bottoms = rock_bottoms(&cave);
+    let legend = legend();
+    let mut n_sands = 0;
+    let mut into_the_abyss = false;
+    loop {
+        let mut sand = SOURCE;
+        loop {
+            if let Some(next) = next_position(sand, &cave) {
+                sand = next
+            } else {
+                cave.insert(cell(sand), 'o');
+                recorder.record(|| Frame::from_sparse(&cave, &legend));
+                break;
+            }
+            if let Some(bottom_y) = bottoms.get(&sand.x) {
+                if sand.y > *bottom_y {
+                    into_the_abyss = true;
+                    break;
+                }
+            } else {
+                into_the_abyss = true;
+                break;
+            }
+        }
+        if !into_the_abyss {
+            n_sands += 1;
+        } else {
+            break;
+        }
+    }
+    recorder.finish(|| Frame::from_sparse(&cave, &legend));
+    n_sands
+}
+
+fn parse_cave(input: &str) -> Result<SparseGrid<char>, ParseError> {
+    let mut cave: SparseGrid<char> = SparseGrid::new();
+    let paths = parse_lines(input, |line| {
+        let mut corners = vec![parse_corner(line)?];
+        while line.skip(" -> ") {
+            corners.push(parse_corner(line)?)
+        }
+        Ok(corners)
+    })?;
+    if paths.is_empty() {
+        return Err(ParseError::new(1, 1, "", "a path of rock"))
+    }
+    for corners in paths {
+        let mut rock = corners[0];
+        cave.insert(cell(rock), '#');
+        for end in &corners[1..] {
+            while rock != *end {
+                rock = rock.step_towards(*end);
+                cave.insert(cell(rock), '#');
+            }
+        }
+    }
+    Ok(cave)
+}
+
+fn parse_corner(line: &mut LineParser) -> Result<Point2, ParseError> {
+    let x = line.number::<isize>()?;
+    line.literal(",")?;
+    let y = line.number::<isize>()?;
+    Ok(Point2::new(x, y))
+}
+
+/// `size` paths of rock below the source of the sand, going straight across and down by turns. One
+/// column near the source is left without rock: sand that gets there falls into the abyss, so the
+/// sand of part 1 can't pile up all the way to the source and keep coming forever. That only holds
+/// if the pile would have to cover the column above the rock, hence the gap is nearer to the source
+/// sideways than the rock is downwards.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let sign = |rng: &mut StdRng| if rng.gen_bool(0.5) { 1 } else { -1 };
+    let gap = SOURCE.x + rng.gen_range(3..=12) * sign(rng);
+    generate::lines(rng, size.max(1), |rng, _| {
+        let (mut x, mut y) = (gap, rng.gen_range(13..=80));
+        while x == gap {
+            x = rng.gen_range(485..=515)
+        }
+        // The side of the gap that the whole path stays on
+        let side = (x - gap).signum();
+        let mut corners = vec![format!("{x},{y}")];
+        for ind in 0..rng.gen_range(1..=4_usize) {
+            if ind.is_multiple_of(2) {
+                x += rng.gen_range(2..=10) * sign(rng);
+                if (x - gap).signum() != side {
+                    x = gap + side
+                }
+            } else {
+                y += rng.gen_range(2..=10)
+            }
+            corners.push(format!("{x},{y}"))
+        }
+        corners.join(" -> ")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    // Drops sand one square at a time until it falls below all the rock, or, with a floor two below
+    // the lowest rock, until it blocks the source
+    fn reference_sand(input: &str, floor: bool) -> usize {
+        let mut blocked: HashSet<(isize, isize)> = HashSet::new();
+        for line in input.lines() {
+            let corners: Vec<(isize, isize)> = line
+                .split(" -> ")
+                .map(|corner| {
+                    let (x, y) = corner.split_once(',').unwrap();
+                    (x.parse().unwrap(), y.parse().unwrap())
+                })
+                .collect();
+            for pair in corners.windows(2) {
+                let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+                for x in x0.min(x1)..=x0.max(x1) {
+                    for y in y0.min(y1)..=y0.max(y1) {
+                        blocked.insert((x, y));
+                    }
+                }
+            }
+        }
+        let lowest = blocked.iter().map(|(_, y)| *y).max().unwrap();
+        let mut n_sands = 0;
+        while !blocked.contains(&(500, 0)) {
+            let (mut x, mut y) = (500, 0);
+            while let Some(dx) = [0, -1, 1].into_iter().find(|dx| !(blocked.contains(&(x + dx, y + 1)) || (floor && y + 1 == lowest + 2))) {
+                (x, y) = (x + dx, y + 1);
+                if y > lowest + 2 {
+                    return n_sands
+                }
+            }
+            blocked.insert((x, y));
+            n_sands += 1
+        }
+        n_sands
+    }
+
+    proptest! {
+        // Each case pours thousands of units of sand
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn sand_matches_reference(seed in any::<u64>(), size in 1..8usize) {
+            let input = generate(&mut generate::rng(seed), size);
+            let cave = parse_cave(&input).unwrap();
+            prop_assert_eq!(part_1(cave.clone(), &mut Recorder::off()), reference_sand(&input, false));
+            prop_assert_eq!(part_2(cave, &mut Recorder::off()), reference_sand(&input, true));
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let cave = parse_cave(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_1(cave, &mut Recorder::off()), 24)
+    }
+
+    #[test]
+    fn test_part_2() {
+        let cave = parse_cave(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_2(cave, &mut Recorder::off()), 93)
+    }
+
+    #[test]
+    fn test_frames() {
+        let cave = parse_cave(include_str!("../test.txt")).unwrap();
+        let mut recorder = Recorder::every(1);
+        part_1(cave, &mut recorder);
+        let frames = recorder.into_frames();
+        assert_eq!(frames.len(), 24);
+        let last = frames.last().unwrap().to_string();
+        assert_eq!(last.matches('o').count(), 24)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse_cave("").err().unwrap(), ParseError::new(1, 1, "", "a path of rock"));
+        // A path can be a single rock
+        let cave = parse_cave("500,2\n").unwrap();
+        assert!(cave.contains((2, 500)));
+        assert_eq!(part_2(cave, &mut Recorder::off()), 15)
+    }
+}
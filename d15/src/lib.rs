@@ -0,0 +1,267 @@
+use aoc_common::generate::{self, Rng, StdRng};
+use aoc_common::parse::{parse_lines, LineParser};
+use aoc_common::{Answer, Day, ParseError, Point2, Solution};
+use std::ops::RangeInclusive;
+
+pub struct Day15;
+
+pub const DAY: Day = Day::new::<Day15>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day15 {
+    type Input = Vec<(Point2, Point2)>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input, search_area(input).0).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input, search_area(input).1).into()
+    }
+}
+
+// The example asks about row 10 and a 20x20 area instead of the real puzzle's
+// row 2000000 and 4000000x4000000, and its coordinates are tiny in comparison
+fn search_area(sensors_and_beacons: &[(Point2, Point2)]) -> (isize, isize) {
+    let max_sensor_coord = sensors_and_beacons
+        .iter()
+        .map(|(sensor, _)| sensor.x.max(sensor.y))
+        .max()
+        .unwrap();
+    if max_sensor_coord <= 100 {
+        (10, 20)
+    } else {
+        (2_000_000, 4_000_000)
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<(Point2, Point2)>, ParseError> {
+    let sensors_and_beacons = parse_lines(input, |line| {
+        line.literal("Sensor at ")?;
+        let sensor = parse_position(line)?;
+        line.literal(": closest beacon is at ")?;
+        let beacon = parse_position(line)?;
+        Ok((sensor, beacon))
+    })?;
+    if sensors_and_beacons.is_empty() {
+        return Err(ParseError::new(1, 1, "", "a sensor"));
+    }
+    Ok(sensors_and_beacons)
+}
+
+// x=2, y=18
+fn parse_position(line: &mut LineParser) -> Result<Point2, ParseError> {
+    line.literal("x=")?;
+    let x = line.number::<isize>()?;
+    line.literal(", y=")?;
+    let y = line.number::<isize>()?;
+    Ok(Point2::new(x, y))
+}
+
+fn compute_merged_ranges_for_row(
+    sensors_and_beacons: &[(Point2, Point2)],
+    row: isize,
+) -> Vec<RangeInclusive<isize>> {
+    sensors_and_beacons
+        .iter()
+        .map(|(sensor, beacon)| intersecting_range(*sensor, *beacon, row))
+        .fold(vec![], |acc, range| {
+            if let Some(r) = range {
+                merge_range_with_ranges(r, acc)
+            } else {
+                acc
+            }
+        })
+}
+
+fn part_1(sensors_and_beacons: &[(Point2, Point2)], row: isize) -> usize {
+    let merged_ranges = compute_merged_ranges_for_row(sensors_and_beacons, row);
+    // The beacons in the row are within reach of their sensors, but a beacon can be there
+    let mut beacons: Vec<isize> = sensors_and_beacons.iter().filter(|(_, beacon)| beacon.y == row).map(|(_, beacon)| beacon.x).collect();
+    beacons.sort_unstable();
+    beacons.dedup();
+    merged_ranges
+        .iter()
+        .map(|r| (r.end() - r.start() + 1) as usize)
+        .sum::<usize>()
+        - beacons.len()
+}
+
+fn part_2(sensors_and_beacons: &[(Point2, Point2)], max_coord: isize) -> usize {
+    for row in 0..=max_coord {
+        let merged_ranges = compute_merged_ranges_for_row(sensors_and_beacons, row);
+        let pruned_ranges = prune_ranges(merged_ranges, max_coord);
+        if let Some(gap) = find_gap(pruned_ranges, max_coord) {
+            return (gap * 4_000_000 + row) as usize;
+        }
+    }
+    panic!("Could not find beacon.")
+}
+
+// The first position from 0 that none of the ranges covers, if it isn't past `max_coord`
+fn find_gap(mut ranges: Vec<RangeInclusive<isize>>, max_coord: isize) -> Option<isize> {
+    ranges.sort_by_key(|r| *r.start());
+    let mut first_free = 0;
+    for r in ranges {
+        if *r.start() > first_free {
+            break;
+        }
+        first_free = first_free.max(r.end() + 1);
+    }
+    (first_free <= max_coord).then_some(first_free)
+}
+
+fn prune_ranges(
+    mut ranges: Vec<RangeInclusive<isize>>,
+    max_coord: isize,
+) -> Vec<RangeInclusive<isize>> {
+    ranges = ranges
+        .into_iter()
+        .filter(|r| *r.end() >= 0 && *r.start() <= max_coord)
+        .map(|r| {
+            if *r.start() >= 0 && *r.end() <= max_coord {
+                r
+            } else {
+                0.max(*r.start())..=max_coord.min(*r.end())
+            }
+        })
+        .collect();
+    ranges
+}
+
+fn ranges_overlapping(r1: &RangeInclusive<isize>, r2: &RangeInclusive<isize>) -> bool {
+    r1.contains(r2.start())
+        || r1.contains(r2.end())
+        || r2.contains(r1.start())
+        || r2.contains(r1.end())
+}
+
+fn merge_range_with_ranges(
+    range: RangeInclusive<isize>,
+    ranges: Vec<RangeInclusive<isize>>,
+) -> Vec<RangeInclusive<isize>> {
+    let (mut overlapping, mut ranges): (Vec<_>, Vec<_>) = ranges
+        .into_iter()
+        .partition(|r| ranges_overlapping(r, &range));
+    if !overlapping.is_empty() {
+        overlapping.push(range);
+        let range_min = overlapping.iter().map(|r| r.start()).min().unwrap();
+        let range_max = overlapping.iter().map(|r| r.end()).max().unwrap();
+        ranges.push(*range_min..=*range_max);
+    } else {
+        ranges.push(range);
+    }
+    ranges
+}
+
+fn intersecting_range(sensor: Point2, beacon: Point2, row: isize) -> Option<RangeInclusive<isize>> {
+    let reach = sensor.manhattan(beacon);
+    let row_min = sensor.y - reach;
+    let row_max = sensor.y + reach;
+    if row < row_min || row > row_max {
+        None
+    } else {
+        let intersection_half_length = reach - (sensor.y - row).abs();
+        Some((sensor.x - intersection_half_length)..=(sensor.x + intersection_half_length))
+    }
+}
+
+/// `size` sensors, none of which reaches as far as a hidden position, so that part 2 always finds a
+/// beacon that could be there
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let hidden = (rng.gen_range(0..=4_000_000), rng.gen_range(0..=4_000_000));
+    generate::lines(rng, size.max(1), |rng, _| {
+        let (sensor, distance) = loop {
+            let sensor: (isize, isize) = (rng.gen_range(0..=4_000_000), rng.gen_range(0..=4_000_000));
+            let distance = (sensor.0 - hidden.0).abs() + (sensor.1 - hidden.1).abs();
+            if distance > 1 {
+                break (sensor, distance);
+            }
+        };
+        let reach = rng.gen_range(distance / 2..distance);
+        let dx = rng.gen_range(0..=reach);
+        let mut sign = || if rng.gen_bool(0.5) { 1 } else { -1 };
+        let beacon = (sensor.0 + sign() * dx, sensor.1 + sign() * (reach - dx));
+        format!("Sensor at x={}, y={}: closest beacon is at x={}, y={}", sensor.0, sensor.1, beacon.0, beacon.1)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Sensors around a 21x21 area with a position hidden from all of them, as in the example
+    fn sensors_and_beacons() -> impl Strategy<Value = Vec<(Point2, Point2)>> {
+        let sensor = (-5..=25isize, -5..=25isize, any::<prop::sample::Index>(), any::<prop::sample::Index>(), any::<bool>(), any::<bool>());
+        ((0..=20isize, 0..=20isize), prop::collection::vec(sensor, 1..8)).prop_map(|(hidden, sensors)| {
+            let hidden = Point2::new(hidden.0, hidden.1);
+            sensors
+                .into_iter()
+                .filter(|(x, y, ..)| Point2::new(*x, *y) != hidden)
+                .map(|(x, y, reach, dx, flip_x, flip_y)| {
+                    let sensor = Point2::new(x, y);
+                    // Not as far as the hidden position
+                    let reach = reach.index(sensor.manhattan(hidden) as usize) as isize;
+                    let dx = dx.index(reach as usize + 1) as isize;
+                    let beacon = Point2::new(sensor.x + if flip_x { -dx } else { dx }, sensor.y + if flip_y { dx - reach } else { reach - dx });
+                    (sensor, beacon)
+                })
+                .collect()
+        })
+        .prop_filter("a sensor", |sensors_and_beacons: &Vec<(Point2, Point2)>| !sensors_and_beacons.is_empty())
+    }
+
+    fn covered(sensors_and_beacons: &[(Point2, Point2)], position: Point2) -> bool {
+        sensors_and_beacons.iter().any(|(sensor, beacon)| sensor.manhattan(position) <= sensor.manhattan(*beacon))
+    }
+
+    proptest! {
+        #[test]
+        fn part_1_matches_counting(sensors_and_beacons in sensors_and_beacons(), row in 0..=20isize) {
+            // Far enough to the sides for any sensor to reach
+            let n_positions = (-70..=90)
+                .map(|x| Point2::new(x, row))
+                .filter(|position| covered(&sensors_and_beacons, *position))
+                .filter(|position| sensors_and_beacons.iter().all(|(_, beacon)| beacon != position))
+                .count();
+            prop_assert_eq!(part_1(&sensors_and_beacons, row), n_positions);
+        }
+
+        #[test]
+        fn part_2_finds_a_hidden_position(sensors_and_beacons in sensors_and_beacons()) {
+            let frequency = part_2(&sensors_and_beacons, 20) as isize;
+            let position = Point2::new(frequency / 4_000_000, frequency % 4_000_000);
+            prop_assert!((0..=20).contains(&position.x) && (0..=20).contains(&position.y));
+            prop_assert!(!covered(&sensors_and_beacons, position));
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let sensors_and_beacons = parse_input(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_1(&sensors_and_beacons, 10), 26)
+    }
+
+    #[test]
+    fn test_part_2() {
+        let sensors_and_beacons = parse_input(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_2(&sensors_and_beacons, 20), 56000011)
+    }
+
+    #[test]
+    fn test_search_area() {
+        let sensors_and_beacons = parse_input(include_str!("../test.txt")).unwrap();
+        assert_eq!(search_area(&sensors_and_beacons), (10, 20))
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("Sensor at x=2, y=18: closest beacon is at x=-2 y=15\n").err().unwrap();
+        assert_eq!(err, ParseError::new(1, 47, "y=15", "\", y=\""));
+        assert_eq!(parse_input("").err().unwrap(), ParseError::new(1, 1, "", "a sensor"))
+    }
+}
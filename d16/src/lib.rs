@@ -0,0 +1,381 @@
+use aoc_common::generate::{self, Rng, StdRng};
+use aoc_common::parse::{parse_lines, LineParser};
+use aoc_common::{Answer, Day, ParseError, Solution};
+use aoc_common::search::{a_star, SearchProblem};
+use std::{collections::{HashMap, BTreeSet, BTreeMap}};
+use itertools::{Itertools};
+use tracing::info;
+
+pub struct Day16;
+
+pub const DAY: Day = Day::new::<Day16>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day16 {
+    type Input = PipeSystem;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(pre_process(parse_pipe_system(input)?))
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        explore(input, 1, 30).map_or(Answer::Missing, Answer::from)
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        explore(input, 2, 26).map_or(Answer::Missing, Answer::from)
+    }
+}
+
+#[derive(Debug, Clone, Ord, PartialOrd, PartialEq, Eq, Hash)]
+struct Valve {
+    label: String,
+    capacity: usize,
+    connected_valves: Vec<String>
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+struct Explorer {
+    round: usize,
+    current_valve: String
+}
+
+// The explorers are kept sorted, so the first one is the one that is the least far, and an explorer
+// that has stopped is left at round n_rounds
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct ExplorationState {
+    explorers: Vec<Explorer>,
+    open_valves: BTreeSet<String>,
+}
+
+// The most pressure released is the least cost when opening a valve in a round costs its capacity
+// times the round, and a valve that is never opened costs its capacity times the number of rounds
+struct Exploration<'a> {
+    pipe_system: &'a PipeSystem,
+    n_explorers: usize,
+    n_rounds: usize,
+}
+
+impl Exploration<'_> {
+    fn capacity(&self, label: &str) -> usize {
+        self.pipe_system.valve_paths.values().flatten().find(|vp| vp.end_valve == label).unwrap().capacity
+    }
+
+    fn closed_valves<'b>(&'b self, state: &'b ExplorationState) -> impl Iterator<Item = &'b String> {
+        self.pipe_system.valves_to_open.iter().filter(|label| !state.open_valves.contains(*label))
+    }
+}
+
+impl SearchProblem for Exploration<'_> {
+    type State = ExplorationState;
+
+    fn start(&self) -> ExplorationState {
+        let explorer = Explorer{round: 0, current_valve: String::from("AA")};
+        ExplorationState { explorers: vec![explorer; self.n_explorers], open_valves: BTreeSet::new() }
+    }
+
+    fn successors(&self, state: &ExplorationState) -> Vec<(ExplorationState, usize)> {
+        let explorer = &state.explorers[0];
+        let mut next_states = vec![];
+        for valve_path in self.pipe_system.valve_paths.get(&explorer.current_valve).unwrap() {
+            let round = explorer.round + valve_path.cost + 1;
+            if state.open_valves.contains(&valve_path.end_valve) || round >= self.n_rounds {
+                continue
+            }
+            let mut next_state = state.clone();
+            next_state.explorers[0] = Explorer { round, current_valve: valve_path.end_valve.clone() };
+            next_state.explorers.sort();
+            next_state.open_valves.insert(valve_path.end_valve.clone());
+            next_states.push((next_state, valve_path.capacity * round))
+        }
+        // Or this explorer doesn't open any more valves
+        let mut next_state = state.clone();
+        next_state.explorers[0].round = self.n_rounds;
+        next_state.explorers.sort();
+        let cost = if self.is_goal(&next_state) {
+            self.closed_valves(state).map(|label| self.capacity(label) * self.n_rounds).sum()
+        } else {
+            0
+        };
+        next_states.push((next_state, cost));
+        next_states
+    }
+
+    fn is_goal(&self, state: &ExplorationState) -> bool {
+        state.explorers[0].round == self.n_rounds
+    }
+
+    // Every closed valve is opened at the earliest when the closest explorer could get there. The
+    // valves that are left closed at the end have already been paid for by then.
+    fn heuristic(&self, state: &ExplorationState) -> usize {
+        if self.is_goal(state) {
+            return 0
+        }
+        self.closed_valves(state)
+            .map(|label| {
+                let earliest = state.explorers.iter()
+                    .filter(|explorer| explorer.round < self.n_rounds)
+                    .filter_map(|explorer| {
+                        let paths = self.pipe_system.valve_paths.get(&explorer.current_valve).unwrap();
+                        paths.iter().find(|vp| vp.end_valve == *label).map(|vp| explorer.round + vp.cost + 1)
+                    })
+                    .min()
+                    .unwrap_or(self.n_rounds)
+                    .min(self.n_rounds);
+                self.capacity(label) * earliest
+            })
+            .sum()
+    }
+}
+
+fn explore(pipe_system: &PipeSystem, n_explorers: usize, n_rounds: usize) -> Option<usize> {
+    let exploration = Exploration { pipe_system, n_explorers, n_rounds };
+    let search = a_star(&exploration);
+    info!(n_explorers, n_rounds, stats = ?search.stats, "Explored");
+    let total_capacity: usize = pipe_system.valves_to_open.iter().map(|label| exploration.capacity(label)).sum();
+    search.found.map(|found| total_capacity * n_rounds - found.cost)
+}
+
+fn parse_pipe_system(input: &str) -> Result<HashMap<String, Valve>, ParseError> {
+    // Where each tunnel leads, to check that the valves exist once all are read
+    let mut tunnel_ends = vec![];
+    let valves = parse_lines(input, |line| {
+        line.literal("Valve ")?;
+        let label = parse_label(line)?;
+        line.literal(" has flow rate=")?;
+        let capacity = line.number::<usize>()?;
+        let tunnels = [("; tunnels lead to valves ", ()), ("; tunnel leads to valve ", ())];
+        line.one_of(&tunnels, "; tunnel(s) lead(s) to valve(s)")?;
+        let mut connected_valves = vec![];
+        loop {
+            let connected_valve = parse_label(line)?;
+            tunnel_ends.push(line.error_at(&connected_valve, "a valve that has a line of its own"));
+            connected_valves.push(connected_valve);
+            if !line.skip(", ") {
+                break
+            }
+        }
+        Ok((
+            label.clone(),
+            Valve {
+                label,
+                capacity,
+                connected_valves
+            },
+        ))
+    })?;
+    let pipe_system: HashMap<String, Valve> = valves.into_iter().collect();
+    if let Some(err) = tunnel_ends.into_iter().find(|err| !pipe_system.contains_key(&err.text)) {
+        return Err(err)
+    }
+    if !pipe_system.contains_key("AA") {
+        return Err(ParseError::new(pipe_system.len() + 1, 1, "", "a line for valve AA"))
+    }
+    Ok(pipe_system)
+}
+
+fn parse_label(line: &mut LineParser) -> Result<String, ParseError> {
+    Ok(String::from(line.take_until(|c| !c.is_ascii_uppercase(), "a valve label")?))
+}
+
+#[derive(Clone, Debug)]
+struct SearchPath {
+    start_valve: String,
+    current_valve: String,
+    cost: usize
+}
+
+#[derive(Clone,Debug, Ord, PartialOrd, Eq, PartialEq)]
+struct ValvePath {
+    start_valve: String,
+    end_valve: String,
+    capacity: usize,
+    cost: usize    
+}
+
+#[derive(Debug, Clone)]
+pub struct PipeSystem {
+    valve_paths: BTreeMap<String, Vec<ValvePath>>,
+    valves_to_open: BTreeSet<String>
+}
+
+impl PipeSystem {
+    fn new(valve_paths: BTreeMap<String, Vec<ValvePath>>, valves_to_open: BTreeSet<String>) -> Self {
+        PipeSystem {
+            valve_paths,
+            valves_to_open
+        }
+    } 
+}
+
+fn pre_process(pipe_system: HashMap<String, Valve>) -> PipeSystem {
+    let valves_worth_opening: Vec<_> = pipe_system.iter().filter(|(_, v)| v.capacity > 0).map(|(_, v)| String::from(&v.label)).collect();
+    let end_valves = valves_worth_opening;
+    let mut start_valves = vec![String::from("AA")];
+    start_valves.append(&mut end_valves.clone());
+    let mut all_valve_paths: Vec<ValvePath> = vec![];
+
+    for start_valve in &start_valves {
+        let mut done_valve_paths: Vec<ValvePath> = vec![];
+        let mut search_paths = vec![SearchPath{start_valve: String::from(start_valve), current_valve: String::from(start_valve), cost: 0}];
+        let number_of_paths_to_find = if start_valve == "AA" {
+            end_valves.len()
+        } else {
+            end_valves.len() - 1
+        };
+        while done_valve_paths.len() < number_of_paths_to_find {
+            let mut new_search_paths: Vec<SearchPath> = vec![];
+            for search_path in search_paths {
+
+                let current_valve = pipe_system.get(&search_path.current_valve).unwrap();
+                for connected_valve in &current_valve.connected_valves {
+                    if !done_valve_paths.iter().any(|dvp| dvp.end_valve == *connected_valve) {
+                        if end_valves.contains(connected_valve) && start_valve != connected_valve {
+                            let capacity = pipe_system.get(connected_valve).unwrap().capacity;
+                            done_valve_paths.push(ValvePath { start_valve: search_path.start_valve.clone(), end_valve: connected_valve.clone(), capacity, cost: search_path.cost + 1 })
+                        }
+                        let mut new_search_path = search_path.clone();
+                        new_search_path.current_valve = connected_valve.clone();
+                        new_search_path.cost += 1;
+                        new_search_paths.push(new_search_path)
+                    }
+                }
+            }
+            search_paths = new_search_paths;
+        }
+        all_valve_paths.append(&mut done_valve_paths);
+    }
+    all_valve_paths.sort();
+    let mut valve_paths: BTreeMap<String, Vec<ValvePath>> = all_valve_paths.into_iter().group_by(|vp| vp.start_valve.clone()).into_iter().map(|(key, group)| (key.clone(), group.collect())).collect();
+    // Even the only valve worth opening, which has nowhere to go from there
+    for start_valve in start_valves {
+        valve_paths.entry(start_valve).or_default();
+    }
+
+    let valves_to_open = end_valves.into_iter().collect();
+    PipeSystem::new( valve_paths, valves_to_open)
+
+}
+
+/// `size` valves (at most 676, with two letter labels), all reachable from AA, about half of them
+/// with a flow rate above 0. AA itself has none, as in the puzzle.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let n_valves = size.clamp(2, 676);
+    let mut labels = vec![String::from("AA")];
+    labels.extend(generate::names(rng, n_valves - 1, 2, generate::UPPERCASE, &["AA"]));
+    let mut tunnels: Vec<Vec<usize>> = vec![vec![]; n_valves];
+    let mut connect = |first: usize, second: usize| {
+        if first != second && !tunnels[first].contains(&second) {
+            tunnels[first].push(second);
+            tunnels[second].push(first)
+        }
+    };
+    // A tree, so that all of the valves can be reached, and some more tunnels
+    for ind in 1..n_valves {
+        connect(ind, rng.gen_range(0..ind))
+    }
+    for _ in 0..n_valves / 2 {
+        connect(rng.gen_range(0..n_valves), rng.gen_range(0..n_valves))
+    }
+    let mut flow_rates: Vec<u16> = (0..n_valves).map(|_| if rng.gen_bool(0.5) { rng.gen_range(1..=25) } else { 0 }).collect();
+    flow_rates[0] = 0;
+    flow_rates[n_valves - 1] = flow_rates[n_valves - 1].max(1);
+    generate::lines(rng, n_valves, |_, ind| {
+        let connected: Vec<&str> = tunnels[ind].iter().map(|other| labels[*other].as_str()).collect();
+        let lead = if connected.len() == 1 { "tunnel leads to valve" } else { "tunnels lead to valves" };
+        format!("Valve {} has flow rate={}; {lead} {}", labels[ind], flow_rates[ind], connected.join(", "))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // The pressure released by each set of valves (as a bit each) that can be opened in turn within
+    // the rounds, the most of any order, trying every order
+    fn reference_pressures(valves: &HashMap<String, Valve>, n_rounds: usize) -> HashMap<usize, usize> {
+        let labels: Vec<&String> = valves.keys().sorted().collect();
+        let n = labels.len();
+        // The fewest steps between every two valves, shortening them through each valve in turn
+        let mut distances = vec![vec![usize::MAX / 2; n]; n];
+        for (ind, label) in labels.iter().enumerate() {
+            distances[ind][ind] = 0;
+            for other in &valves[*label].connected_valves {
+                distances[ind][labels.iter().position(|label| *label == other).unwrap()] = 1
+            }
+        }
+        for via in 0..n {
+            for from in 0..n {
+                for to in 0..n {
+                    distances[from][to] = distances[from][to].min(distances[from][via] + distances[via][to])
+                }
+            }
+        }
+        let worth_opening: Vec<usize> = (0..n).filter(|ind| valves[labels[*ind]].capacity > 0).collect();
+        let mut pressures = HashMap::new();
+        let start = labels.iter().position(|label| *label == "AA").unwrap();
+        let mut stack = vec![(start, 0, 0, 0)];
+        while let Some((at, round, opened, pressure)) = stack.pop() {
+            let best = pressures.entry(opened).or_insert(0);
+            *best = pressure.max(*best);
+            for (bit, valve) in worth_opening.iter().enumerate() {
+                let round = round + distances[at][*valve] + 1;
+                if opened & 1 << bit == 0 && round < n_rounds {
+                    stack.push((*valve, round, opened | 1 << bit, pressure + valves[labels[*valve]].capacity * (n_rounds - round)))
+                }
+            }
+        }
+        pressures
+    }
+
+    proptest! {
+        #[test]
+        fn exploration_matches_trying_every_order(seed in any::<u64>(), size in 2..12usize) {
+            let valves = parse_pipe_system(&generate(&mut generate::rng(seed), size)).unwrap();
+            let pipe_system = pre_process(valves.clone());
+            let alone = reference_pressures(&valves, 30);
+            prop_assert_eq!(explore(&pipe_system, 1, 30), alone.values().max().copied());
+            // With an elephant, each opens valves the other doesn't
+            let together = reference_pressures(&valves, 26);
+            let mut most = 0;
+            for (mine, pressure) in &together {
+                for (elephants, elephant_pressure) in &together {
+                    if mine & elephants == 0 {
+                        most = most.max(pressure + elephant_pressure)
+                    }
+                }
+            }
+            prop_assert_eq!(explore(&pipe_system, 2, 26), Some(most));
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let pipe_system = pre_process(parse_pipe_system(include_str!("../test.txt")).unwrap());
+        assert_eq!(explore(&pipe_system, 1, 30), Some(1651))
+    }
+
+    #[test]
+    fn test_part_2() {
+        let pipe_system = pre_process(parse_pipe_system(include_str!("../test.txt")).unwrap());
+        assert_eq!(explore(&pipe_system, 2, 26), Some(1707))
+    }
+
+    #[test]
+    fn test_large_flow_rates() {
+        // More pressure than fits in a u16
+        let input = "\
+Valve AA has flow rate=0; tunnel leads to valve BB
+Valve BB has flow rate=5000; tunnels lead to valves AA, CC
+Valve CC has flow rate=1; tunnel leads to valve BB
+";
+        let pipe_system = pre_process(parse_pipe_system(input).unwrap());
+        assert_eq!(explore(&pipe_system, 1, 30), Some(5000 * 28 + 26));
+        assert_eq!(explore(&pipe_system, 2, 26), Some(5000 * 24 + 23))
+    }
+
+    #[test]
+    fn test_generate_clamps_size() {
+        assert_eq!(generate(&mut generate::rng(0), 700).lines().count(), 676)
+    }
+}
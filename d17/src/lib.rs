@@ -0,0 +1,342 @@
+use aoc_common::frames::{Frame, Palette, Recorder, Visualize};
+use aoc_common::generate::{Rng, StdRng};
+use aoc_common::parse::Lines;
+use aoc_common::sparse_grid::Legend;
+use aoc_common::{Answer, Day, Grid, ParseError, Part, Solution, SparseGrid};
+use std::collections::{HashSet, HashMap};
+use tracing::debug;
+
+pub struct Day17;
+
+pub const DAY: Day = Day::new::<Day17>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR"))
+    .with_frames::<Day17>()
+    .with_generator(generate);
+
+impl Solution for Day17 {
+    type Input = Vec<isize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_gusts(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        tower_height(input, 2022, &mut Recorder::off()).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        tower_height(input, 1_000_000_000_000, &mut Recorder::off()).into()
+    }
+}
+
+// A frame for every time a rock falls one row or comes to rest, showing the top of the chamber
+impl Visualize for Day17 {
+    fn simulate(input: &Self::Input, part: Part, recorder: &mut Recorder) {
+        let n_rocks = if part == Part::One { 2022 } else { 1_000_000_000_000 };
+        tower_height(input, n_rocks, recorder);
+    }
+
+    fn palette() -> Palette {
+        Palette::new([0x0f, 0x0f, 0x23])
+            .with('#', [0x99, 0x99, 0x99])
+            .with('@', [0xff, 0x99, 0x00])
+            .with('|', [0xff, 0xff, 0xff])
+            .with('-', [0xff, 0xff, 0xff])
+            .with('+', [0xff, 0xff, 0xff])
+            .with(' ', [0x0f, 0x0f, 0x23])
+    }
+}
+// Coordinate system is row, cols starting from lower left
+// The first row is 1 and the first column is 0 to confguse things.
+
+// How far below the top of the pile the signature looks. A rock could fall further down a shaft
+// that is left open, but then the pile is not likely to repeat anyway.
+const SIGNATURE_DEPTH: isize = 100;
+
+// The air that a falling rock can still get to, moving only down and sideways from above the pile,
+// as a bit per column for each row from the top down. Everything a rock does depends only on that,
+// so the tower repeats once it does (for the same rock and gust).
+fn top_signature(rock_pile: &SparseGrid<()>, top_row: isize) -> Vec<u8> {
+    let lowest_row = (top_row - SIGNATURE_DEPTH).max(1);
+    let mut reachable = vec![0u8; (top_row - lowest_row + 2) as usize];
+    reachable[0] = 0b111_1111;
+    for depth in 1..reachable.len() {
+        let row = top_row + 1 - depth as isize;
+        let free = |col: isize| !rock_pile.contains((row, col));
+        // Straight down from the row above, then sideways as far as the rock lets it
+        let mut cols: Vec<isize> = (0..7).filter(|col| reachable[depth - 1] & 1 << col != 0 && free(*col)).collect();
+        while let Some(col) = cols.pop() {
+            reachable[depth] |= 1 << col;
+            for next in [col - 1, col + 1] {
+                if (0..7).contains(&next) && reachable[depth] & 1 << next == 0 && free(next) {
+                    cols.push(next)
+                }
+            }
+        }
+    }
+    reachable
+}
+
+fn tower_height(gusts: &[isize], n_rocks: usize, recorder: &mut Recorder) -> usize {
+    let mut jet_impact = JetImpact::new(gusts);
+    let mut rock_generator = RockGenerator::new();
+    let mut highest_bottom = 0;
+    let mut rock_pile: SparseGrid<()> = SparseGrid::new();
+    let mut top_signatures: HashMap<(Vec<u8>, usize, usize), (usize, isize)> = HashMap::new();
+    let mut total_short_circuit_height = 0;
+    let mut last_rock = n_rocks;
+    let mut loop_detected = false;
+    for rock_number in 0..n_rocks {
+        let (mut rock, rock_type) = rock_generator.next_rock(highest_bottom + 1);
+        let mut fall_next = false;
+        loop {
+            if fall_next {
+                if at_bottom(&rock, &rock_pile) {
+                    highest_bottom = rock.coordinates.iter().map(|(row, _)| *row).max().unwrap().max(highest_bottom);
+                    for pos in rock.coordinates {
+                        rock_pile.insert(pos, ());
+                    }
+                    recorder.record(|| draw(&rock_pile, None, highest_bottom));
+                    if !loop_detected {
+                        let top_signature = top_signature(&rock_pile, highest_bottom);
+                        if let Some((previous_number_of_fallen_rocks, previous_highest_bottom)) = top_signatures.get(&(top_signature.clone(), rock_type, jet_impact.jet_position())) {
+                            debug!(rock_type, previous_number_of_fallen_rocks, previous_highest_bottom, "Repeat signature detected");
+                            debug!(number_of_fallen_rocks = rock_number + 1, highest_bottom, "Current top");
+                            let delta_rocks =rock_number + 1 - previous_number_of_fallen_rocks;
+                            let delta_height = highest_bottom - previous_highest_bottom;
+                            let n_short_circuit_loops = (n_rocks - (rock_number + 1)) / delta_rocks;
+                            total_short_circuit_height = n_short_circuit_loops * delta_height as usize;
+                            last_rock = (n_rocks - (rock_number + 1)) % delta_rocks + rock_number + 1;
+                            loop_detected = true;
+                            debug!(last_rock, "Skipping ahead");
+                        } else {
+                            top_signatures.insert((top_signature, rock_type, jet_impact.jet_position()), (rock_number + 1, highest_bottom));
+                        }
+                    }
+                    break
+                } else {
+                    rock.fall();
+                    recorder.record(|| draw(&rock_pile, Some(&rock), highest_bottom));
+                    fall_next = false;
+                }
+            } else {
+                rock.adjust_column(jet_impact.next_gust(), &rock_pile);
+                fall_next = true;
+            }
+        }
+        if rock_number + 1 == last_rock {
+            break
+        }
+    }
+    recorder.finish(|| draw(&rock_pile, None, highest_bottom));
+    highest_bottom as usize + total_short_circuit_height
+}
+
+// How many rows of the chamber a frame shows
+const FRAME_HEIGHT: isize = 30;
+
+// The top of the chamber, with room above the pile for a new rock to appear, and the floor
+// once the pile is low enough for it to show. The rows are counted from the bottom, so they
+// are drawn in reverse.
+fn draw(rock_pile: &SparseGrid<()>, falling_rock: Option<&Rock>, highest_bottom: isize) -> Frame {
+    let mut picture: SparseGrid<char> = rock_pile.positions().map(|pos| (pos, '#')).collect();
+    for pos in falling_rock.iter().flat_map(|rock| &rock.coordinates) {
+        picture.insert(*pos, '@');
+    }
+    let legend = Legend::new('.', "air").with('#', '#', "rock").with('@', '@', "falling rock");
+    let top_row = highest_bottom + 7;
+    let cells = Grid::from_fn(FRAME_HEIGHT as usize, 9, |(ind, col)| {
+        let row = top_row - ind as isize;
+        match (row, col) {
+            (row, _) if row < 0 => ' ',
+            (0, 0) | (0, 8) => '+',
+            (0, _) => '-',
+            (_, 0) | (_, 8) => '|',
+            (row, col) => legend.symbol(picture.get((row, col as isize - 1))),
+        }
+    });
+    Frame::new(cells)
+}
+
+fn at_bottom(rock: &Rock, rock_pile: &SparseGrid<()>) -> bool {
+    if rock.coordinates.iter().any(|(row, _)| *row == 1) {
+        return true
+    }
+    let mut would_be_rock = rock.clone();
+    would_be_rock.fall();
+    would_be_rock.intersects(rock_pile)
+}
+
+
+fn parse_gusts(input: &str) -> Result<Vec<isize>, ParseError> {
+    let mut line = Lines::new(input).expect_line("the jet pattern")?;
+    let mut gusts = vec![line.one_of(&[(">", 1), ("<", -1)], "> or <")?];
+    while !line.is_done() {
+        gusts.push(line.one_of(&[(">", 1), ("<", -1)], "> or <")?)
+    }
+    Ok(gusts)
+}
+
+struct JetImpact {
+    pattern: Vec<isize>,
+    next_ind: usize
+}
+
+impl JetImpact {
+    fn new(gusts: &[isize]) -> Self {
+        Self{
+            pattern: gusts.to_vec(),
+            next_ind: 0
+        }
+    }
+
+    fn jet_position(&self) -> usize {
+        self.next_ind
+    }
+
+    fn next_gust(&mut self) -> isize {
+        let gust = self.pattern[self.next_ind];
+        self.next_ind += 1;
+        if self.next_ind == self.pattern.len() {
+            self.next_ind = 0;
+        }
+        gust
+    }
+
+}
+
+struct RockGenerator {
+    ind: usize
+}
+
+impl RockGenerator {
+    fn new() -> Self {
+        RockGenerator { ind: 0 }
+    }
+
+    fn next_rock(&mut self, bottom: isize) -> (Rock, usize) {
+        let (rock, rock_type) = (Rock::new(self.ind, bottom), self.ind);
+        self.ind += 1;
+        if self.ind == 5 {
+            self.ind = 0;
+        }
+        (rock, rock_type)
+    } 
+}
+
+#[derive(Clone)]
+struct Rock {
+    coordinates: HashSet<(isize, isize)>
+}
+
+impl Rock {
+    fn new(ind: usize, bottom: isize) -> Self {
+        let shape = match ind {
+            0 => vec![(0, 0), (0, 1), (0, 2), (0, 3)],
+            1 => vec![(0, 1), (1, 0), (1, 1), (1, 2), (2, 1)],
+            2 => vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)],
+            3 => vec![(0, 0), (1, 0), (2, 0), (3, 0)],
+            4 => vec![(0, 0), (0, 1), (1, 0), (1, 1)],
+            _ => panic!("Illegal rock prototype index.")
+        };
+        let position = (bottom + 3, 2);
+        let coordinates = shape.into_iter().map(|(row, col)| (row + position.0, col + position.1)).collect();
+        Rock{coordinates}
+    }
+
+    fn fall(&mut self) {
+        self.coordinates = self.coordinates.iter().map(|(row, col)| (row - 1, *col)).collect();
+    }
+
+    fn translate_col(&mut self, delta_col: isize) {
+        self.coordinates = self.coordinates.iter().map(|(row, col)| (*row, col + delta_col)).collect();
+    }
+
+    fn intersects(&self, rock_pile: &SparseGrid<()>) -> bool {
+        self.coordinates.iter().any(|pos| rock_pile.contains(*pos))
+    }
+
+    fn adjust_column(&mut self, delta_col: isize, rock_pile: &SparseGrid<()>) -> bool {
+        if self.coordinates.iter().any(|(_, col)| *col + delta_col < 0 || *col + delta_col > 6) {
+            return false;
+        }
+        let mut would_be_rock = self.clone();
+        would_be_rock.translate_col(delta_col);
+        if would_be_rock.intersects(rock_pile) {
+            return false;
+        }
+        self.coordinates = would_be_rock.coordinates;
+        true
+    }
+
+}
+
+/// A jet pattern of `size` gusts
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    (0..size.max(1)).map(|_| if rng.gen_bool(0.5) { '<' } else { '>' }).collect::<String>() + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Drops every rock, with no skipping ahead, and gives the height of the tower after each
+    fn reference_heights(gusts: &[isize], n_rocks: usize) -> Vec<usize> {
+        let shapes: [&[(isize, isize)]; 5] = [
+            &[(0, 0), (1, 0), (2, 0), (3, 0)],
+            &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+            &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+            &[(0, 0), (0, 1), (0, 2), (0, 3)],
+            &[(0, 0), (1, 0), (0, 1), (1, 1)],
+        ];
+        let mut filled: HashSet<(isize, isize)> = HashSet::new();
+        let (mut height, mut heights, mut gust) = (0, vec![], 0);
+        for rock in 0..n_rocks {
+            let (mut x, mut y) = (2, height + 3);
+            let fits = |x: isize, y: isize, filled: &HashSet<(isize, isize)>| {
+                shapes[rock % 5].iter().all(|(dx, dy)| (0..7).contains(&(x + dx)) && y + dy >= 0 && !filled.contains(&(x + dx, y + dy)))
+            };
+            loop {
+                if fits(x + gusts[gust], y, &filled) {
+                    x += gusts[gust]
+                }
+                gust = (gust + 1) % gusts.len();
+                if !fits(x, y - 1, &filled) {
+                    break
+                }
+                y -= 1
+            }
+            for (dx, dy) in shapes[rock % 5] {
+                filled.insert((x + dx, y + dy));
+                height = height.max(y + dy + 1)
+            }
+            heights.push(height as usize)
+        }
+        heights
+    }
+
+    proptest! {
+        #[test]
+        fn height_matches_dropping_every_rock(gusts in prop::collection::vec(prop::sample::select(vec![-1isize, 1]), 1..60), n_rocks in 1..3000usize) {
+            let heights = reference_heights(&gusts, n_rocks);
+            prop_assert_eq!(tower_height(&gusts, n_rocks, &mut Recorder::off()), heights[n_rocks - 1]);
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(tower_height(&parse_gusts(include_str!("../test.txt")).unwrap(), 2022, &mut Recorder::off()), 3068)
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(tower_height(&parse_gusts(include_str!("../test.txt")).unwrap(), 1_000_000_000_000, &mut Recorder::off()), 1514285714288)
+    }
+
+    #[test]
+    fn test_open_column() {
+        // Everything is pushed to the right and the left column stays open all the way down, so no
+        // row is ever filled. Each five rocks stack up 13 rows.
+        assert_eq!(tower_height(&[1], 1_000_000_000_000, &mut Recorder::off()), 2_600_000_000_000)
+    }
+}
@@ -0,0 +1,223 @@
+use aoc_common::generate::{Rng, StdRng};
+use aoc_common::parse::{parse_lines, LineParser};
+use aoc_common::{Answer, Day, ParseError, Point3, Solution, Stats};
+use std::collections::HashSet;
+
+pub struct Day18;
+
+pub const DAY: Day = Day::new::<Day18>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR"))
+    .with_stats::<Day18>()
+    .with_generator(generate);
+
+impl Solution for Day18 {
+    type Input = Vec<Point3>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_cubes(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input.clone()).into()
+    }
+}
+
+impl Stats for Day18 {
+    fn stats(input: &Self::Input) -> Vec<(&'static str, String)> {
+        let (min, max) = bounding_box(input);
+        let pockets = hollows(input.clone());
+        vec![
+            ("cubes", input.len().to_string()),
+            ("bounding box", format!("{},{},{} to {},{},{}", min.x, min.y, min.z, max.x, max.y, max.z)),
+            ("air pockets", pockets.len().to_string()),
+            ("trapped air", pockets.iter().map(Vec::len).sum::<usize>().to_string()),
+            ("surface of the pockets", pockets.iter().map(|pocket| calculate_surface_size(pocket)).sum::<usize>().to_string()),
+        ]
+    }
+}
+
+// 2,2,2
+fn parse_cube(line: &mut LineParser) -> Result<Point3, ParseError> {
+    let x = line.number::<isize>()?;
+    line.literal(",")?;
+    let y = line.number::<isize>()?;
+    line.literal(",")?;
+    let z = line.number::<isize>()?;
+    Ok(Point3::new(x, y, z))
+}
+
+// A face is identified by the two cubes on either side of it, the smaller one first
+fn faces(cube: Point3) -> HashSet<(Point3, Point3)> {
+    cube.neighbours_6().into_iter().map(|other| (cube.min(other), cube.max(other))).collect()
+}
+
+fn parse_cubes(input: &str) -> Result<Vec<Point3>, ParseError> {
+    parse_lines(input, parse_cube)
+}
+
+fn calculate_surface_size(cubes: &[Point3]) -> usize {
+    let mut outer_surface: HashSet<(Point3, Point3)> = HashSet::new();
+    for cube in cubes {
+        let cube_surfaces = faces(*cube);
+        let common_surfaces: HashSet<_> = outer_surface.intersection(&cube_surfaces).copied().collect();
+        outer_surface = outer_surface.union(&cube_surfaces).copied().collect();
+        outer_surface = outer_surface.difference(&common_surfaces).copied().collect();
+    }
+    outer_surface.len()
+
+}
+
+fn part_1(cubes: &[Point3]) -> usize {
+    calculate_surface_size(cubes)
+}
+
+
+fn part_2(cubes: Vec<Point3>) -> usize {
+    calculate_surface_size(&cubes) - hollow_surface_size(cubes)
+}
+
+fn hollow_surface_size(cubes: Vec<Point3>) -> usize {
+    hollows(cubes).iter().map(|hollow| calculate_surface_size(hollow)).sum()
+}
+
+// The smallest and largest corner of the box that the cubes fit in
+fn bounding_box(cubes: &[Point3]) -> (Point3, Point3) {
+    let (xmin, xmax, ymin, ymax, zmin, zmax) = cubes.iter().fold(
+        (isize::MAX, isize::MIN, isize::MAX, isize::MIN, isize::MAX, isize::MIN), |(xmin, xmax, ymin, ymax, zmin, zmax), cube|
+            (xmin.min(cube.x), xmax.max(cube.x), ymin.min(cube.y), ymax.max(cube.y), zmin.min(cube.z), zmax.max(cube.z)));
+    (Point3::new(xmin, ymin, zmin), Point3::new(xmax, ymax, zmax))
+}
+
+// The bodies of air that the cubes enclose
+fn hollows(cubes: Vec<Point3>) -> Vec<Vec<Point3>> {
+    let (min, max) = bounding_box(&cubes);
+    let (xmin, xmax, ymin, ymax, zmin, zmax) = (min.x, max.x, min.y, max.y, min.z, max.z);
+    let mut extent_cubes: HashSet<Point3> = HashSet::new();
+    for x in (xmin-1)..=(xmax+1) {
+        for y in (ymin-1)..=(ymax+1) {
+            for z in (zmin-1)..=(zmax+1) {
+                extent_cubes.insert(Point3::new(x, y, z));
+            }
+        }
+    }
+    let cube_set: HashSet<Point3> = cubes.into_iter().collect();
+    let empty_space_cubes: Vec<Point3> = extent_cubes.difference(&cube_set).copied().collect();
+    let distinct_empty_spaces = find_distinct_bodies(&empty_space_cubes);
+
+    // Filter out the surrounding space
+    distinct_empty_spaces.into_iter().filter(|space| !space.contains(&Point3::new(xmin - 1, ymin - 1, zmin - 1))).collect()
+}
+
+fn find_distinct_bodies(cubes: &[Point3]) -> Vec<Vec<Point3>> {
+    let mut distinct_bodies: Vec<Vec<Point3>> = vec![];
+    for cube in cubes {
+        let (mergable, non_mergable): (Vec<_>, Vec<_>) = distinct_bodies.into_iter().partition(|body| body.iter().any(|body_cube| body_cube.manhattan(*cube) == 1));
+        let mut merged: Vec<_> = mergable.into_iter().flatten().collect();
+        distinct_bodies = non_mergable;
+        merged.push(*cube);
+        distinct_bodies.push(merged)
+    }
+    distinct_bodies
+}
+
+/// `size` different cubes, filling about half of the smallest box that there is room for them in
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let edge = (1..).find(|edge| edge * edge * edge >= 2 * size).unwrap().max(3);
+    let mut cubes = HashSet::new();
+    let mut input = String::new();
+    while cubes.len() < size.max(1) {
+        let cube = (rng.gen_range(0..edge), rng.gen_range(0..edge), rng.gen_range(0..edge));
+        if cubes.insert(cube) {
+            input += &format!("{},{},{}\n", cube.0, cube.1, cube.2)
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_part_2_simple() {
+        // The air around a single cube is one body, and it has no hollow
+        let cube = Point3::new(1, 1, 1);
+        let mut outer_cubes = vec![];
+        for x in 0..=2 {
+            for y in 0..=2 {
+                for z in 0..=2 {
+                    outer_cubes.push(Point3::new(x, y, z));
+                }
+            }
+        }
+        outer_cubes.retain(|outer| *outer != cube);
+        assert_eq!(find_distinct_bodies(&outer_cubes).len(), 1);
+        assert_eq!(part_2(vec![cube]), 6)
+    }
+
+    fn neighbours((x, y, z): (isize, isize, isize)) -> [(isize, isize, isize); 6] {
+        [(x - 1, y, z), (x + 1, y, z), (x, y - 1, z), (x, y + 1, z), (x, y, z - 1), (x, y, z + 1)]
+    }
+
+    // Faces next to a cell that isn't a cube
+    fn reference_surface(cubes: &HashSet<(isize, isize, isize)>) -> usize {
+        cubes.iter().flat_map(|cube| neighbours(*cube)).filter(|cell| !cubes.contains(cell)).count()
+    }
+
+    // Faces reached by flooding the air from outside the cubes
+    fn reference_outer_surface(cubes: &HashSet<(isize, isize, isize)>) -> usize {
+        let (min, max) = (-1, 8);
+        let mut seen = HashSet::from([(min, min, min)]);
+        let mut queue = vec![(min, min, min)];
+        let mut n_faces = 0;
+        while let Some(cell) = queue.pop() {
+            for next in neighbours(cell) {
+                if [next.0, next.1, next.2].iter().any(|c| !(min..=max).contains(c)) {
+                    continue;
+                }
+                if cubes.contains(&next) {
+                    n_faces += 1
+                } else if seen.insert(next) {
+                    queue.push(next)
+                }
+            }
+        }
+        n_faces
+    }
+
+    proptest! {
+        #[test]
+        fn surfaces_match_reference(cubes in prop::collection::hash_set((0..7isize, 0..7isize, 0..7isize), 1..120)) {
+            let input: String = cubes.iter().map(|(x, y, z)| format!("{x},{y},{z}\n")).collect();
+            let parsed = parse_cubes(&input).unwrap();
+            prop_assert_eq!(part_1(&parsed), reference_surface(&cubes));
+            prop_assert_eq!(part_2(parsed), reference_outer_surface(&cubes));
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let cubes = parse_cubes(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_1(&cubes), 64)
+    }
+
+    #[test]
+    fn test_part_2() {
+        let cubes = parse_cubes(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_2(cubes), 58)
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Day18::stats(&parse_cubes(include_str!("../test.txt")).unwrap());
+        assert_eq!(stats[0], ("cubes", String::from("13")));
+        assert_eq!(stats[1], ("bounding box", String::from("1,1,1 to 3,3,6")));
+        assert_eq!(stats[2], ("air pockets", String::from("1")));
+        assert_eq!(stats[3], ("trapped air", String::from("1")));
+        assert_eq!(stats[4], ("surface of the pockets", String::from("6")))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,314 @@
+use aoc_common::generate::{self, Rng, StdRng};
+use aoc_common::parse::parse_lines;
+use aoc_common::{Answer, Day, ParseError, Solution};
+use aoc_common::search::{branch_and_bound, SearchProblem};
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use tracing::{info, info_span};
+
+pub struct Day19;
+
+pub const DAY: Day = Day::new::<Day19>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day19 {
+    type Input = Vec<Blueprint>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_blueprints(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    // No part 2: with 32 minutes the search takes minutes even for the example, and then finds
+    // 3348 geodes multiplied instead of 56 * 62
+}
+
+// The blueprints are independent of each other, so they are searched in parallel
+fn part_1(blueprints: &[Blueprint]) -> usize {
+    blueprints.par_iter().map(|bp| max_geodes_for_blueprint(bp, 24) * bp.id).sum::<usize>()
+}
+
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Ord, PartialOrd)]
+enum Resource {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+#[derive(Debug, Clone)]
+struct RobotType {
+    produces: Resource,
+    cost: BTreeMap<Resource, usize>,
+}
+
+impl RobotType {
+    fn can_build(&self, resources: &BTreeMap<Resource, usize>) -> bool {
+        self.cost.iter().all(|(resource, required)| 
+        if let Some(available) = resources.get(resource) {
+            available >= required
+        } else {
+            false
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Blueprint {
+    id: usize,
+    robot_types: Vec<RobotType>,
+}
+
+fn determine_buildable_robot_types(blueprint: &Blueprint, resources: &BTreeMap<Resource, usize>) -> Vec<RobotType> {
+    let geode_robot = blueprint.robot_types.iter().find(|rt| rt.produces == Resource::Geode).unwrap();
+    let obsidian_robot = blueprint.robot_types.iter().find(|rt| rt.produces == Resource::Obsidian).unwrap();
+    if geode_robot.can_build(resources) {
+        vec![geode_robot.clone()]
+    } else if obsidian_robot.can_build(resources) {
+        vec![obsidian_robot.clone()]
+    } else {
+        blueprint.robot_types.iter().filter(|rt| rt.can_build(resources)).cloned().collect()
+    }
+}
+
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+struct SearchState {
+    round: usize,
+    current_production: BTreeMap<Resource, usize>,
+    resources: BTreeMap<Resource, usize>
+}
+
+impl SearchState {
+
+    fn max_possible_geode(&self, rounds: usize) -> usize {
+        // Assume one robot can be produced in each round
+        let n_geode_robots = *self.current_production.get(&Resource::Geode).unwrap();
+        let (geode_produced, _n_geode_robots) = (0..rounds).fold((0, n_geode_robots), |(acc_geodes, acc_robots), _ind| (acc_geodes + acc_robots, acc_robots + 1));
+        geode_produced
+    }
+
+    fn new() -> Self {
+        Self {
+            round: 0,
+            current_production: [(Resource::Ore, 1), (Resource::Clay, 0), (Resource::Obsidian, 0), (Resource::Geode, 0), ].into_iter().collect(),
+            resources: [(Resource::Ore, 0), (Resource::Clay, 0), (Resource::Obsidian, 0), (Resource::Geode, 0)].into_iter().collect()
+        }
+    }
+
+    fn produce(mut self) -> Self {
+        self.round += 1;
+        self.resources = self.resources.into_iter()
+            .map(|(resource, current_units)| 
+                if let Some(new_units) = self.current_production.get(&resource) {
+                    (resource, current_units + new_units)
+                } else {
+                    (resource, current_units)
+                }
+            ).collect();
+        self
+    }
+
+    fn build_robot(mut self, robot: &RobotType) -> Self {
+        self.resources = self.resources.into_iter()
+            .map(|(resource, current_units)|  
+                if let Some(cost) = robot.cost.get(&resource) {
+                    (resource, current_units - cost)
+                } else {
+                    (resource, current_units)
+                }
+            ).collect();
+        self.current_production = self.current_production.into_iter()
+            .map(|(resource, n_units)|
+                if resource == robot.produces {
+                    (resource, n_units + 1)
+                } else {
+                    (resource, n_units)
+                }
+            ).collect();
+        self
+    }
+}
+
+// The text around the numbers in a blueprint line
+const BLUEPRINT_TEXT: [&str; 8] = [
+    "Blueprint ",
+    ": Each ore robot costs ",
+    " ore. Each clay robot costs ",
+    " ore. Each obsidian robot costs ",
+    " ore and ",
+    " clay. Each geode robot costs ",
+    " ore and ",
+    " obsidian.",
+];
+
+fn parse_blueprints(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+    parse_lines(input, |line| {
+        let mut numbers: [usize; 7] = [0; 7];
+        for (ind, text) in BLUEPRINT_TEXT.iter().enumerate() {
+            line.literal(text)?;
+            if ind < numbers.len() {
+                numbers[ind] = line.number()?;
+            }
+        }
+        Ok(Blueprint {
+            id: numbers[0],
+            robot_types: vec![
+                RobotType {
+                    produces: Resource::Geode,
+                    cost: BTreeMap::from([
+                        (Resource::Ore, numbers[5]),
+                        (Resource::Obsidian, numbers[6]),
+                    ]),
+                },
+                RobotType {
+                    produces: Resource::Obsidian,
+                    cost: BTreeMap::from([
+                        (Resource::Ore, numbers[3]),
+                        (Resource::Clay, numbers[4]),
+                    ]),
+                },
+                RobotType {
+                    produces: Resource::Clay,
+                    cost: BTreeMap::from([(Resource::Ore, numbers[2])]),
+                },
+                RobotType {
+                    produces: Resource::Ore,
+                    cost: BTreeMap::from([(Resource::Ore, numbers[1])]),
+                },
+            ],
+        })
+    })
+}
+
+
+// The most geodes is the least cost when every round costs the number of rounds less the
+// geodes produced in it
+struct Geodes<'a> {
+    blueprint: &'a Blueprint,
+    n_rounds: usize,
+}
+
+impl SearchProblem for Geodes<'_> {
+    type State = SearchState;
+
+    fn start(&self) -> SearchState {
+        SearchState::new()
+    }
+
+    fn successors(&self, state: &SearchState) -> Vec<(SearchState, usize)> {
+        let buildable_robots = determine_buildable_robot_types(self.blueprint, &state.resources);
+        let cost = self.n_rounds - state.current_production.get(&Resource::Geode).unwrap();
+        let produced_state = state.clone().produce();
+        let mut next_states: Vec<_> = buildable_robots
+            .iter()
+            .map(|robot| (produced_state.clone().build_robot(robot), cost))
+            .collect();
+        next_states.push((produced_state, cost));
+        next_states
+    }
+
+    fn is_goal(&self, state: &SearchState) -> bool {
+        state.round == self.n_rounds
+    }
+
+    fn heuristic(&self, state: &SearchState) -> usize {
+        let remaining_rounds = self.n_rounds - state.round;
+        (self.n_rounds * remaining_rounds).saturating_sub(state.max_possible_geode(remaining_rounds))
+    }
+}
+
+fn max_geodes_for_blueprint(blueprint: &Blueprint, n_rounds: usize) -> usize {
+    let _span = info_span!("blueprint", id = blueprint.id, n_rounds).entered();
+    let search = branch_and_bound(&Geodes { blueprint, n_rounds });
+    let geodes = n_rounds * n_rounds - search.found.unwrap().cost;
+    info!(geodes, stats = ?search.stats, "Searched");
+    geodes
+}
+
+/// `size` blueprints, with costs in the same ranges as in the puzzle
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    generate::lines(rng, size.max(1), |rng, ind| {
+        let numbers = [
+            ind + 1,
+            rng.gen_range(2..=4),
+            rng.gen_range(2..=4),
+            rng.gen_range(2..=4),
+            rng.gen_range(5..=20),
+            rng.gen_range(2..=4),
+            rng.gen_range(5..=20),
+        ];
+        // The last text has no number after it
+        let numbers = numbers.iter().map(usize::to_string).chain([String::new()]);
+        BLUEPRINT_TEXT.iter().zip(numbers).map(|(text, number)| format!("{text}{number}")).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // The costs of the robots for ore, clay, obsidian and geodes, in the order of the blueprint text
+    fn costs() -> impl Strategy<Value = [usize; 6]> {
+        (2..=4usize, 2..=4usize, 2..=4usize, 5..=20usize, 2..=4usize, 5..=20usize).prop_map(|(a, b, c, d, e, f)| [a, b, c, d, e, f])
+    }
+
+    fn blueprint_text(costs: &[usize; 6]) -> String {
+        let numbers = [1].iter().chain(costs).map(usize::to_string).chain([String::new()]);
+        BLUEPRINT_TEXT.iter().zip(numbers).map(|(text, number)| format!("{text}{number}")).collect()
+    }
+
+    // Tries every order of building robots, waiting as long as it takes for each. No more robots of
+    // a kind are worth building than a round can spend of what it collects.
+    fn reference_geodes(costs: &[usize; 6], rounds_left: usize, robots: [usize; 4], resources: [usize; 4]) -> usize {
+        let robot_costs = [[costs[0], 0, 0], [costs[1], 0, 0], [costs[2], costs[3], 0], [costs[4], 0, costs[5]]];
+        let most_spent = [costs[0].max(costs[1]).max(costs[2]).max(costs[4]), costs[3], costs[5], usize::MAX];
+        let mut most = resources[3] + robots[3] * rounds_left;
+        for (robot, cost) in robot_costs.iter().enumerate() {
+            if robots[robot] >= most_spent[robot] {
+                continue
+            }
+            let wait = (0..3).map(|resource| {
+                match (cost[resource].saturating_sub(resources[resource]), robots[resource]) {
+                    (0, _) => Some(0),
+                    (_, 0) => None,
+                    (missing, n_robots) => Some(missing.div_ceil(n_robots)),
+                }
+            });
+            let Some(wait) = wait.collect::<Option<Vec<usize>>>().map(|waits| waits.into_iter().max().unwrap()) else { continue };
+            if wait + 1 >= rounds_left {
+                continue
+            }
+            let mut next_resources = resources;
+            for resource in 0..4 {
+                next_resources[resource] += robots[resource] * (wait + 1);
+            }
+            for resource in 0..3 {
+                next_resources[resource] -= cost[resource];
+            }
+            let mut next_robots = robots;
+            next_robots[robot] += 1;
+            most = most.max(reference_geodes(costs, rounds_left - wait - 1, next_robots, next_resources))
+        }
+        most
+    }
+
+    proptest! {
+        // The search takes a while for more rounds
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn geodes_match_trying_every_order(costs in costs(), n_rounds in 1..=18usize) {
+            let blueprints = parse_blueprints(&blueprint_text(&costs)).unwrap();
+            prop_assert_eq!(max_geodes_for_blueprint(&blueprints[0], n_rounds), reference_geodes(&costs, n_rounds, [1, 0, 0, 0], [0; 4]));
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let blueprints = parse_blueprints(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_1(&blueprints), 33)
+    }
+}
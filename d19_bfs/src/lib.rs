@@ -0,0 +1,481 @@
+use aoc_common::generate::{self, Rng, StdRng};
+use aoc_common::parse::parse_lines;
+use aoc_common::{Answer, Day, ParseError, Solution};
+use aoc_common::search::{bfs, SearchProblem};
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use tracing::{debug, info, info_span, trace};
+
+pub struct Day19Bfs;
+
+pub const DAY: Day = Day::new::<Day19Bfs>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day19Bfs {
+    type Input = Vec<Blueprint>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_blueprints(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    // No part 2: with 32 minutes the pruning doesn't keep the number of states down
+}
+
+// The blueprints are independent of each other, so they are searched in parallel
+fn part_1(blueprints: &[Blueprint]) -> u16 {
+    blueprints.par_iter().map(|bp| max_geodes_for_blueprint(bp, 24) * bp.id).sum::<u16>()
+}
+
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Ord, PartialOrd)]
+enum Resource {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+#[derive(Debug, Clone)]
+struct RobotType {
+    produces: Resource,
+    cost: BTreeMap<Resource, u16>,
+}
+
+impl RobotType {
+    fn can_build(&self, resources: &BTreeMap<Resource, u16>) -> bool {
+        self.cost.iter().all(|(resource, required)| 
+        if let Some(available) = resources.get(resource) {
+            available >= required
+        } else {
+            false
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Blueprint {
+    id: u16,
+    robot_types: Vec<RobotType>,
+}
+
+fn determine_buildable_robot_types(blueprint: &Blueprint, resources: &BTreeMap<Resource, u16>) -> Vec<RobotType> {
+    blueprint.robot_types.iter().filter(|rt| rt.can_build(resources)).cloned().collect()
+}
+
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+struct SearchState {
+    round: u16,
+    current_production: BTreeMap<Resource, u16>,
+    resources: BTreeMap<Resource, u16>
+}
+
+impl SearchState {
+
+    fn robot_key(&self) -> (u16, u16, u16, u16) {
+        (
+            *self.current_production.get(&Resource::Ore).unwrap(),
+            *self.current_production.get(&Resource::Clay).unwrap(),
+            *self.current_production.get(&Resource::Obsidian).unwrap(),
+            *self.current_production.get(&Resource::Geode).unwrap()
+        )
+    }
+
+    fn has_better_robots_than(&self, other: &SearchState) -> bool {
+        if self.current_production.get(&Resource::Geode).unwrap() > other.current_production.get(&Resource::Geode).unwrap() ||
+            self.current_production.get(&Resource::Geode).unwrap() == other.current_production.get(&Resource::Geode).unwrap() &&
+            self.current_production.get(&Resource::Obsidian).unwrap() > other.current_production.get(&Resource::Obsidian).unwrap() {
+            return true
+        }
+
+        let better = self.current_production.get(&Resource::Ore).unwrap() > other.current_production.get(&Resource::Ore).unwrap() &&
+        self.current_production.get(&Resource::Clay).unwrap() > other.current_production.get(&Resource::Clay).unwrap() &&
+        self.current_production.get(&Resource::Obsidian).unwrap() > other.current_production.get(&Resource::Obsidian).unwrap() &&
+        self.current_production.get(&Resource::Geode).unwrap() > other.current_production.get(&Resource::Geode).unwrap();
+        better
+    }
+
+
+    // fn has_better_robots_than(&self, other: &SearchState) -> bool {
+    //     let better = self.current_production.get(&Resource::Ore).unwrap() > other.current_production.get(&Resource::Ore).unwrap() &&
+    //     self.current_production.get(&Resource::Clay).unwrap() > other.current_production.get(&Resource::Clay).unwrap() &&
+    //     self.current_production.get(&Resource::Obsidian).unwrap() > other.current_production.get(&Resource::Obsidian).unwrap() &&
+    //     self.current_production.get(&Resource::Geode).unwrap() > other.current_production.get(&Resource::Geode).unwrap();
+    //     better
+    // }
+
+    #[allow(dead_code)]
+    fn has_any_better_robot_than(&self, other: &SearchState) -> bool {
+        let better = self.current_production.get(&Resource::Ore).unwrap() > other.current_production.get(&Resource::Ore).unwrap() ||
+        self.current_production.get(&Resource::Clay).unwrap() > other.current_production.get(&Resource::Clay).unwrap() ||
+        self.current_production.get(&Resource::Obsidian).unwrap() > other.current_production.get(&Resource::Obsidian).unwrap() ||
+        self.current_production.get(&Resource::Geode).unwrap() > other.current_production.get(&Resource::Geode).unwrap();
+        better
+    }
+
+
+    fn has_better_robots_than_all(&self, other_states: &[SearchState]) -> bool {
+        other_states.iter().all(|other_state| self.has_better_robots_than(other_state))
+    }
+
+    fn has_worse_robots_than_all(&self, other_states: &[SearchState]) -> bool {
+        other_states.iter().all(|other_state| other_state.has_better_robots_than(self))
+    }
+
+    fn resource_key(&self) -> (u16, u16, u16, u16) {
+        (
+            *self.resources.get(&Resource::Ore).unwrap(),
+            *self.resources.get(&Resource::Clay).unwrap(),
+            *self.resources.get(&Resource::Obsidian).unwrap(),
+            *self.resources.get(&Resource::Geode).unwrap()
+        )
+    }
+
+    fn has_better_resources_than(&self, other: &SearchState) -> bool {
+        let better = self.resources.get(&Resource::Ore).unwrap() >= other.resources.get(&Resource::Ore).unwrap() &&
+        self.resources.get(&Resource::Clay).unwrap() >= other.resources.get(&Resource::Clay).unwrap() &&
+        self.resources.get(&Resource::Obsidian).unwrap() >= other.resources.get(&Resource::Obsidian).unwrap() &&
+        self.resources.get(&Resource::Geode).unwrap() >= other.resources.get(&Resource::Geode).unwrap();
+        better
+    }
+
+    #[allow(dead_code)]
+    fn has_any_better_resource_than(&self, other: &SearchState) -> bool {
+        let better = self.resources.get(&Resource::Ore).unwrap() >= other.resources.get(&Resource::Ore).unwrap() ||
+        self.resources.get(&Resource::Clay).unwrap() >= other.resources.get(&Resource::Clay).unwrap() ||
+        self.resources.get(&Resource::Obsidian).unwrap() >= other.resources.get(&Resource::Obsidian).unwrap() ||
+        self.resources.get(&Resource::Geode).unwrap() >= other.resources.get(&Resource::Geode).unwrap();
+        better
+    }
+
+
+    fn has_better_resources_than_all(&self, other_states: &[SearchState]) -> bool {
+        other_states.iter().all(|other_state| self.has_better_resources_than(other_state))
+    }
+
+    fn has_worse_resources_than_all(&self, other_states: &[SearchState]) -> bool {
+        other_states.iter().all(|other_state| other_state.has_better_resources_than(self))
+    }
+
+    fn new() -> Self {
+        Self {
+            round: 0,
+            current_production: [(Resource::Ore, 1), (Resource::Clay, 0), (Resource::Obsidian, 0), (Resource::Geode, 0), ].into_iter().collect(),
+            resources: [(Resource::Ore, 0), (Resource::Clay, 0), (Resource::Obsidian, 0), (Resource::Geode, 0)].into_iter().collect()
+        }
+    }
+
+    fn produce(mut self) -> Self {
+        self.round += 1;
+        self.resources = self.resources.into_iter()
+            .map(|(resource, current_units)| 
+                if let Some(new_units) = self.current_production.get(&resource) {
+                    (resource, current_units + new_units)
+                } else {
+                    (resource, current_units)
+                }
+            ).collect();
+        self
+    }
+
+    fn build_robot(mut self, robot: &RobotType) -> Self {
+        self.resources = self.resources.into_iter()
+            .map(|(resource, current_units)|  
+                if let Some(cost) = robot.cost.get(&resource) {
+                    (resource, current_units - cost)
+                } else {
+                    (resource, current_units)
+                }
+            ).collect();
+        self.current_production = self.current_production.into_iter()
+            .map(|(resource, n_units)|
+                if resource == robot.produces {
+                    (resource, n_units + 1)
+                } else {
+                    (resource, n_units)
+                }
+            ).collect();
+        self
+    }
+}
+
+fn drop_worse_robots(search_states: Vec<SearchState>) -> Vec<SearchState> {
+    let mut kept_states: BTreeMap<(u16, u16, u16, u16), Vec<SearchState>> = BTreeMap::new();
+    for state in search_states {
+        let key = state.resource_key();
+        if let Some(other_states) = kept_states.get_mut(&key) {
+            if state.has_better_robots_than_all(other_states) {
+                trace!(?other_states, in_favour_of = ?state, "Dropping states with worse robots");
+                kept_states.insert(key, vec![state]);
+            } else if state.has_worse_robots_than_all(other_states) {
+                trace!(?state, in_favour_of = ?other_states, "Dropping state with worse robots");
+            } else {
+                trace!(?state, ?other_states, "Keeping state");
+                other_states.push(state);
+            }
+        } else {
+            kept_states.insert(key, vec![state]);
+        }
+    }
+    kept_states.into_values().flat_map(|states| states.into_iter()).collect()
+}
+
+fn drop_worse_resources(search_states: Vec<SearchState>) -> Vec<SearchState> {
+    let mut kept_states: BTreeMap<(u16, u16, u16, u16), Vec<SearchState>> = BTreeMap::new();
+    for state in search_states {
+        let key = state.robot_key();
+        if let Some(other_states) = kept_states.get_mut(&key) {
+            if state.has_better_resources_than_all(other_states) {
+                trace!(?other_states, in_favour_of = ?state, "Dropping states with worse resources");
+                kept_states.insert(key, vec![state]);
+            } else if state.has_worse_resources_than_all(other_states) {
+                trace!(?state, in_favour_of = ?other_states, "Dropping state with worse resources");
+            } else {
+                trace!(?state, ?other_states, "Keeping state");
+                other_states.push(state);
+            }
+        } else {
+            kept_states.insert(key, vec![state]);
+        }
+    }
+    kept_states.into_values().flat_map(|states| states.into_iter()).collect()
+}
+
+#[allow(dead_code)]
+fn drop_worse(search_states: Vec<SearchState>) -> Vec<SearchState> {
+    let mut kept_states: Vec<SearchState> = vec![];
+    for state in search_states {
+        if kept_states.is_empty() {
+            kept_states.push(state)
+        } else {
+            // Först filtrerar vi genom att bara behålla sådana som antingen har bättre resurser eller bättre robotar än den nya.
+            kept_states.retain(|kept| kept.has_any_better_resource_than(&state) || kept.has_any_better_robot_than(&state));
+            // Sedan lägger vi till den nya endast om det har bättre state eller robotar än någon befintlig
+            if kept_states.iter().any(|kept| state.has_any_better_resource_than(kept) || state.has_any_better_robot_than(kept)) {
+                kept_states.push(state)
+            } else {
+                trace!(?state, in_favour_of = ?kept_states, "Dropping state");
+            }
+        }
+    }
+    kept_states
+}
+
+/*
+    Eller ska vi helt enkelt uppdatera has_better_robots 
+    till att prioritera Geode robots, dvs fler Geode robotar är alltid bättre.
+
+
+    Eller ska vi filtrera på vad som totalt har producerats?
+    Vid lika robotar och lika resurser så är det samma värde.
+
+    Eller ska vi räkna Ore värdet som vad vi har i Ore + hur mycket Ore vi pröjsat för robotar
+    - Samma för Clay och Obsidian
+
+
+*/
+
+
+fn keep_best(mut search_states: Vec<SearchState>) -> Vec<SearchState> {
+    search_states.sort();
+    let before = search_states.len();
+    search_states.dedup();
+
+
+    search_states = drop_worse_robots(search_states);
+    search_states = drop_worse_resources(search_states);
+    // search_states = drop_worse(search_states);
+    let after = search_states.len();
+    debug!(pruned = before - after, remaining = after, "Pruned layer");
+    search_states
+}
+
+// The most geodes is the least cost when every round costs the number of rounds less the
+// geodes produced in it
+struct Geodes<'a> {
+    blueprint: &'a Blueprint,
+    n_rounds: u16,
+}
+
+impl SearchProblem for Geodes<'_> {
+    type State = SearchState;
+
+    fn start(&self) -> SearchState {
+        SearchState::new()
+    }
+
+    fn successors(&self, search_state: &SearchState) -> Vec<(SearchState, usize)> {
+        let buildable_robots = determine_buildable_robot_types(self.blueprint, &search_state.resources);
+        let cost = (self.n_rounds - search_state.current_production.get(&Resource::Geode).unwrap()) as usize;
+        let produced_state = search_state.clone().produce();
+        let mut new_search_states = vec![];
+        for robot in buildable_robots {
+            // Only one robot is built a round, so there is no use in producing more of a resource
+            // than any robot costs
+            let max_cost = self.blueprint.robot_types.iter().filter_map(|rt| rt.cost.get(&robot.produces)).max();
+            if max_cost.is_some_and(|max_cost| search_state.current_production.get(&robot.produces).unwrap() >= max_cost) {
+                continue
+            }
+            let new_state = produced_state.clone().build_robot(&robot);
+            new_search_states.push((new_state, cost));
+        }
+        new_search_states.push((produced_state, cost));
+        new_search_states
+    }
+
+    fn is_goal(&self, search_state: &SearchState) -> bool {
+        search_state.round == self.n_rounds
+    }
+
+    fn prune(&self, search_states: Vec<SearchState>) -> Vec<SearchState> {
+        keep_best(search_states)
+    }
+}
+
+fn max_geodes_for_blueprint(blueprint: &Blueprint, n_rounds: u16) -> u16 {
+    let _span = info_span!("blueprint", id = blueprint.id, n_rounds).entered();
+    let search = bfs(&Geodes { blueprint, n_rounds });
+    let geodes = n_rounds * n_rounds - search.found.unwrap().cost as u16;
+    info!(geodes, stats = ?search.stats, "Searched");
+    geodes
+}
+
+
+// The text around the numbers in a blueprint line
+const BLUEPRINT_TEXT: [&str; 8] = [
+    "Blueprint ",
+    ": Each ore robot costs ",
+    " ore. Each clay robot costs ",
+    " ore. Each obsidian robot costs ",
+    " ore and ",
+    " clay. Each geode robot costs ",
+    " ore and ",
+    " obsidian.",
+];
+
+fn parse_blueprints(input: &str) -> Result<Vec<Blueprint>, ParseError> {
+    parse_lines(input, |line| {
+        let mut numbers: [u16; 7] = [0; 7];
+        for (ind, text) in BLUEPRINT_TEXT.iter().enumerate() {
+            line.literal(text)?;
+            if ind < numbers.len() {
+                numbers[ind] = line.number()?;
+            }
+        }
+        Ok(Blueprint {
+            id: numbers[0],
+            robot_types: vec![
+                RobotType {
+                    produces: Resource::Ore,
+                    cost: BTreeMap::from([(Resource::Ore, numbers[1])]),
+                },
+                RobotType {
+                    produces: Resource::Clay,
+                    cost: BTreeMap::from([(Resource::Ore, numbers[2])]),
+                },
+                RobotType {
+                    produces: Resource::Obsidian,
+                    cost: BTreeMap::from([
+                        (Resource::Ore, numbers[3]),
+                        (Resource::Clay, numbers[4]),
+                    ]),
+                },
+                RobotType {
+                    produces: Resource::Geode,
+                    cost: BTreeMap::from([
+                        (Resource::Ore, numbers[5]),
+                        (Resource::Obsidian, numbers[6]),
+                    ]),
+                },
+            ],
+        })
+    })
+}
+
+/// `size` blueprints, with costs in the same ranges as in the puzzle
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    generate::lines(rng, size.max(1), |rng, ind| {
+        let numbers = [
+            ind + 1,
+            rng.gen_range(2..=4),
+            rng.gen_range(2..=4),
+            rng.gen_range(2..=4),
+            rng.gen_range(5..=20),
+            rng.gen_range(2..=4),
+            rng.gen_range(5..=20),
+        ];
+        // The last text has no number after it
+        let numbers = numbers.iter().map(usize::to_string).chain([String::new()]);
+        BLUEPRINT_TEXT.iter().zip(numbers).map(|(text, number)| format!("{text}{number}")).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // The costs of the robots for ore, clay, obsidian and geodes, in the order of the blueprint text
+    fn costs() -> impl Strategy<Value = [usize; 6]> {
+        (2..=4usize, 2..=4usize, 2..=4usize, 5..=20usize, 2..=4usize, 5..=20usize).prop_map(|(a, b, c, d, e, f)| [a, b, c, d, e, f])
+    }
+
+    fn blueprint_text(costs: &[usize; 6]) -> String {
+        let numbers = [1].iter().chain(costs).map(usize::to_string).chain([String::new()]);
+        BLUEPRINT_TEXT.iter().zip(numbers).map(|(text, number)| format!("{text}{number}")).collect()
+    }
+
+    // Tries every order of building robots, waiting as long as it takes for each. No more robots of
+    // a kind are worth building than a round can spend of what it collects.
+    fn reference_geodes(costs: &[usize; 6], rounds_left: usize, robots: [usize; 4], resources: [usize; 4]) -> usize {
+        let robot_costs = [[costs[0], 0, 0], [costs[1], 0, 0], [costs[2], costs[3], 0], [costs[4], 0, costs[5]]];
+        let most_spent = [costs[0].max(costs[1]).max(costs[2]).max(costs[4]), costs[3], costs[5], usize::MAX];
+        let mut most = resources[3] + robots[3] * rounds_left;
+        for (robot, cost) in robot_costs.iter().enumerate() {
+            if robots[robot] >= most_spent[robot] {
+                continue
+            }
+            let wait = (0..3).map(|resource| {
+                match (cost[resource].saturating_sub(resources[resource]), robots[resource]) {
+                    (0, _) => Some(0),
+                    (_, 0) => None,
+                    (missing, n_robots) => Some(missing.div_ceil(n_robots)),
+                }
+            });
+            let Some(wait) = wait.collect::<Option<Vec<usize>>>().map(|waits| waits.into_iter().max().unwrap()) else { continue };
+            if wait + 1 >= rounds_left {
+                continue
+            }
+            let mut next_resources = resources;
+            for resource in 0..4 {
+                next_resources[resource] += robots[resource] * (wait + 1);
+            }
+            for resource in 0..3 {
+                next_resources[resource] -= cost[resource];
+            }
+            let mut next_robots = robots;
+            next_robots[robot] += 1;
+            most = most.max(reference_geodes(costs, rounds_left - wait - 1, next_robots, next_resources))
+        }
+        most
+    }
+
+    proptest! {
+        // The search takes a while for more rounds
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn geodes_match_trying_every_order(costs in costs(), n_rounds in 1..=18u16) {
+            let blueprints = parse_blueprints(&blueprint_text(&costs)).unwrap();
+            let geodes = reference_geodes(&costs, n_rounds as usize, [1, 0, 0, 0], [0; 4]);
+            prop_assert_eq!(max_geodes_for_blueprint(&blueprints[0], n_rounds) as usize, geodes);
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let blueprints = parse_blueprints(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_1(&blueprints), 33)
+    }
+}
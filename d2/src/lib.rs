@@ -0,0 +1,508 @@
+use aoc_common::args::{RunArgs, UsageError, USAGE};
+use aoc_common::generate::{self, SliceRandom, StdRng};
+use aoc_common::parse::{parse_lines, LineParser};
+use aoc_common::runner::{print_answers, read_input};
+use aoc_common::{Answer, Day, ParseError, Part, Solution, Stats};
+use itertools::Itertools;
+use std::path::{Path, PathBuf};
+use std::process;
+
+pub struct Day2;
+
+pub const DAY: Day = Day::new::<Day2>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR"))
+    .with_stats::<Day2>()
+    .with_generator(generate);
+
+impl Solution for Day2 {
+    type Input = Guide;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_guide(input, Rules::standard())
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        input.total_score_part_1().map_or(Answer::Missing, Answer::from)
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        input.total_score_part_2().map_or(Answer::Missing, Answer::from)
+    }
+}
+
+// The most plausible readings of the guide
+impl Stats for Day2 {
+    fn stats(input: &Self::Input) -> Vec<(&'static str, String)> {
+        input.plausible(&input.interpretations())
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+enum Verdict {
+    Win,
+    Draw,
+    Lose
+}
+
+// A shape is its index in the rule table
+type Shape = usize;
+
+#[derive(Debug, Clone)]
+struct ShapeRule {
+    name: String,
+    // The letters for it in the opponent's column and in ours
+    theirs: char,
+    mine: char,
+    points: usize,
+}
+
+/// The game being played: the shapes, who beats whom, what the letters of the strategy guide
+/// mean and what everything is worth. The puzzle's game is in `rules.toml`, and other games can
+/// be loaded from files like it, e.g. `rpsls.toml` for Rock-Paper-Scissors-Lizard-Spock.
+#[derive(Debug, Clone)]
+pub struct Rules {
+    shapes: Vec<ShapeRule>,
+    // The verdict of each shape against each shape, `outcomes[mine][theirs]`
+    outcomes: Vec<Vec<Verdict>>,
+    // Our letters for the verdicts in part 2, and their points
+    verdicts: Vec<(char, Verdict, usize)>,
+}
+
+impl Rules {
+    pub fn standard() -> Self {
+        Rules::parse(include_str!("../rules.toml")).unwrap()
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))?;
+        Self::parse(&text).map_err(|err| format!("{}: {err}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let table: toml::Table = text.parse().map_err(|err: toml::de::Error| err.message().to_string())?;
+        let shape_tables = table.get("shapes").and_then(toml::Value::as_array).ok_or("expected [[shapes]]")?;
+        let mut shapes = vec![];
+        let mut beats = vec![];
+        for (ind, shape) in shape_tables.iter().enumerate() {
+            let field = |key: &str| shape.get(key).ok_or_else(|| format!("shapes[{ind}].{key} is missing"));
+            let name = field("name")?.as_str().ok_or_else(|| format!("shapes[{ind}].name should be a string"))?;
+            let theirs = letter(field("theirs")?).ok_or_else(|| format!("shapes[{ind}].theirs should be a letter"))?;
+            let mine = letter(field("mine")?).ok_or_else(|| format!("shapes[{ind}].mine should be a letter"))?;
+            let points = points(field("points")?).ok_or_else(|| format!("shapes[{ind}].points should be a number"))?;
+            let beaten: Option<Vec<&str>> = field("beats")?.as_array().and_then(|beaten| beaten.iter().map(toml::Value::as_str).collect());
+            beats.push(beaten.ok_or_else(|| format!("shapes[{ind}].beats should be a list of shape names"))?);
+            shapes.push(ShapeRule { name: String::from(name), theirs, mine, points });
+        }
+        if shapes.is_empty() {
+            return Err(String::from("there are no shapes"));
+        }
+        for (ind, shape) in shapes.iter().enumerate() {
+            for other in &shapes[..ind] {
+                if other.name == shape.name || other.theirs == shape.theirs || other.mine == shape.mine {
+                    return Err(format!("{} and {} have the same name or letter", other.name, shape.name));
+                }
+            }
+        }
+
+        let mut outcomes = vec![vec![Verdict::Draw; shapes.len()]; shapes.len()];
+        for (mine, beaten) in beats.iter().enumerate() {
+            for name in beaten {
+                let theirs = shapes.iter().position(|shape| shape.name == *name).ok_or_else(|| format!("{} beats {name}, which isn't a shape", shapes[mine].name))?;
+                if theirs == mine || outcomes[mine][theirs] == Verdict::Lose {
+                    return Err(format!("{} and {name} can't both win", shapes[mine].name));
+                }
+                outcomes[mine][theirs] = Verdict::Win;
+                outcomes[theirs][mine] = Verdict::Lose;
+            }
+        }
+
+        let mut verdicts = vec![];
+        for (key, verdict) in [("lose", Verdict::Lose), ("draw", Verdict::Draw), ("win", Verdict::Win)] {
+            let letter = table.get("verdicts").and_then(|letters| letters.get(key)).and_then(letter).ok_or_else(|| format!("verdicts.{key} should be a letter"))?;
+            let points = table.get("points").and_then(|points| points.get(key)).and_then(points).ok_or_else(|| format!("points.{key} should be a number"))?;
+            if verdicts.iter().any(|(other, _, _)| *other == letter) {
+                return Err(format!("verdicts.{key} has the letter of another verdict"));
+            }
+            verdicts.push((letter, verdict, points));
+        }
+        Ok(Rules { shapes, outcomes, verdicts })
+    }
+
+    fn shape_points(&self, shape: Shape) -> usize {
+        self.shapes[shape].points
+    }
+
+    fn verdict_points(&self, verdict: Verdict) -> usize {
+        self.verdicts.iter().find(|(_, known, _)| *known == verdict).unwrap().2
+    }
+
+    // The points of a round that we play with `mine`
+    fn score(&self, mine: Shape, theirs: Shape) -> usize {
+        self.verdict_points(self.outcomes[mine][theirs]) + self.shape_points(mine)
+    }
+
+    // The first shape that gets `verdict` against `theirs`, if any does
+    fn my_shape_to_get_verdict(&self, theirs: Shape, verdict: Verdict) -> Option<Shape> {
+        (0..self.shapes.len()).find(|mine| self.outcomes[*mine][theirs] == verdict)
+    }
+
+    // The letters that can be in our column: the shapes' and the verdicts', in order
+    fn my_letters(&self) -> Vec<char> {
+        let mut letters: Vec<char> = self.shapes.iter().map(|shape| shape.mine).collect();
+        letters.extend(self.verdicts.iter().map(|(letter, _, _)| *letter).filter(|letter| !letters.contains(letter)).collect::<Vec<_>>());
+        letters
+    }
+}
+
+fn letter(value: &toml::Value) -> Option<char> {
+    let mut chars = value.as_str()?.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), None) if letter.is_ascii_uppercase() => Some(letter),
+        _ => None,
+    }
+}
+
+fn points(value: &toml::Value) -> Option<usize> {
+    value.as_integer().and_then(|points| usize::try_from(points).ok())
+}
+
+// "A, B or C"
+fn or_list(letters: &[char]) -> String {
+    match letters {
+        [] => String::new(),
+        [letter] => letter.to_string(),
+        [first @ .., last] => format!("{} or {last}", first.iter().map(char::to_string).collect::<Vec<_>>().join(", ")),
+    }
+}
+
+pub struct Round {
+    theirs: Shape,
+    // Our letter, a shape in part 1 and a verdict in part 2
+    mine: char,
+}
+
+impl Round {
+    fn parse(line: &mut LineParser, rules: &Rules) -> Result<Self, ParseError> {
+        let their_letters: Vec<char> = rules.shapes.iter().map(|shape| shape.theirs).collect();
+        let their_options: Vec<(String, Shape)> = their_letters.iter().enumerate().map(|(shape, letter)| (letter.to_string(), shape)).collect();
+        let their_options: Vec<(&str, Shape)> = their_options.iter().map(|(letter, shape)| (letter.as_str(), *shape)).collect();
+        let theirs = line.one_of(&their_options, &or_list(&their_letters))?;
+        line.literal(" ")?;
+        let my_letters = rules.my_letters();
+        let my_options: Vec<(String, char)> = my_letters.iter().map(|letter| (letter.to_string(), *letter)).collect();
+        let my_options: Vec<(&str, char)> = my_options.iter().map(|(text, letter)| (text.as_str(), *letter)).collect();
+        let mine = line.one_of(&my_options, &or_list(&my_letters))?;
+        Ok(Round {theirs, mine})
+    }
+}
+
+/// The strategy guide, with the rules it is played by.
+pub struct Guide {
+    rules: Rules,
+    rounds: Vec<Round>,
+}
+
+impl Guide {
+    fn score_part_1(&self, round: &Round) -> usize {
+        let mine = self.rules.shapes.iter().position(|shape| shape.mine == round.mine).unwrap();
+        self.rules.score(mine, round.theirs)
+    }
+
+    // `None` if our letter isn't a verdict, or no shape gets it
+    fn score_part_2(&self, round: &Round) -> Option<usize> {
+        let (_, verdict, _) = self.rules.verdicts.iter().find(|(letter, _, _)| *letter == round.mine)?;
+        let mine = self.rules.my_shape_to_get_verdict(round.theirs, *verdict)?;
+        Some(self.rules.score(mine, round.theirs))
+    }
+
+    /// `None` if a letter of ours isn't a shape
+    fn total_score_part_1(&self) -> Option<usize> {
+        let is_shape = |round: &Round| self.rules.shapes.iter().any(|shape| shape.mine == round.mine);
+        self.rounds.iter().all(is_shape).then(|| self.rounds.iter().map(|round| self.score_part_1(round)).sum())
+    }
+
+    fn total_score_part_2(&self) -> Option<usize> {
+        self.rounds.iter().map(|round| self.score_part_2(round)).sum()
+    }
+
+    // The different letters in our column
+    fn my_letters(&self) -> Vec<char> {
+        self.rounds.iter().map(|round| round.mine).sorted().dedup().collect()
+    }
+
+    // Every way of giving each of our letters its own shape, or its own verdict
+    fn readings(&self) -> Vec<Reading> {
+        let letters = self.my_letters();
+        let as_shapes = (0..self.rules.shapes.len())
+            .permutations(letters.len())
+            .map(|shapes| Reading::Shapes(letters.iter().copied().zip(shapes).collect()));
+        let as_verdicts = [Verdict::Lose, Verdict::Draw, Verdict::Win]
+            .into_iter()
+            .permutations(letters.len())
+            .map(|verdicts| Reading::Verdicts(letters.iter().copied().zip(verdicts).collect()));
+        as_shapes.chain(as_verdicts).collect()
+    }
+
+    // `None` if the guide can't be played that way, when no shape gets a verdict
+    fn interpret(&self, reading: Reading) -> Option<Interpretation> {
+        let mut interpretation = Interpretation { reading, my_score: 0, their_score: 0, outcomes: [0; 3] };
+        for round in &self.rounds {
+            let mine = match &interpretation.reading {
+                Reading::Shapes(shapes) => shapes.iter().find(|(letter, _)| *letter == round.mine)?.1,
+                Reading::Verdicts(verdicts) => {
+                    let (_, verdict) = verdicts.iter().find(|(letter, _)| *letter == round.mine)?;
+                    self.rules.my_shape_to_get_verdict(round.theirs, *verdict)?
+                }
+            };
+            interpretation.my_score += self.rules.score(mine, round.theirs);
+            interpretation.their_score += self.rules.score(round.theirs, mine);
+            let outcome = match self.rules.outcomes[mine][round.theirs] {
+                Verdict::Win => 0,
+                Verdict::Draw => 1,
+                Verdict::Lose => 2,
+            };
+            interpretation.outcomes[outcome] += 1;
+        }
+        Some(interpretation)
+    }
+
+    /// How the guide plays out for every reading of our column.
+    fn interpretations(&self) -> Vec<Interpretation> {
+        self.readings().into_iter().filter_map(|reading| self.interpret(reading)).collect()
+    }
+
+    // Every reading of the guide, then the most plausible ones
+    fn analysis(&self) -> String {
+        let interpretations = self.interpretations();
+        let mut report: String = interpretations.iter().map(|interpretation| interpretation.describe(&self.rules) + "\n").collect();
+        for (label, value) in self.plausible(&interpretations) {
+            report += &format!("{label}: {value}\n");
+        }
+        report
+    }
+
+    // The reading with the most points for us and the one with the fewest for the opponent (the
+    // first of them, on ties)
+    fn plausible(&self, interpretations: &[Interpretation]) -> Vec<(&'static str, String)> {
+        let best = interpretations.iter().min_by_key(|interpretation| std::cmp::Reverse(interpretation.my_score));
+        let meanest = interpretations.iter().min_by_key(|interpretation| interpretation.their_score);
+        [("Most points for us", best), ("Fewest points for the opponent", meanest)]
+            .into_iter()
+            .filter_map(|(label, interpretation)| Some((label, interpretation?.describe(&self.rules))))
+            .collect()
+    }
+}
+
+/// What our column could mean: each letter a shape to play, or a verdict to play for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Reading {
+    Shapes(Vec<(char, Shape)>),
+    Verdicts(Vec<(char, Verdict)>),
+}
+
+/// The guide played by one reading.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Interpretation {
+    reading: Reading,
+    my_score: usize,
+    their_score: usize,
+    // The rounds we win, draw and lose
+    outcomes: [usize; 3],
+}
+
+impl Interpretation {
+    // "X=Rock, Y=Paper, Z=Scissors: 15 to 15, 1 won, 1 drawn, 1 lost"
+    fn describe(&self, rules: &Rules) -> String {
+        let meanings = match &self.reading {
+            Reading::Shapes(shapes) => shapes.iter().map(|(letter, shape)| format!("{letter}={}", rules.shapes[*shape].name)).join(", "),
+            Reading::Verdicts(verdicts) => verdicts.iter().map(|(letter, verdict)| format!("{letter}={verdict:?}")).join(", "),
+        };
+        let [won, drawn, lost] = self.outcomes;
+        format!("{meanings}: {} to {}, {won} won, {drawn} drawn, {lost} lost", self.my_score, self.their_score)
+    }
+}
+
+fn parse_guide(input: &str, rules: Rules) -> Result<Guide, ParseError> {
+    let rounds = parse_lines(input, |line| Round::parse(line, &rules))?;
+    Ok(Guide { rules, rounds })
+}
+
+/// `d2 [--rules <path>] [--analyze] ...`: solves the strategy guide with the rules of another
+/// game, such as `rpsls.toml`, or with `--analyze` scores every reading of our column instead.
+pub fn guide_main(args: Vec<String>) {
+    let program = std::env::args().next().unwrap_or_else(|| String::from(DAY.name));
+    let (rules, analyze, run_args) = guide_args(args).unwrap_or_else(|err| {
+        eprintln!("{err}\nUsage: {program} [--rules <path>] [--analyze] {USAGE}");
+        process::exit(2)
+    });
+    let rules = rules.map_or_else(|| Ok(Rules::standard()), |rules| Rules::load(&rules)).unwrap_or_else(|err| {
+        eprintln!("Could not load the rules: {err}");
+        process::exit(1)
+    });
+    let input = read_input(&DAY, &run_args);
+    let guide = parse_guide(&input, rules).unwrap_or_else(|err| {
+        eprintln!("Could not parse {}: {err}", run_args.input.name(Path::new(DAY.dir)));
+        process::exit(1)
+    });
+    if analyze {
+        print!("{}", guide.analysis());
+        return;
+    }
+    let answers: Vec<(Part, Answer)> = run_args
+        .parts()
+        .into_iter()
+        .map(|part| {
+            let score = match part {
+                Part::One => guide.total_score_part_1(),
+                Part::Two => guide.total_score_part_2(),
+            };
+            (part, score.map_or(Answer::Missing, Answer::from))
+        })
+        .collect();
+    print_answers(&answers, "")
+}
+
+fn guide_args(args: Vec<String>) -> Result<(Option<PathBuf>, bool, RunArgs), UsageError> {
+    let mut rules = None;
+    let mut analyze = false;
+    let mut run_args = vec![];
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rules" => rules = Some(args.next().ok_or_else(|| UsageError(format!("{arg} needs a path")))?.into()),
+            "--analyze" => analyze = true,
+            _ => run_args.push(arg),
+        }
+    }
+    Ok((rules, analyze, RunArgs::parse(run_args)?))
+}
+
+/// `size` rounds
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    generate::lines(rng, size, |rng, _| {
+        format!("{} {}", *b"ABC".choose(rng).unwrap() as char, *b"XYZ".choose(rng).unwrap() as char)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // The rounds as their index and ours, 0 to 2
+    fn rounds() -> impl Strategy<Value = Vec<(usize, usize)>> {
+        prop::collection::vec((0..3usize, 0..3usize), 0..50)
+    }
+
+    fn input_text(rounds: &[(usize, usize)]) -> String {
+        rounds.iter().map(|(theirs, mine)| format!("{} {}\n", b"ABC"[*theirs] as char, b"XYZ"[*mine] as char)).collect()
+    }
+
+    // Each shape beats the one before it, and a win is 3 points more than a draw, which is 3 more than a loss
+    fn reference_score(mine: usize, theirs: usize) -> usize {
+        mine + 1 + 3 * ((mine + 4 - theirs) % 3)
+    }
+
+    proptest! {
+        #[test]
+        fn scores_match_reference(rounds in rounds()) {
+            let guide = parse_guide(&input_text(&rounds), Rules::standard()).unwrap();
+            let part_1: usize = rounds.iter().map(|&(theirs, mine)| reference_score(mine, theirs)).sum();
+            prop_assert_eq!(guide.total_score_part_1(), Some(part_1));
+            // X loses, Y draws and Z wins, by playing the shape before theirs, theirs or the one after it
+            let part_2: usize = rounds.iter().map(|&(theirs, verdict)| reference_score((theirs + verdict + 2) % 3, theirs)).sum();
+            prop_assert_eq!(guide.total_score_part_2(), Some(part_2));
+        }
+
+        #[test]
+        fn interpretations_include_both_parts(rounds in rounds()) {
+            let guide = parse_guide(&input_text(&rounds), Rules::standard()).unwrap();
+            let interpretations = guide.interpretations();
+            let letters = guide.my_letters();
+            // Some shape gets every verdict in the standard game, so every reading can be played
+            prop_assert_eq!(interpretations.len(), 2 * (4 - letters.len()..=3).product::<usize>());
+            let as_parts = |reading: &Reading| interpretations.iter().find(|interpretation| interpretation.reading == *reading).map(|interpretation| interpretation.my_score);
+            let shapes = Reading::Shapes(letters.iter().map(|letter| (*letter, (*letter as u8 - b'X') as usize)).collect());
+            let verdicts = Reading::Verdicts(letters.iter().map(|letter| (*letter, [Verdict::Lose, Verdict::Draw, Verdict::Win][(*letter as u8 - b'X') as usize])).collect());
+            prop_assert_eq!(as_parts(&shapes), guide.total_score_part_1());
+            prop_assert_eq!(as_parts(&verdicts), guide.total_score_part_2());
+            for interpretation in &interpretations {
+                prop_assert_eq!(interpretation.outcomes.iter().sum::<usize>(), rounds.len());
+                // Each round gives 6 points to share out for the verdict, plus both shapes' points
+                prop_assert!(interpretation.my_score + interpretation.their_score >= 6 * rounds.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let guide = parse_guide(include_str!("../test.txt"), Rules::standard()).unwrap();
+        assert_eq!(guide.total_score_part_1(),  Some(15))
+    }
+
+    #[test]
+    fn test_part_2() {
+        let guide = parse_guide(include_str!("../test.txt"), Rules::standard()).unwrap();
+        assert_eq!(guide.total_score_part_2(),  Some(12))
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_guide("A Y\nB Q\n", Rules::standard()).err().unwrap();
+        assert_eq!(err, ParseError::new(2, 3, "Q", "X, Y or Z"))
+    }
+
+    #[test]
+    fn test_standard_rules() {
+        // All nine rounds, our shape down and theirs across
+        let rules = Rules::standard();
+        let scores: Vec<Vec<usize>> = (0..3).map(|mine| (0..3).map(|theirs| rules.score(mine, theirs)).collect()).collect();
+        assert_eq!(scores, vec![vec![4, 1, 7], vec![8, 5, 2], vec![3, 9, 6]]);
+        // Custom points
+        let cheap_wins = include_str!("../rules.toml").replace("win = 6", "win = 1");
+        let guide = parse_guide(include_str!("../test.txt"), Rules::parse(&cheap_wins).unwrap()).unwrap();
+        assert_eq!(guide.total_score_part_1(), Some(10))
+    }
+
+    #[test]
+    fn test_rpsls() {
+        let guide = parse_guide(include_str!("../test_rpsls.txt"), Rules::parse(include_str!("../rpsls.toml")).unwrap()).unwrap();
+        assert_eq!(guide.total_score_part_1(), Some(21));
+        assert_eq!(guide.total_score_part_2(), Some(16));
+        let guide = parse_guide("A W\n", guide.rules).unwrap();
+        assert_eq!((guide.total_score_part_1(), guide.total_score_part_2()), (Some(8), None))
+    }
+
+    #[test]
+    fn test_interpretations() {
+        let guide = parse_guide(include_str!("../test.txt"), Rules::standard()).unwrap();
+        let interpretations = guide.interpretations();
+        // Six ways to give X, Y and Z a shape each, and six to give them a verdict each
+        assert_eq!(interpretations.len(), 12);
+        let part_1 = interpretations.iter().find(|interpretation| interpretation.reading == Reading::Shapes(vec![('X', 0), ('Y', 1), ('Z', 2)])).unwrap();
+        assert_eq!((part_1.my_score, part_1.their_score, part_1.outcomes), (15, 15, [1, 1, 1]));
+        let part_2 = Reading::Verdicts(vec![('X', Verdict::Lose), ('Y', Verdict::Draw), ('Z', Verdict::Win)]);
+        assert_eq!(interpretations.iter().find(|interpretation| interpretation.reading == part_2).unwrap().my_score, 12);
+        assert_eq!(
+            guide.plausible(&interpretations),
+            vec![
+                ("Most points for us", String::from("X=Scissors, Y=Paper, Z=Rock: 24 to 6, 3 won, 0 drawn, 0 lost")),
+                ("Fewest points for the opponent", String::from("X=Scissors, Y=Paper, Z=Rock: 24 to 6, 3 won, 0 drawn, 0 lost")),
+            ]
+        );
+        // Two letters for five shapes, or for two of the three verdicts
+        let guide = parse_guide("A X\nB Y\n", Rules::parse(include_str!("../rpsls.toml")).unwrap()).unwrap();
+        assert_eq!(guide.interpretations().len(), 5 * 4 + 3 * 2)
+    }
+
+    #[test]
+    fn test_bad_rules() {
+        let rules = include_str!("../rules.toml");
+        assert!(Rules::parse(&rules.replace("beats = [\"Rock\"]", "beats = [\"Stone\"]")).is_err());
+        assert!(Rules::parse(&rules.replace("beats = [\"Rock\"]", "beats = [\"Paper\"]")).is_err());
+        assert!(Rules::parse(&rules.replace("beats = [\"Rock\"]", "beats = [\"Scissors\"]")).is_err());
+        assert!(Rules::parse(&rules.replace("theirs = \"B\"", "theirs = \"A\"")).is_err());
+        assert!(Rules::parse(&rules.replace("win = \"Z\"", "win = \"Y\"")).is_err());
+        assert!(Rules::parse(&rules.replace("points = 2", "points = -2")).is_err());
+        assert!(Rules::parse("").is_err())
+    }
+}
@@ -0,0 +1,217 @@
+use aoc_common::generate::{self, Rng, StdRng};
+use aoc_common::parse::parse_lines;
+use aoc_common::{Answer, Day, ParseError, Solution};
+
+pub struct Day20;
+
+pub const DAY: Day = Day::new::<Day20>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day20 {
+    type Input = Vec<isize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input).into()
+    }
+}
+
+#[derive(Clone)]
+struct Value {
+    value: isize,
+    original_index: usize
+}
+
+fn mix(mut file: Vec<Value>) -> Vec<Value> {
+    for original_ind in 0..file.len() {
+        let current_ind = file.iter().position(|v| v.original_index == original_ind).unwrap();
+        file = move_index(file, current_ind)
+    }
+    file
+}
+
+fn part_1(input: &[isize]) -> isize {
+    let file: Vec<_> = input.iter().enumerate().map(|(ind, v)| Value {value: *v, original_index: ind }).collect();
+    let file = mix(file);
+    grove_coordinates(&file).iter().sum()
+}
+
+fn part_2(input: &[isize]) -> isize {
+    let mut file: Vec<_> = input.iter().enumerate().map(|(ind, v)| Value {value: *v * 811589153, original_index: ind }).collect();
+    for _ in 0..10 {
+        file = mix(file)
+    }
+    grove_coordinates(&file).iter().sum()
+}
+
+fn move_index(mut file: Vec<Value>, current_ind: usize) -> Vec<Value> {
+    let value_to_move = file.remove(current_ind);
+    let delta = value_to_move.value;
+    let new_ind = if delta < 0 {
+        let delta = -delta % file.len() as isize;
+        if delta as usize >= current_ind {
+            file.len() - (delta as usize - current_ind)
+        } else {
+            current_ind - delta as usize
+        }
+    } else if delta > 0 {
+        let delta = delta % file.len() as isize;
+        if delta as usize + current_ind <= file.len() {
+            current_ind + delta as usize
+        } else {
+            current_ind + delta as usize - file.len()
+        }
+    } else {
+        current_ind
+    };
+    file.insert(new_ind, value_to_move);
+    file
+}
+
+
+fn grove_coordinates(file: &[Value]) -> Vec<isize> {
+    let indexes_after_zero: [usize; 3] = [1000, 2000, 3000];
+    let zero_index = file.iter().position(|v| v.value == 0).unwrap();
+    let file_indexes: Vec<_> = indexes_after_zero.iter()
+        .map(|ind| {
+            (zero_index + ind) % file.len()
+        })
+        .collect();
+    file_indexes.iter().map(|ind| file[*ind].value).collect()
+}
+
+fn load_input(input: &str) -> Result<Vec<isize>, ParseError> {
+    let numbers = parse_lines(input, |line| line.number::<isize>())?;
+    // The coordinates are counted from the 0
+    if !numbers.contains(&0) {
+        return Err(ParseError::new(numbers.len() + 1, 1, "", "a 0 somewhere in the file"));
+    }
+    Ok(numbers)
+}
+
+/// `size` numbers, one of them 0
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let zero = rng.gen_range(0..size.max(1));
+    generate::lines(rng, size.max(1), |rng, ind| {
+        let number = if ind == zero { 0 } else { rng.gen_range(1..=10_000) * if rng.gen_bool(0.5) { 1 } else { -1 } };
+        number.to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Moves each number by swapping it with its neighbour, one step at a time around the circle
+    #[allow(clippy::needless_range_loop)]
+    fn reference_mix(numbers: &[isize], n_rounds: usize) -> Vec<isize> {
+        let n = numbers.len();
+        let mut order: Vec<usize> = (0..n).collect();
+        for _ in 0..n_rounds {
+            for original_ind in 0..n {
+                let mut ind = order.iter().position(|o| *o == original_ind).unwrap();
+                let steps = numbers[original_ind].rem_euclid(n as isize - 1);
+                for _ in 0..steps {
+                    let next = (ind + 1) % n;
+                    order.swap(ind, next);
+                    ind = next;
+                }
+            }
+        }
+        order.into_iter().map(|ind| numbers[ind]).collect()
+    }
+
+    fn reference_grove_sum(mixed: &[isize]) -> isize {
+        let zero = mixed.iter().position(|v| *v == 0).unwrap();
+        [1000, 2000, 3000].iter().map(|offset| mixed[(zero + offset) % mixed.len()]).sum()
+    }
+
+    // Starting from the 0, since where the circle starts doesn't matter
+    fn from_zero(values: &[isize]) -> Vec<isize> {
+        let zero = values.iter().position(|v| *v == 0).unwrap();
+        values[zero..].iter().chain(&values[..zero]).copied().collect()
+    }
+
+    // One 0, and other numbers that may repeat
+    fn numbers() -> impl Strategy<Value = Vec<isize>> {
+        (prop::collection::vec((-30..30isize).prop_filter("not 0", |v| *v != 0), 1..25), any::<prop::sample::Index>())
+            .prop_map(|(mut numbers, zero)| {
+                numbers.insert(zero.index(numbers.len() + 1), 0);
+                numbers
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn mix_matches_reference(numbers in numbers()) {
+            let file: Vec<_> = numbers.iter().enumerate().map(|(ind, v)| Value { value: *v, original_index: ind }).collect();
+            let mixed: Vec<isize> = mix(file).into_iter().map(|v| v.value).collect();
+            let reference = reference_mix(&numbers, 1);
+            prop_assert_eq!(from_zero(&mixed), from_zero(&reference));
+            prop_assert_eq!(part_1(&numbers), reference_grove_sum(&reference));
+        }
+
+        #[test]
+        fn part_2_matches_reference(numbers in numbers()) {
+            let decrypted: Vec<isize> = numbers.iter().map(|v| v * 811589153).collect();
+            prop_assert_eq!(part_2(&numbers), reference_grove_sum(&reference_mix(&decrypted, 10)));
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let input = load_input(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_1(&input), 3)
+    }
+
+    #[test]
+    fn test_part_2() {
+        let input = load_input(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_2(&input), 1623178306)
+    }
+
+    #[test]
+    fn test_move() {
+        let input: [isize; 3] = [1,2,3];
+
+        let mut file: Vec<_> = input.iter().enumerate().map(|(ind, v)| Value {value: *v, original_index: ind }).collect();
+        file = move_index(file, 0);
+        let after_move = file.into_iter().map(|v|v.value).collect::<Vec<_>>();
+        assert_eq!(after_move, vec![2isize,1,3]);
+
+        let mut file: Vec<_> = input.iter().enumerate().map(|(ind, v)| Value {value: *v, original_index: ind }).collect();
+        file = move_index(file, 1);
+        let after_move = file.into_iter().map(|v|v.value).collect::<Vec<_>>();
+        assert_eq!(after_move, vec![1isize,2,3]);
+
+        let mut file: Vec<_> = input.iter().enumerate().map(|(ind, v)| Value {value: *v, original_index: ind }).collect();
+        file = move_index(file, 2);
+        let after_move = file.into_iter().map(|v|v.value).collect::<Vec<_>>();
+        assert_eq!(after_move, vec![1isize,3,2]);
+
+        let input: [isize; 3] = [-1,-2,-3];
+
+        let mut file: Vec<_> = input.iter().enumerate().map(|(ind, v)| Value {value: *v, original_index: ind }).collect();
+        file = move_index(file, 0);
+        let after_move = file.into_iter().map(|v|v.value).collect::<Vec<_>>();
+        assert_eq!(after_move, vec![-2isize,-1,-3]);
+
+        let mut file: Vec<_> = input.iter().enumerate().map(|(ind, v)| Value {value: *v, original_index: ind }).collect();
+        file = move_index(file, 1);
+        let after_move = file.into_iter().map(|v|v.value).collect::<Vec<_>>();
+        assert_eq!(after_move, vec![-1isize,-2,-3]);
+
+        let mut file: Vec<_> = input.iter().enumerate().map(|(ind, v)| Value {value: *v, original_index: ind }).collect();
+        file = move_index(file, 2);
+        let after_move = file.into_iter().map(|v|v.value).collect::<Vec<_>>();
+        assert_eq!(after_move, vec![-1isize,-3,-2]);
+
+    }
+}
\ No newline at end of file
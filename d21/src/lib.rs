@@ -0,0 +1,278 @@
+use aoc_common::generate::{self, Rng, SliceRandom, StdRng};
+use aoc_common::parse::{parse_lines, LineParser};
+use aoc_common::{Answer, Day, ParseError, Solution};
+use std::collections::HashMap;
+use tracing::{debug_span, trace};
+
+pub struct Day21;
+
+pub const DAY: Day = Day::new::<Day21>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day21 {
+    type Input = HashMap<String, Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_monkeys(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input.clone()).map_or(Answer::Missing, Answer::from)
+    }
+}
+
+fn part_1(monkeys: &HashMap<String, Monkey>) -> usize {
+    monkeys.get("root").unwrap().eval(monkeys)
+}
+
+// `None` if no number makes both sides of root the same
+fn part_2(mut monkeys: HashMap<String, Monkey>) -> Option<usize> {
+    let (first, second) = monkeys.get("root").unwrap().operands()?;
+    let (first, second) = (first.clone(), second.clone());
+    let _span = debug_span!("working out what to shout").entered();
+    // Whatever humn shouts has to make its side of root come out as the other side does
+    let (human_side, other_side) = if listens_to_human(&first, &monkeys) { (&first, &second) } else { (&second, &first) };
+    let target = monkeys.get(other_side).unwrap().eval(&monkeys);
+    let my_shout = shout_for(human_side, target, &monkeys)?;
+    // The divisions round down, and humn might be on both sides
+    monkeys.insert(String::from("humn"), Monkey::NumberMonkey { number: my_shout });
+    monkeys.insert(String::from("root"), Monkey::EqualityMonkey { first, second });
+    (monkeys.get("root").unwrap().eval(&monkeys) == 1).then_some(my_shout)
+}
+
+fn listens_to_human(name: &str, monkeys: &HashMap<String, Monkey>) -> bool {
+    name == "humn"
+        || monkeys.get(name).unwrap().operands().is_some_and(|(first, second)| listens_to_human(first, monkeys) || listens_to_human(second, monkeys))
+}
+
+// What humn has to shout for the monkey `name`, which listens to humn somewhere down the line, to
+// shout `target`: undoing the operation of each monkey on the way down to humn
+fn shout_for(name: &str, target: usize, monkeys: &HashMap<String, Monkey>) -> Option<usize> {
+    if name == "humn" {
+        return Some(target)
+    }
+    let monkey = monkeys.get(name).unwrap();
+    let (first, second) = monkey.operands()?;
+    let human_first = listens_to_human(first, monkeys);
+    let (human_side, other_side) = if human_first { (first, second) } else { (second, first) };
+    let other = monkeys.get(other_side).unwrap().eval(monkeys);
+    trace!(name, target, other, "Undoing");
+    let target = match (monkey, human_first) {
+        (Monkey::AddingMonkey { .. }, _) => target.checked_sub(other)?,
+        (Monkey::SubtractingMonkey { .. }, true) => target.checked_add(other)?,
+        (Monkey::SubtractingMonkey { .. }, false) => other.checked_sub(target)?,
+        (Monkey::MultiplyingMonkey { .. }, _) => target.checked_div(other).filter(|_| target.is_multiple_of(other))?,
+        (Monkey::DividingMonkey { .. }, true) => target.checked_mul(other)?,
+        (Monkey::DividingMonkey { .. }, false) => other.checked_div(target).filter(|_| other.is_multiple_of(target))?,
+        _ => return None,
+    };
+    shout_for(human_side, target, monkeys)
+}
+
+#[derive(Clone)]
+pub enum Monkey {
+    EqualityMonkey {
+        first: String,
+        second: String
+    },
+    AddingMonkey {
+        first: String,
+        second: String
+    },
+    SubtractingMonkey {
+        first: String,
+        second: String
+    },
+    DividingMonkey {
+        first: String,
+        second: String,
+    },
+    MultiplyingMonkey {
+        first: String,
+        second: String
+    },
+    NumberMonkey {
+        number: usize
+    }
+}
+
+impl Monkey {
+    fn eval(&self, monkeys: &HashMap<String, Monkey>) -> usize {
+        match self {
+            Monkey::EqualityMonkey { first, second } => {
+                let first_value  = monkeys.get(first).unwrap().eval(monkeys);
+                let second_value = monkeys.get(second).unwrap().eval(monkeys);
+                trace!(first_value, second_value, "Comparing");
+                if  first_value == second_value {
+                    1  // C-style true
+                } else {
+                    0  // C-style false
+                }
+            },
+            Monkey::AddingMonkey { first, second } => {
+                monkeys.get(first).unwrap().eval(monkeys) + monkeys.get(second).unwrap().eval(monkeys)
+            },
+            Monkey::SubtractingMonkey { first, second } => {
+                monkeys.get(first).unwrap().eval(monkeys) - monkeys.get(second).unwrap().eval(monkeys)
+            },
+            Monkey::DividingMonkey { first, second } => {
+                monkeys.get(first).unwrap().eval(monkeys) / monkeys.get(second).unwrap().eval(monkeys)
+            },
+            Monkey::MultiplyingMonkey { first, second } => {
+                monkeys.get(first).unwrap().eval(monkeys) * monkeys.get(second).unwrap().eval(monkeys)
+            },
+            Monkey::NumberMonkey { number } => *number
+        }
+    }
+    
+    // The monkeys it listens to, unless it shouts a number of its own
+    fn operands(&self) -> Option<(&String, &String)> {
+        match self {
+            Monkey::EqualityMonkey { first, second }
+            | Monkey::AddingMonkey { first, second }
+            | Monkey::SubtractingMonkey { first, second }
+            | Monkey::DividingMonkey { first, second }
+            | Monkey::MultiplyingMonkey { first, second } => Some((first, second)),
+            Monkey::NumberMonkey { .. } => None,
+        }
+    }
+}
+
+// `root: pppw + sjmn` or `dbpl: 5`, remembering the names of the monkeys listened to in `listened_to`
+fn parse_monkey(line: &mut LineParser, listened_to: &mut Vec<ParseError>) -> Result<(String, Monkey), ParseError> {
+    let label = parse_name(line)?;
+    line.literal(": ")?;
+    if line.rest().starts_with(|c: char| c.is_ascii_digit()) {
+        return Ok((label, Monkey::NumberMonkey { number: line.number()? }))
+    }
+    let first = parse_name(line)?;
+    listened_to.push(line.error_at(&first, "the name of a monkey"));
+    line.literal(" ")?;
+    let operation = line.one_of(&[("+", '+'), ("-", '-'), ("/", '/'), ("*", '*')], "+, -, / or *")?;
+    line.literal(" ")?;
+    let second = parse_name(line)?;
+    listened_to.push(line.error_at(&second, "the name of a monkey"));
+    Ok((label, match operation {
+        '+' => Monkey::AddingMonkey { first, second },
+        '-' => Monkey::SubtractingMonkey { first, second },
+        '/' => Monkey::DividingMonkey { first, second },
+        _ => Monkey::MultiplyingMonkey { first, second },
+    }))
+}
+
+fn parse_name(line: &mut LineParser) -> Result<String, ParseError> {
+    Ok(String::from(line.take_until(|c| !c.is_ascii_lowercase(), "a monkey name (a-z)")?))
+}
+
+fn parse_monkeys(input: &str) -> Result<HashMap<String, Monkey>, ParseError> {
+    let mut listened_to = vec![];
+    let monkeys: HashMap<_, _> = parse_lines(input, |line| parse_monkey(line, &mut listened_to))?
+        .into_iter()
+        .collect();
+    if let Some(err) = listened_to.into_iter().find(|err| !monkeys.contains_key(&err.text)) {
+        return Err(err)
+    }
+    for name in ["root", "humn"] {
+        if !monkeys.contains_key(name) {
+            return Err(ParseError::new(monkeys.len() + 1, 1, "", &format!("a monkey named {name}")))
+        }
+    }
+    Ok(monkeys)
+}
+
+/// About `size` monkeys (at most 400,000, with four letter names) in a tree of operations, with root adding up two of them and humn somewhere
+/// among the leaves. Subtractions and divisions are only used where they come out even and positive,
+/// and multiplications where they can't overflow. The monkeys that listen to humn only add and
+/// multiply, so that they still do for whatever humn shouts, and the side of root without humn is
+/// evened up with one more monkey to what the other side comes to for some other number humn could
+/// shout, which is the answer of part 2.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let n_leaves = (size / 2).clamp(2, 200_000);
+    let mut names = generate::names(rng, 2 * n_leaves - 1, 4, generate::LOWERCASE, &["root", "humn"]);
+    let humn = rng.gen_range(0..n_leaves);
+    names.insert(humn, String::from("humn"));
+    let answer = rng.gen_range(1..=20);
+    let mut monkeys = vec![];
+    // The names of the monkeys that no other monkey listens to yet, what they shout for part 1 and
+    // for part 2, and whether they listen to humn
+    let mut unheard: Vec<(usize, [usize; 2], bool)> = (0..n_leaves)
+        .map(|ind| {
+            let value = rng.gen_range(1..=20);
+            monkeys.push(format!("{}: {value}", names[ind]));
+            (ind, if ind == humn { [value, answer] } else { [value, value] }, ind == humn)
+        })
+        .collect();
+    for ind in n_leaves..2 * n_leaves - 2 {
+        unheard.shuffle(rng);
+        let (mut first, mut second) = (unheard.pop().unwrap(), unheard.pop().unwrap());
+        if first.1[0] < second.1[0] {
+            (first, second) = (second, first)
+        }
+        let human = first.2 || second.2;
+        let (a, b) = (first.1, second.1);
+        let (operation, value) = match rng.gen_range(0..4) {
+            0 if !human && a[0] > b[0] => ('-', a.map(|_| a[0] - b[0])),
+            1 if !human && a[0].is_multiple_of(b[0]) => ('/', a.map(|_| a[0] / b[0])),
+            2 if (0..2).all(|part| a[part] * b[part] <= 1_000_000_000) => ('*', [a[0] * b[0], a[1] * b[1]]),
+            _ => ('+', [a[0] + b[0], a[1] + b[1]]),
+        };
+        monkeys.push(format!("{}: {} {operation} {}", names[ind], names[first.0], names[second.0]));
+        unheard.push((ind, value, human))
+    }
+    unheard.sort_by_key(|(_, _, human)| *human);
+    let (other, human) = (unheard[0], unheard[1]);
+    let (even_up, evened) = (2 * n_leaves - 2, 2 * n_leaves - 1);
+    let (operation, difference) = if other.1[1] <= human.1[1] {
+        ('+', human.1[1] - other.1[1])
+    } else {
+        ('-', other.1[1] - human.1[1])
+    };
+    monkeys.push(format!("{}: {difference}", names[even_up]));
+    monkeys.push(format!("{}: {} {operation} {}", names[evened], names[other.0], names[even_up]));
+    let mut sides = [&names[human.0], &names[evened]];
+    sides.shuffle(rng);
+    monkeys.push(format!("root: {} + {}", sides[0], sides[1]));
+    monkeys.shuffle(rng);
+    monkeys.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn shout_evens_up_root(seed in any::<u64>(), size in 0..200usize) {
+            let mut monkeys = parse_monkeys(&generate(&mut generate::rng(seed), size)).unwrap();
+            let my_shout = part_2(monkeys.clone());
+            prop_assert!(my_shout.is_some());
+            monkeys.insert(String::from("humn"), Monkey::NumberMonkey { number: my_shout.unwrap() });
+            let (first, second) = monkeys.get("root").unwrap().operands().unwrap();
+            prop_assert_eq!(monkeys.get(first).unwrap().eval(&monkeys), monkeys.get(second).unwrap().eval(&monkeys));
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let monkeys = parse_monkeys(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_1(&monkeys), 152)
+    }
+
+    #[test]
+    fn test_part_2() {
+        let monkeys = parse_monkeys(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_2(monkeys), Some(301))
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_monkeys("root: pppw % sjmn\n").err().unwrap();
+        assert_eq!(err, ParseError::new(1, 12, "%", "+, -, / or *"));
+        let err = parse_monkeys("root: pppw + sjmn\nhumn: 5\npppw: 2\n").err().unwrap();
+        assert_eq!(err, ParseError::new(1, 14, "sjmn", "the name of a monkey"))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,756 @@
+use aoc_common::frames::{Frame, Palette, Recorder, Visualize};
+use aoc_common::generate::{self, Rng, StdRng};
+use aoc_common::input_store::split_sections;
+use aoc_common::parse::{padded_char_grid, LineParser, Lines};
+use aoc_common::Direction;
+use aoc_common::{Answer, Day, Grid, ParseError, Part, Solution};
+use std::collections::{HashSet, HashMap};
+use tracing::debug;
+
+pub struct Day22;
+
+pub const DAY: Day = Day::new::<Day22>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR"))
+    .with_sections(&["map", "instructions"])
+    .with_frames::<Day22>()
+    .with_generator(generate);
+
+impl Solution for Day22 {
+    type Input = (Map, Vec<Instruction>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1((map, instructions): &Self::Input) -> Answer {
+        part_1(map.clone(), instructions.clone(), &mut Recorder::off()).into()
+    }
+
+    fn part_2((map, instructions): &Self::Input) -> Answer {
+        part_2(load_map2(map), instructions.clone(), &mut Recorder::off()).into()
+    }
+}
+
+// A frame for every instruction, with the trail so far drawn on the map. Part 2 walks the cube,
+// but is drawn on the flat map too.
+impl Visualize for Day22 {
+    fn simulate((map, instructions): &Self::Input, part: Part, recorder: &mut Recorder) {
+        match part {
+            Part::One => part_1(map.clone(), instructions.clone(), recorder),
+            Part::Two => part_2(load_map2(map), instructions.clone(), recorder),
+        };
+    }
+
+    fn palette() -> Palette {
+        let palette = Palette::new([0x0f, 0x0f, 0x23]).with(' ', [0x0f, 0x0f, 0x23]).with('.', [0x30, 0x30, 0x50]).with('#', [0x99, 0x99, 0x99]);
+        ['>', 'v', '<', '^'].into_iter().fold(palette, |palette, arrow| palette.with(arrow, [0xff, 0xff, 0x00]))
+    }
+}
+
+// The map and the instructions, the sections the input cache splits the input into
+fn parse_input(input: &str) -> Result<(Map, Vec<Instruction>), ParseError> {
+    let sections = split_sections(input, 2);
+    let mut n_rows = 0;
+    for mut line in Lines::new(sections[0]) {
+        // Only the first line can be empty, the section ends at the first blank line
+        if line.is_done() {
+            return Err(line.error("a row of the map"))
+        }
+        let tiles = [(".", ()), ("#", ()), (" ", ())];
+        while !line.is_done() {
+            line.one_of(&tiles, "an open tile (.), a wall (#) or a space")?
+        }
+        n_rows += 1
+    }
+    if n_rows == 0 {
+        return Err(ParseError::new(1, 1, "", "a row of the map"))
+    }
+    let Some(path) = sections.get(1) else {
+        return Err(ParseError::new(n_rows + 1, 1, "", "a row of the map or the blank line before the path"))
+    };
+    // After the map and the blank line
+    let mut line = Lines::after(n_rows + 1, path).expect_line("the path")?;
+    let instructions = load_instructions(&mut line)?;
+    Ok((load_map(sections[0]), instructions))
+}
+
+fn part_2(map: Map2, instructions: Vec<Instruction>, recorder: &mut Recorder) -> usize {
+    let (start_row, start_col) = map.get_start_position();
+    let mut cursor = Cursor2::new(start_row, start_col);
+    // Only drawn on when someone is watching
+    let drawing = recorder.is_on();
+    let mut trail = if drawing { map.flat_grid() } else { Grid::new(0, 0, ' ') };
+    let mark = |trail: &mut Grid<char>, cursor: &Cursor2| {
+        if drawing {
+            trail[map.original_coordinates(cursor.row, cursor.col, cursor.face)] = arrow(cursor.direction)
+        }
+    };
+    mark(&mut trail, &cursor);
+    for instruction in instructions {
+        match instruction {
+            Instruction::TurnLeft => {
+                cursor.turn_left();
+                mark(&mut trail, &cursor);
+            },
+            Instruction::TurnRight => {
+                cursor.turn_right();
+                mark(&mut trail, &cursor);
+            },
+            Instruction::Move {n_steps} => {
+                // A step at a time, to leave a trail; a blocked step stays blocked
+                for _ in 0..n_steps {
+                    let (new_row, new_col, new_direction, new_face) = map.find_next_position_face_and_direction(cursor.row, cursor.col, cursor.direction,  cursor.face, 1);
+                    cursor.row = new_row;
+                    cursor.col = new_col;
+                    cursor.face = new_face;
+                    cursor.direction = new_direction;
+                    mark(&mut trail, &cursor);
+                }
+            }
+        }
+        recorder.record(|| Frame::new(trail.clone()));
+    }
+    recorder.finish(|| Frame::new(trail.clone()));
+    debug!(face = ?cursor.face, direction = ?cursor.direction, cursor.row, cursor.col, "Final position");
+
+    let (final_row, final_col) = map.original_coordinates(cursor.row, cursor.col, cursor.face);
+
+    1_000 * (final_row + 1) + 4 * (final_col + 1) + facing(cursor.direction)
+}
+
+
+fn part_1(mut map: Map, instructions: Vec<Instruction>, recorder: &mut Recorder) -> usize {
+    let (start_row, start_col) = map.get_start_position();
+    let mut cursor = Cursor::new(start_row, start_col);
+    map.update(cursor.row, cursor.col, cursor.direction);
+    for instruction in instructions {
+        match instruction {
+            Instruction::TurnLeft => {
+                cursor.turn_left();
+                map.update(cursor.row, cursor.col, cursor.direction);
+            },
+            Instruction::TurnRight => {
+                cursor.turn_right();
+                map.update(cursor.row, cursor.col, cursor.direction);
+            },
+            Instruction::Move {n_steps} => {
+                let trail = map.find_trail(cursor.row, cursor.col, cursor.direction, n_steps);
+                let (new_row, new_col) = trail.iter().last().unwrap();
+                cursor.row = *new_row;
+                cursor.col = *new_col;
+                for (r, c) in trail {
+                    map.update(r, c, cursor.direction);
+                }
+            }
+        }
+        recorder.record(|| Frame::new(map.grid.clone()));
+    }
+    recorder.finish(|| Frame::new(map.grid.clone()));
+    1_000 * (cursor.row + 1) + 4 * (cursor.col + 1) + facing(cursor.direction)
+}
+
+#[derive(Clone)]
+pub struct Map {
+    // (row = 0, col = 0) is in upper left 
+    grid: Grid<char>,
+}
+
+impl Map {
+    fn update(&mut self, row: usize, col: usize, direction: Direction) {
+        self.grid[(row, col)] = arrow(direction)
+    }
+
+
+    fn get_start_position(&self) -> (usize, usize) {
+        (0, self.grid.row(0).iter().position(|c| *c == '.').unwrap())
+    }
+
+    fn first_col_in_row(&self, row: usize) -> usize {
+        for col in 0..self.grid.n_cols() {
+            if self.grid[(row, col)] != ' ' {
+                return col
+            }
+        }        
+        panic!("Could not find first col in row.")
+    }
+
+    fn last_col_in_row(&self, row: usize) -> usize {
+        for col in (0..self.grid.n_cols()).rev() {
+            if self.grid[(row, col)] != ' ' {
+                return col
+            }
+        }        
+        panic!("Could not find last col in row.")
+    }
+
+
+    fn first_row_in_col(&self, col: usize) -> usize {
+        for row in 0..self.grid.n_rows() {
+            if self.grid[(row, col)] != ' ' {
+                return row
+            }
+        }        
+        panic!("Could not find first row in col.")
+    }
+
+    fn last_row_in_col(&self, col: usize) -> usize {
+        for row in (0..self.grid.n_rows()).rev() {
+            if self.grid[(row, col)] != ' ' {
+                return row
+            }
+        }        
+        panic!("Could not find last row in col.")
+    }
+
+    fn next_position(&self, current_row: usize, current_col: usize, direction: Direction) -> (usize, usize) {
+        match direction {
+            Direction::Right => {
+                if current_col == self.grid.n_cols() - 1 || self.grid[(current_row, current_col + 1)] == ' ' {
+                    (current_row, self.first_col_in_row(current_row))
+                } else {
+                    (current_row, current_col + 1)
+                }
+            },
+            Direction::Left => {
+                if current_col == 0 || self.grid[(current_row, current_col - 1)] == ' ' {
+                    (current_row, self.last_col_in_row(current_row))
+                } else {
+                    (current_row, current_col - 1)
+                }
+            },
+            Direction::Up => {
+                if current_row == 0 || self.grid[(current_row - 1, current_col)] == ' ' {
+                    (self.last_row_in_col(current_col), current_col)
+                } else {
+                    (current_row - 1, current_col)
+                }
+            },
+            Direction::Down => {
+                if current_row == self.grid.n_rows() - 1 || self.grid[(current_row + 1, current_col)] == ' ' {
+                    (self.first_row_in_col(current_col), current_col)
+                } else {
+                    (current_row + 1, current_col)
+                }
+            },
+        }
+    }
+
+    fn find_trail(&self, current_row: usize, current_col: usize, direction: Direction, n_steps: usize) -> Vec<(usize, usize)> {
+        let mut trail: Vec<(usize, usize)> = vec![];
+        let (mut current_row, mut current_col) = (current_row, current_col);
+        trail.push((current_row, current_col));
+        for _ in 0..n_steps {
+            let (next_row, next_col) = self.next_position(current_row, current_col, direction);
+            if self.grid[(next_row, next_col)] == '#' {
+                return trail
+            }
+            (current_row, current_col) = (next_row, next_col);
+            trail.push((current_row, current_col));
+        }
+        trail
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Instruction {
+    TurnLeft,
+    TurnRight,
+    Move {n_steps: usize}
+}
+
+
+// How the trail is drawn, like in the puzzle text
+fn arrow(direction: Direction) -> char {
+    match direction {
+        Direction::Right => '>',
+        Direction::Left => '<',
+        Direction::Up => '^',
+        Direction::Down => 'v',
+    }
+}
+
+fn facing(direction: Direction) -> usize {
+    match direction {
+        Direction::Right => 0,
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Up => 3,
+    }
+}
+
+struct Cursor {
+    row: usize,
+    col: usize,
+    direction: Direction
+}
+
+struct Cursor2 {
+    face: Face,
+    row: usize,
+    col: usize,
+    direction: Direction
+}
+
+impl Cursor2 {
+    fn new(row: usize, col: usize) -> Self {
+        Self {row, col, direction: Direction::Right, face: Face::Top}
+    }
+
+    fn turn_left(&mut self) {
+        self.direction = self.direction.turn_left()
+    }
+
+    fn turn_right(&mut self) {
+        self.direction = self.direction.turn_right()
+    }
+}
+
+impl Cursor {
+    fn new(row: usize, col: usize) -> Self {
+        Self {row, col, direction: Direction::Right}
+    }
+
+    fn turn_left(&mut self) {
+        self.direction = self.direction.turn_left()
+    }
+
+    fn turn_right(&mut self) {
+        self.direction = self.direction.turn_right()
+    }
+}
+
+fn load_instructions(line: &mut LineParser) -> Result<Vec<Instruction>, ParseError> {
+    let mut instructions: Vec<Instruction> = vec![];
+    while !line.is_done() {
+        if line.skip("R") {
+            instructions.push(Instruction::TurnRight)
+        } else if line.skip("L") {
+            instructions.push(Instruction::TurnLeft)
+        } else {
+            let n_steps = line.number::<usize>().map_err(|_| line.error("a number of steps, L or R"))?;
+            instructions.push(Instruction::Move{n_steps})
+        }
+    }
+    Ok(instructions)
+}
+
+#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
+enum Face {
+    Top, Bottom, Left, Right, Front, Back
+}
+
+#[derive(Eq, PartialEq, Hash, Clone, Copy)]
+enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right
+}
+
+struct EdgeTransition {
+    edges: HashSet<(Face, Edge)>,
+    flip_dimensions: bool,
+    invert_coordinates: bool
+}
+
+impl EdgeTransition {
+    fn new(edge: (Face, Edge), other_edge: (Face, Edge), flip_dimensions: bool, invert_coordinates: bool ) -> Self {
+        Self {
+            edges: HashSet::from([edge, other_edge]),
+            flip_dimensions,
+            invert_coordinates
+        }
+    }
+}
+
+struct Map2 {
+    faces: HashMap<Face, Grid<char>>,
+    face_positions: HashMap<Face, (usize, usize)>,
+    edge_transitions: Vec<EdgeTransition>,
+    n_cols: usize,
+    n_rows: usize
+}
+
+impl Map2 {
+
+    fn find_next_position_face_and_direction(&self, current_row: usize, current_col: usize, current_direction: Direction, current_face: Face, n_steps: usize) -> (usize, usize, Direction, Face) {
+
+        let (mut current_row, mut current_col, mut current_direction, mut current_face) = (current_row, current_col, current_direction, current_face);
+        for _ in 0..n_steps {
+            let (next_row, next_col, next_direction, next_face) = self.next_position_face_and_direction(current_row, current_col, current_direction, current_face);
+            if self.faces.get(&next_face).unwrap()[(next_row, next_col)] == '#' {
+                return (current_row, current_col, current_direction, current_face)
+            }
+            (current_row, current_col, current_direction, current_face) = (next_row, next_col, next_direction, next_face)
+        }
+        (current_row, current_col, current_direction, current_face)
+    }
+
+    fn find_transition(&self, face: Face, edge: Edge) -> (Face, Edge, bool, bool) {
+        let transition = self.edge_transitions.iter().find(|et| et.edges.contains(&(face, edge))).unwrap();
+        let (other_face, other_edge) = transition.edges.iter().find(|e| **e != (face, edge)).unwrap();
+        (*other_face, *other_edge, transition.flip_dimensions, transition.invert_coordinates)
+    }
+
+    fn determine_next_pos_and_direction(&self, current_row: usize, current_col: usize, exit_edge: Edge, entry_edge: Edge) -> (usize, usize, Direction)  {
+        let max_coord = self.n_cols - 1;
+        match exit_edge {
+            Edge::Right => {
+                match entry_edge {
+                    Edge::Right => {
+                        (max_coord - current_row, max_coord, Direction::Left)
+                    },  
+                    Edge::Left => {
+                        (current_row, 0, Direction::Right)
+                    },  
+                    Edge::Top => {
+                        (0, max_coord - current_row, Direction::Down)
+                    },  
+                    Edge::Bottom => {
+                        (max_coord, current_row, Direction::Up)
+                    },  
+                }
+            },
+            Edge::Left => {
+                match entry_edge {
+                    Edge::Right => {
+                        (current_row, max_coord, Direction::Left)
+                    },  
+                    Edge::Left => {
+                        (max_coord - current_row, 0, Direction::Right)
+                    },  
+                    Edge::Top => {
+                        (0, current_row, Direction::Down)
+                    },  
+                    Edge::Bottom => {
+                        (max_coord, max_coord - current_row, Direction::Up)
+                    },  
+                }
+
+            },
+            Edge::Top => {
+                match entry_edge {
+                    Edge::Right => {
+                        (max_coord - current_col, max_coord, Direction::Left)
+                    },  
+                    Edge::Left => {
+                        (current_col, 0, Direction::Right)
+                    },  
+                    Edge::Top => {
+                        (0, max_coord - current_col, Direction::Down)
+                    },  
+                    Edge::Bottom => {
+                        (max_coord, current_col, Direction::Up)
+                    },  
+                }
+
+            },
+            Edge::Bottom => {
+                match entry_edge {
+                    Edge::Right => {
+                        (current_col, max_coord, Direction::Left)
+                    },  
+                    Edge::Left => {
+                        (max_coord - current_col, 0, Direction::Right)
+                    },  
+                    Edge::Top => {
+                        (0, current_col, Direction::Down)
+                    },  
+                    Edge::Bottom => {
+                        (max_coord, max_coord - current_col, Direction::Up)
+                    },  
+                }
+
+            },
+        }
+    }
+
+    fn next_position_face_and_direction(&self, current_row: usize, current_col: usize, current_direction: Direction, current_face: Face) -> (usize, usize, Direction, Face) {
+        let current_grid = self.faces.get(&current_face).unwrap();
+        match current_direction {
+            Direction::Right => {
+                if current_col == self.n_cols  - 1 || current_grid[(current_row, current_col + 1)] == ' ' {
+                    let (new_face, entry_edge, _flip_dimensions, _invert_coordinates) = self.find_transition(current_face, Edge::Right);
+                    let (new_row, new_col, new_direction) = self.determine_next_pos_and_direction(current_row, current_col, Edge::Right, entry_edge);
+                    (new_row, new_col, new_direction, new_face)
+                } else {
+                    (current_row, current_col + 1, current_direction, current_face)
+                }
+            },
+            Direction::Left => {
+                if current_col == 0 || current_grid[(current_row, current_col - 1)] == ' ' {
+                    let (new_face, entry_edge, _flip_dimensions, _invert_coordinates) = self.find_transition(current_face, Edge::Left);
+                    let (new_row, new_col, new_direction) = self.determine_next_pos_and_direction(current_row, current_col, Edge::Left, entry_edge);
+                    (new_row, new_col, new_direction, new_face)
+                } else {
+                    (current_row, current_col - 1, current_direction, current_face)
+                }
+            },
+            Direction::Up => {
+                if current_row == 0 || current_grid[(current_row - 1, current_col)] == ' ' {
+                    let (new_face, entry_edge, _flip_dimensions, _invert_coordinates) = self.find_transition(current_face, Edge::Top);
+                    let (new_row, new_col, new_direction) = self.determine_next_pos_and_direction(current_row, current_col, Edge::Top, entry_edge);
+                    (new_row, new_col, new_direction, new_face)
+                } else {
+                    (current_row - 1, current_col, current_direction, current_face)
+                }
+            },
+            Direction::Down => {
+                if current_row == self.n_rows  - 1 || current_grid[(current_row + 1, current_col)] == ' ' {
+                    let (new_face, entry_edge, _flip_dimensions, _invert_coordinates) = self.find_transition(current_face, Edge::Bottom);
+                    let (new_row, new_col, new_direction) = self.determine_next_pos_and_direction(current_row, current_col, Edge::Bottom, entry_edge);
+                    (new_row, new_col, new_direction, new_face)
+                } else {
+                    (current_row + 1, current_col, current_direction, current_face)
+                }
+            },
+        }
+
+    }
+
+    fn get_start_position(&self) -> (usize, usize) {
+        (0, self.faces.get(&Face::Top).unwrap().row(0).iter().position(|c| *c == '.').unwrap())
+    }    
+
+    // The faces put back where they are on the flat map
+    fn flat_grid(&self) -> Grid<char> {
+        let max_row = *self.face_positions.iter().map(|(_, (m_r, _))| m_r).max().unwrap();
+        let max_col = *self.face_positions.iter().map(|(_, (_, m_c))| m_c).max().unwrap();
+        let mega_col = (max_col + 1) * self.n_cols;
+        let mega_row = (max_row + 1) * self.n_rows;
+        let mut grid = Grid::new(mega_row, mega_col, ' ');
+
+        for (face, face_grid) in &self.faces {
+            let (mega_row, mega_col) = self.face_positions.get(face).unwrap();
+            let delta_row = mega_row * self.n_rows;
+            let delta_col = mega_col * self.n_cols;
+            for r in 0..self.n_rows {
+                for c in 0..self.n_cols {
+                    grid[(delta_row + r, delta_col + c)] = face_grid[(r, c)]
+                }
+            }
+        }
+        grid
+    }
+    
+    fn original_coordinates(&self, face_row: usize, face_col: usize, face: Face) -> (usize, usize) {
+        let (mega_row, mega_col) = self.face_positions.get(&face).unwrap();
+        let delta_row = mega_row * self.n_rows;
+        let delta_col = mega_col * self.n_cols;
+        (delta_row + face_row, delta_col + face_col)
+    }
+
+}
+
+fn load_map2(map: &Map) -> Map2 {
+    let n_tiles = map.grid.iter().filter(|(_, c)| **c != ' ').count();
+    let face_dim = (1..).find(|dim| 6 * dim * dim >= n_tiles).unwrap();
+    // The face layout and edge transitions are worked out by hand for the cube net
+    // of the example (4x4 faces) and the one in my input (50x50 faces)
+    let test = face_dim == 4;
+    let face_positions = if test {
+        [
+            (Face::Top,    (0, 2)),
+            (Face::Bottom, (2, 2)),
+            (Face::Left,   (1, 1)),
+            (Face::Right,  (2, 3)),
+            (Face::Front,  (1, 2)),
+            (Face::Back,   (1, 0)),        
+        ]
+    } else {
+        [
+            (Face::Top,    (0, 1)),
+            (Face::Bottom, (2, 1)),
+            (Face::Left,   (2, 0)),
+            (Face::Right,  (0, 2)),
+            (Face::Front,  (1, 1)),
+            (Face::Back,   (3, 0)),        
+        ]
+    };
+    let faces = face_positions.iter()
+    .map(|(face, (m_row, m_col))| {
+        let start_row = m_row * face_dim;
+        let start_col = m_col * face_dim;
+        let face_grid = Grid::from_fn(face_dim, face_dim, |(row, col)| map.grid[(start_row + row, start_col + col)]);
+        (*face, face_grid)
+    }).collect();
+
+    let edge_transitions = if test {
+        vec![
+            EdgeTransition::new((Face::Top, Edge::Right), (Face::Right, Edge::Right), false , true),
+            EdgeTransition::new((Face::Top, Edge::Left), (Face::Left, Edge::Top), true, false),
+            EdgeTransition::new((Face::Top, Edge::Top), (Face::Back, Edge::Top), false, true),
+            EdgeTransition::new((Face::Top, Edge::Bottom), (Face::Front, Edge::Top), false, false),
+
+            EdgeTransition::new((Face::Bottom, Edge::Right), (Face::Right, Edge::Left), false, false),
+            EdgeTransition::new((Face::Bottom, Edge::Left), (Face::Left, Edge::Bottom), true, true),
+            EdgeTransition::new((Face::Bottom, Edge::Top), (Face::Front, Edge::Bottom), false, false),
+            EdgeTransition::new((Face::Bottom, Edge::Bottom), (Face::Back, Edge::Bottom), false, true),
+
+            EdgeTransition::new((Face::Front, Edge::Right), (Face::Right, Edge::Top), true, true),
+            EdgeTransition::new((Face::Front, Edge::Left), (Face::Left, Edge::Right), false, false),
+            EdgeTransition::new((Face::Back, Edge::Left), (Face::Right, Edge::Bottom), true, true),
+            EdgeTransition::new((Face::Back, Edge::Right), (Face::Left, Edge::Left), false, false),
+        ]
+    } else {
+        vec![                                               // flip_dimensions, invert_coordinates
+            EdgeTransition::new((Face::Top, Edge::Right), (Face::Right, Edge::Left), false, false),
+            EdgeTransition::new((Face::Top, Edge::Left), (Face::Left, Edge::Left), false, true),
+            EdgeTransition::new((Face::Top, Edge::Top), (Face::Back, Edge::Left), true, false),
+            EdgeTransition::new((Face::Top, Edge::Bottom), (Face::Front, Edge::Top), false, false),
+
+            EdgeTransition::new((Face::Bottom, Edge::Right), (Face::Right, Edge::Right), false, true),
+            EdgeTransition::new((Face::Bottom, Edge::Left), (Face::Left, Edge::Right), false, false),
+            EdgeTransition::new((Face::Bottom, Edge::Top), (Face::Front, Edge::Bottom), false, false),
+            EdgeTransition::new((Face::Bottom, Edge::Bottom), (Face::Back, Edge::Right), true, false),
+
+            EdgeTransition::new((Face::Front, Edge::Right), (Face::Right, Edge::Bottom), true, false),
+            EdgeTransition::new((Face::Front, Edge::Left), (Face::Left, Edge::Top), true, false),
+            EdgeTransition::new((Face::Back, Edge::Bottom), (Face::Right, Edge::Top), false, false),
+            EdgeTransition::new((Face::Back, Edge::Top), (Face::Left, Edge::Bottom), false, false),
+        ]
+    };
+    Map2{faces, edge_transitions, face_positions: face_positions.into_iter().collect(), n_rows: face_dim, n_cols: face_dim}
+
+}
+
+fn load_map(input: &str) -> Map {
+    Map { grid: Grid::from_rows(padded_char_grid(input, ' ')) }
+}
+
+/// A path of `size` moves over a cube net with faces of 5 to 20 tiles, a tenth of them walls. The
+/// net is laid out like the real input, as part 2 only knows that layout (and the example's).
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let face_dim = rng.gen_range(5..=20);
+    let faces = [(0, 1), (0, 2), (1, 1), (2, 0), (2, 1), (3, 0)];
+    let map = generate::grid(rng, 4 * face_dim, 3 * face_dim, |rng, (row, col)| {
+        if !faces.contains(&(row / face_dim, col / face_dim)) {
+            ' '
+        } else if (row, col) != (0, face_dim) && rng.gen_ratio(1, 10) {
+            '#'
+        } else {
+            '.'
+        }
+    });
+    let rows: Vec<&str> = map.lines().map(str::trim_end).collect();
+    let mut path = String::new();
+    for ind in 0..size.max(1) {
+        if ind > 0 {
+            path.push(if rng.gen_bool(0.5) { 'R' } else { 'L' })
+        }
+        path += &rng.gen_range(1..=50).to_string()
+    }
+    format!("{}\n\n{path}\n", rows.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Walks the flat map a tile at a time, back to the far end of the row or column from past its edge
+    fn reference_part_1(input: &str) -> usize {
+        let sections = split_sections(input, 2);
+        let (map, path) = (sections[0], sections[1]);
+        let rows: Vec<Vec<char>> = map.lines().map(|line| line.chars().collect()).collect();
+        let tile = |row: isize, col: isize| rows.get(row as usize).and_then(|tiles| tiles.get(col as usize)).copied().unwrap_or(' ');
+        let deltas = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+        let (mut row, mut col) = (0, rows[0].iter().position(|c| *c == '.').unwrap() as isize);
+        let mut facing = 0;
+        for instruction in path.trim_end().split_inclusive(['L', 'R']) {
+            let (d_row, d_col) = deltas[facing];
+            for _ in 0..instruction.trim_end_matches(['L', 'R']).parse().unwrap() {
+                let (mut next_row, mut next_col) = (row + d_row, col + d_col);
+                if tile(next_row, next_col) == ' ' {
+                    (next_row, next_col) = (row, col);
+                    while tile(next_row - d_row, next_col - d_col) != ' ' {
+                        (next_row, next_col) = (next_row - d_row, next_col - d_col)
+                    }
+                }
+                if tile(next_row, next_col) == '#' {
+                    break
+                }
+                (row, col) = (next_row, next_col)
+            }
+            match instruction.chars().last() {
+                Some('R') => facing = (facing + 1) % 4,
+                Some('L') => facing = (facing + 3) % 4,
+                _ => {}
+            }
+        }
+        1_000 * (row as usize + 1) + 4 * (col as usize + 1) + facing
+    }
+
+    fn part_2_of(input: &str) -> usize {
+        let (map, instructions) = parse_input(input).unwrap();
+        part_2(load_map2(&map), instructions, &mut Recorder::off())
+    }
+
+    proptest! {
+        #[test]
+        fn part_1_matches_reference(seed in any::<u64>(), size in 1..50usize) {
+            let input = generate(&mut generate::rng(seed), size);
+            let (map, instructions) = parse_input(&input).unwrap();
+            prop_assert_eq!(part_1(map, instructions, &mut Recorder::off()), reference_part_1(&input));
+        }
+
+        // Without walls in the way, going all the way around the cube ends up where it started, and
+        // so does turning around and walking the path back
+        #[test]
+        fn walks_around_open_cube(seed in any::<u64>(), size in 1..50usize) {
+            let input = generate(&mut generate::rng(seed), size).replace('#', ".");
+            let sections = split_sections(&input, 2);
+            let (map, path) = (sections[0], sections[1]);
+            let (path, face_dim) = (path.trim_end(), map.lines().next().unwrap().len() / 3);
+            let moves: Vec<&str> = path.split(['L', 'R']).collect();
+            let turns: Vec<char> = path.chars().filter(|c| c.is_ascii_alphabetic()).collect();
+            let around = format!("{map}\n{path}R0L{}\n", 4 * face_dim);
+            prop_assert_eq!(part_2_of(&around), part_2_of(&input));
+            let mut back = String::from("RR");
+            for ind in (0..moves.len()).rev() {
+                back += moves[ind];
+                if ind > 0 {
+                    back.push(if turns[ind - 1] == 'R' { 'L' } else { 'R' })
+                }
+            }
+            let there_and_back = format!("{map}\n{path}{back}RR\n");
+            prop_assert_eq!(part_2_of(&there_and_back), 1_000 + 4 * (face_dim + 1));
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let (map, instructions) = parse_input(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_1(map, instructions, &mut Recorder::off()), 6032)
+    }
+
+    #[test]
+    fn test_part_2() {
+        let (map, instructions) = parse_input(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_2(load_map2(&map), instructions, &mut Recorder::off()), 5031)
+    }
+
+    #[test]
+    fn test_frames() {
+        let (map, instructions) = parse_input(include_str!("../test.txt")).unwrap();
+        let mut recorder = Recorder::last_only();
+        part_1(map.clone(), instructions.clone(), &mut recorder);
+        let trail = recorder.into_frames()[0].to_string();
+        // Row 6, where the path ends at column 8 facing right, as drawn in the puzzle text
+        assert_eq!(trail.lines().nth(5).unwrap().trim_end(), ">>>v...>#.>>");
+        let mut recorder = Recorder::every(1);
+        part_2(load_map2(&map), instructions.clone(), &mut recorder);
+        let frames = recorder.into_frames();
+        assert_eq!(frames.len(), instructions.len());
+        // The final position, row 5 and column 7 facing up
+        assert_eq!(frames.last().unwrap().cells[(4, 6)], '^')
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("..#\n.x.\n\n10R5\n").err().unwrap();
+        assert_eq!(err, ParseError::new(2, 2, "x.", "an open tile (.), a wall (#) or a space"));
+        let err = parse_input("...\n\n10R5X\n").err().unwrap();
+        assert_eq!(err, ParseError::new(3, 5, "X", "a number of steps, L or R"))
+    }
+
+}
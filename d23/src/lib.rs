@@ -0,0 +1,238 @@
+use aoc_common::frames::{Frame, Palette, Recorder, Visualize};
+use aoc_common::generate::{self, Rng, StdRng};
+use aoc_common::sparse_grid::Legend;
+use aoc_common::Direction;
+use aoc_common::{Answer, Day, Grid, ParseError, Part, Point2, Solution, SparseGrid, Vec2};
+use std::collections::{HashSet, HashMap};
+use tracing::trace;
+
+pub struct Day23;
+
+pub const DAY: Day = Day::new::<Day23>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR"))
+    .with_frames::<Day23>()
+    .with_generator(generate);
+
+impl Solution for Day23 {
+    type Input = SparseGrid<()>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1_and_2(input.clone(), false, &mut Recorder::off()).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_1_and_2(input.clone(), true, &mut Recorder::off()).into()
+    }
+}
+
+// A frame for every round
+impl Visualize for Day23 {
+    fn simulate(input: &Self::Input, part: Part, recorder: &mut Recorder) {
+        part_1_and_2(input.clone(), part == Part::Two, recorder);
+    }
+
+    fn palette() -> Palette {
+        Palette::new([0x0f, 0x0f, 0x23]).with('#', [0x00, 0xcc, 0x00])
+    }
+}
+
+fn draw(elves: &SparseGrid<()>) -> Frame {
+    Frame::from_sparse(elves, &Legend::new('.', "ground").with((), '#', "elf"))
+}
+
+fn part_1_and_2(input: SparseGrid<()>, is_part_2: bool, recorder: &mut Recorder) -> usize {
+    let mut elves = input.clone();
+    recorder.record(|| draw(&elves));
+    let mut proposer = Proposer::new();
+    let n_rounds = if is_part_2 {
+        usize::MAX
+    } else {
+        10
+    };
+    for round in 1..=n_rounds {
+        trace!(round, first_direction = ?proposer.first_direction, "Round");
+
+        let mut proposals: HashMap<(isize, isize), (isize, isize)> = HashMap::new(); // (proposed_pos, current_pos)
+        let mut duplicate_proposals: HashSet<(isize, isize)> = HashSet::new(); 
+        for (current_row, current_col) in elves.positions() {
+            if let Some((proposed_row, proposed_col)) = proposer.propose(current_row, current_col, &elves) {
+                if proposals.insert((proposed_row, proposed_col), (current_row, current_col)).is_some() {
+                    duplicate_proposals.insert((proposed_row, proposed_col));
+                }
+            }
+        }
+        let valid_proposals: HashMap<_, _> = proposals.into_iter()
+            .filter(|((proposed_row, proposed_col), _)| !duplicate_proposals.contains(&(*proposed_row, *proposed_col)))
+            .map(|((proposed_row, proposed_col), (current_row, current_col))| ((current_row, current_col), (proposed_row, proposed_col))).collect();
+        if is_part_2 && valid_proposals.is_empty() {
+            recorder.finish(|| draw(&elves));
+            return round
+        }
+
+        elves = elves.positions()
+            .map(|current_pos| 
+                if let Some((new_row, new_col)) = valid_proposals.get(&current_pos) {
+                    ((*new_row, *new_col), ())
+                } else {
+                    (current_pos, ())
+                }
+            )
+            .collect();
+
+        proposer.increment_first_direction();
+        recorder.record(|| draw(&elves));
+    }
+    recorder.finish(|| draw(&elves));
+    elves.area() - elves.len()
+
+}
+
+
+// The elves consider north, south, west and east in that order
+fn next_direction(direction: Direction) -> Direction {
+    match direction {
+        Direction::Up => Direction::Down,
+        Direction::Down => Direction::Left,
+        Direction::Left => Direction::Right,
+        Direction::Right => Direction::Up,
+    }
+}
+
+fn can_move(direction: Direction, row: isize, col: isize, grid: &SparseGrid<()>) -> Option<(isize, isize)> {
+    let elf = Point2::new(row, col);
+    if elf.neighbours_8().iter().any(|pos| grid.contains((*pos).into())) {
+        // The position straight ahead and the ones on either side of it
+        let ahead = elf + Vec2::from(direction);
+        let side = Vec2::from(direction).turn_left();
+        let check_positions = [ahead + side, ahead, ahead - side];
+        if check_positions.iter().any(|pos| grid.contains((*pos).into())) {
+            None
+        } else {
+            Some(ahead.into())
+        }
+    } else {
+        None
+    }
+}
+
+struct Proposer {
+    first_direction: Direction
+}
+
+impl Proposer {
+    fn new() -> Self {
+        Self {first_direction: Direction::Up}
+    }
+
+    fn increment_first_direction(&mut self) {
+        self.first_direction = next_direction(self.first_direction)
+    }
+ 
+    fn propose(&self, row: isize, col: isize, grid: &SparseGrid<()>) -> Option<(isize, isize)> {
+        let mut direction = self.first_direction;
+        for _ in 0..4 {
+            if let Some((row, col)) = can_move(direction, row, col, grid) {
+                return Some((row, col))
+            }
+            direction = next_direction(direction)
+        }
+        None
+    }
+}
+
+
+fn load_input(input: &str) -> Result<SparseGrid<()>, ParseError> {
+    let grid = Grid::parse(input, "an elf (#) or ground (.)", |c| "#.".contains(c).then_some(c))?;
+    Ok(grid
+        .iter()
+        .filter(|(_, c)| **c == '#')
+        .map(|((row, col), _)| ((row as isize, col as isize), ()))
+        .collect())
+}
+
+/// A square of `size` by `size` ground tiles, about a third of them with an elf on
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    generate::grid(rng, size.max(1), size.max(1), |rng, _| if rng.gen_ratio(1, 3) { '#' } else { '.' })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Plays rounds with the elves in a set until the first round in which none of them moves,
+    // returning the empty ground after ten rounds and that round
+    fn reference_rounds(input: &str) -> (usize, usize) {
+        let mut elves: HashSet<(isize, isize)> = input
+            .lines()
+            .enumerate()
+            .flat_map(|(row, line)| line.char_indices().filter(|(_, c)| *c == '#').map(move |(col, _)| (row as isize, col as isize)))
+            .collect();
+        // North, south, west and east, each with the three positions to check
+        let looks: [[(isize, isize); 3]; 4] = [
+            [(-1, -1), (-1, 0), (-1, 1)],
+            [(1, -1), (1, 0), (1, 1)],
+            [(-1, -1), (0, -1), (1, -1)],
+            [(-1, 1), (0, 1), (1, 1)],
+        ];
+        let empty = |elves: &HashSet<(isize, isize)>| {
+            let rows = elves.iter().map(|(row, _)| *row);
+            let cols = elves.iter().map(|(_, col)| *col);
+            let height = rows.clone().max().unwrap() - rows.min().unwrap() + 1;
+            let width = cols.clone().max().unwrap() - cols.min().unwrap() + 1;
+            (height * width) as usize - elves.len()
+        };
+        let mut empty_after_10 = 0;
+        for round in 1.. {
+            let proposals: Vec<((isize, isize), (isize, isize))> = elves
+                .iter()
+                .filter(|(row, col)| (-1..=1).any(|d_row| (-1..=1).any(|d_col| (d_row, d_col) != (0, 0) && elves.contains(&(row + d_row, col + d_col)))))
+                .filter_map(|(row, col)| {
+                    (0..4).map(|ind| looks[(round - 1 + ind) % 4])
+                        .find(|look| look.iter().all(|(d_row, d_col)| !elves.contains(&(row + d_row, col + d_col))))
+                        .map(|look| ((*row, *col), (row + look[1].0, col + look[1].1)))
+                })
+                .collect();
+            let mut moved = false;
+            for (from, to) in &proposals {
+                if proposals.iter().filter(|(_, other)| other == to).count() == 1 {
+                    elves.remove(from);
+                    elves.insert(*to);
+                    moved = true
+                }
+            }
+            if round == 10 {
+                empty_after_10 = empty(&elves)
+            }
+            if !moved {
+                // Nothing changes any more if that is before round 10
+                return (if round < 10 { empty(&elves) } else { empty_after_10 }, round)
+            }
+        }
+        unreachable!()
+    }
+
+    proptest! {
+        #[test]
+        fn rounds_match_reference(seed in any::<u64>(), size in 1..15usize) {
+            let input = generate(&mut generate::rng(seed), size);
+            prop_assume!(input.contains('#'));
+            let elves = load_input(&input).unwrap();
+            let (empty_after_10, last_round) = reference_rounds(&input);
+            prop_assert_eq!(part_1_and_2(elves.clone(), false, &mut Recorder::off()), empty_after_10);
+            prop_assert_eq!(part_1_and_2(elves, true, &mut Recorder::off()), last_round);
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1_and_2(load_input(include_str!("../test.txt")).unwrap(), false, &mut Recorder::off()), 110)
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_1_and_2(load_input(include_str!("../test.txt")).unwrap(), true, &mut Recorder::off()), 20)
+    }}
@@ -0,0 +1,307 @@
+use aoc_common::frames::{Frame, Palette, Recorder, Visualize};
+use aoc_common::generate::{self, Rng, SliceRandom, StdRng};
+use aoc_common::search::{bfs, SearchProblem};
+use aoc_common::Direction;
+use aoc_common::{Answer, Day, Grid, ParseError, Part, Point2, Solution, Vec2};
+
+pub struct Day24;
+
+pub const DAY: Day = Day::new::<Day24>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR"))
+    .with_frames::<Day24>()
+    .with_generator(generate);
+
+impl Solution for Day24 {
+    type Input = Valley;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_input(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1_and_2(input, false, &mut Recorder::off()).map_or(Answer::Missing, Answer::from)
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_1_and_2(input, true, &mut Recorder::off()).map_or(Answer::Missing, Answer::from)
+    }
+}
+
+// A frame for every minute of the quickest way through
+impl Visualize for Day24 {
+    fn simulate(input: &Self::Input, part: Part, recorder: &mut Recorder) {
+        part_1_and_2(input, part == Part::Two, recorder);
+    }
+
+    fn palette() -> Palette {
+        let palette = Palette::new([0x0f, 0x0f, 0x23]).with('#', [0x66, 0x66, 0x66]).with('E', [0xff, 0xff, 0x00]);
+        "^v<>".chars().fold(palette, |palette, blizzard| palette.with(blizzard, [0x66, 0x99, 0xff]))
+    }
+}
+
+// `None` if the blizzards block the way for good
+fn part_1_and_2(valley: &Valley, is_part_2: bool, recorder: &mut Recorder) -> Option<usize> {
+    let occupied = valley.occupied();
+    let trips = if is_part_2 {
+        vec![(valley.entrance, valley.exit), (valley.exit, valley.entrance), (valley.entrance, valley.exit)]
+    } else {
+        vec![(valley.entrance, valley.exit)]
+    };
+    let mut minute = 0;
+    let mut position = valley.entrance;
+    for (ind, (from, to)) in trips.into_iter().enumerate() {
+        let trip = Trip { valley, occupied: &occupied, from, to, start_minute: minute % occupied.len() };
+        let found = bfs(&trip).found?;
+        // A trip starts where the one before it ended
+        for (step, (position, _)) in found.path.iter().enumerate().skip(usize::from(ind > 0)) {
+            recorder.record(|| valley.draw(minute + step, *position))
+        }
+        minute += found.cost;
+        position = to
+    }
+    recorder.finish(|| valley.draw(minute, position));
+    Some(minute)
+}
+
+// Getting across the valley, starting at a given minute. The blizzards are back where they
+// started after `occupied.len()` minutes, so that is all of the time a state has to know.
+struct Trip<'a> {
+    valley: &'a Valley,
+    occupied: &'a [Grid<bool>],
+    from: (usize, usize),
+    to: (usize, usize),
+    start_minute: usize,
+}
+
+impl SearchProblem for Trip<'_> {
+    type State = ((usize, usize), usize);
+
+    fn start(&self) -> Self::State {
+        (self.from, self.start_minute)
+    }
+
+    fn successors(&self, (position, minute): &Self::State) -> Vec<(Self::State, usize)> {
+        let next_minute = (minute + 1) % self.occupied.len();
+        let occupied = &self.occupied[next_minute];
+        std::iter::once(*position)
+            .chain(occupied.neighbours_4(*position))
+            .filter(|next| self.valley.is_open(*next) && !occupied[*next])
+            .map(|next| ((next, next_minute), 1))
+            .collect()
+    }
+
+    fn is_goal(&self, (position, _): &Self::State) -> bool {
+        *position == self.to
+    }
+}
+
+struct Blizzard {
+    // (row, col)
+    position: Point2,
+    direction: Vec2,
+}
+pub struct Valley {
+    blizzards: Vec<Blizzard>,
+    n_rows: usize, // Including walls, upper left is (row= 0, col = 0)
+    n_cols: usize,
+    entrance: (usize, usize),
+    exit: (usize, usize),
+}
+
+impl Valley {
+    /// Whether the position is inside the walls, or the entrance or the exit.
+    fn is_open(&self, (row, col): (usize, usize)) -> bool {
+        (row > 0 && col > 0 && row < self.n_rows - 1 && col < self.n_cols - 1)
+            || (row, col) == self.entrance
+            || (row, col) == self.exit
+    }
+
+    /// Where `blizzard` is after `minute` minutes. A blizzard that reaches a wall comes back from the
+    /// opposite one, so the coordinates wrap around between 1 and n - 2.
+    fn blizzard_position(&self, blizzard: &Blizzard, minute: usize) -> (usize, usize) {
+        let wrap = |coord: isize, n: usize| ((coord - 1).rem_euclid(n as isize - 2) + 1) as usize;
+        let position = blizzard.position + blizzard.direction * minute as isize;
+        (wrap(position.x, self.n_rows), wrap(position.y, self.n_cols))
+    }
+
+    /// The valley as in the puzzle, with the number of blizzards where there are several, and
+    /// the expedition as `E`.
+    fn draw(&self, minute: usize, expedition: (usize, usize)) -> Frame {
+        let mut cells = Grid::from_fn(self.n_rows, self.n_cols, |pos| if self.is_open(pos) { '.' } else { '#' });
+        for blizzard in &self.blizzards {
+            let cell = &mut cells[self.blizzard_position(blizzard, minute)];
+            *cell = match *cell {
+                '.' => [(Direction::Up, '^'), (Direction::Down, 'v'), (Direction::Left, '<'), (Direction::Right, '>')]
+                    .into_iter()
+                    .find(|(direction, _)| Vec2::from(*direction) == blizzard.direction)
+                    .unwrap()
+                    .1,
+                count if count.is_ascii_digit() => char::from_digit(count.to_digit(10).unwrap() + 1, 10).unwrap(),
+                _ => '2',
+            }
+        }
+        cells[expedition] = 'E';
+        Frame::new(cells)
+    }
+
+    /// Where there are blizzards in each minute until they are back where they started.
+    fn occupied(&self) -> Vec<Grid<bool>> {
+        let (height, width) = (self.n_rows - 2, self.n_cols - 2);
+        let period = height * width / gcd(height, width);
+        (0..period)
+            .map(|minute| {
+                let mut occupied = Grid::new(self.n_rows, self.n_cols, false);
+                for blizzard in &self.blizzards {
+                    occupied[self.blizzard_position(blizzard, minute)] = true
+                }
+                occupied
+            })
+            .collect()
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn load_input(input: &str) -> Result<Valley, ParseError> {
+    let grid = Grid::parse(input, "a wall (#), open ground (.) or a blizzard (^, v, <, >)", |c| {
+        "#.^v<>".contains(c).then_some(c)
+    })?;
+    if grid.n_rows() < 3 || grid.n_cols() < 3 {
+        return Err(ParseError::new(grid.n_rows() + 1, 1, "", "a valley with walls around it"));
+    }
+    let entrance = (0, 1);
+    let n_rows = grid.n_rows();
+    let n_cols = grid.n_cols();
+    let exit = (n_rows - 1, n_cols - 2);
+    let mut blizzards: Vec<Blizzard> = vec![];
+    for ((row, col), cell) in grid.iter() {
+        let direction = match cell {
+            '^' => Direction::Up,
+            'v' => Direction::Down,
+            '<' => Direction::Left,
+            '>' => Direction::Right,
+            _ => continue,
+        };
+        blizzards.push(Blizzard {
+            position: Point2::new(row as isize, col as isize),
+            direction: direction.into(),
+        })
+    }
+    Ok(Valley {
+        blizzards,
+        n_rows,
+        n_cols,
+        entrance,
+        exit,
+    })
+}
+
+/// A valley `size` rows high and four times as wide inside its walls, about a third of it blizzards.
+/// None of them blow up or down in the columns of the entrance and the exit, as in the puzzle. The
+/// blizzards can still block the way for good in small valleys, so those are drawn until one can be
+/// crossed three times; checking large ones would take longer than drawing them.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    loop {
+        let valley = generate_valley(rng, size);
+        if size > 20 || part_1_and_2(&load_input(&valley).unwrap(), true, &mut Recorder::off()).is_some() {
+            return valley
+        }
+    }
+}
+
+fn generate_valley(rng: &mut StdRng, size: usize) -> String {
+    let (n_rows, n_cols) = (size.max(1) + 2, 4 * size.max(1) + 2);
+    generate::grid(rng, n_rows, n_cols, |rng, (row, col)| {
+        if (row, col) == (0, 1) || (row, col) == (n_rows - 1, n_cols - 2) {
+            '.'
+        } else if row == 0 || col == 0 || row == n_rows - 1 || col == n_cols - 1 {
+            '#'
+        } else if !rng.gen_ratio(1, 3) {
+            '.'
+        } else if col == 1 || col == n_cols - 2 {
+            *['<', '>'].choose(rng).unwrap()
+        } else {
+            *['^', 'v', '<', '>'].choose(rng).unwrap()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    // Moves every blizzard a minute at a time, keeping all the positions the expedition could be at
+    // until one of them is the end of the trip, for each of the trips in turn
+    fn reference_minutes(input: &str, n_trips: usize) -> usize {
+        let rows: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        let (n_rows, n_cols) = (rows.len() as isize, rows[0].len() as isize);
+        let mut blizzards: Vec<((isize, isize), (isize, isize))> = vec![];
+        for (row, line) in rows.iter().enumerate() {
+            for (col, c) in line.iter().enumerate() {
+                let direction = match c {
+                    '^' => (-1, 0),
+                    'v' => (1, 0),
+                    '<' => (0, -1),
+                    '>' => (0, 1),
+                    _ => continue,
+                };
+                blizzards.push(((row as isize, col as isize), direction))
+            }
+        }
+        let ends = [(0, 1), (n_rows - 1, n_cols - 2)];
+        let open = |(row, col): (isize, isize)| ends.contains(&(row, col)) || (row > 0 && col > 0 && row < n_rows - 1 && col < n_cols - 1);
+        let mut minute = 0;
+        for trip in 0..n_trips {
+            let (from, to) = (ends[trip % 2], ends[(trip + 1) % 2]);
+            let mut reachable = HashSet::from([from]);
+            while !reachable.contains(&to) {
+                for ((row, col), (d_row, d_col)) in &mut blizzards {
+                    *row = (*row + *d_row - 1).rem_euclid(n_rows - 2) + 1;
+                    *col = (*col + *d_col - 1).rem_euclid(n_cols - 2) + 1;
+                }
+                let blown: HashSet<(isize, isize)> = blizzards.iter().map(|(position, _)| *position).collect();
+                reachable = reachable
+                    .iter()
+                    .flat_map(|(row, col)| [(*row, *col), (row - 1, *col), (row + 1, *col), (*row, col - 1), (*row, col + 1)])
+                    .filter(|position| open(*position) && !blown.contains(position))
+                    .collect();
+                minute += 1
+            }
+        }
+        minute
+    }
+
+    proptest! {
+        #[test]
+        fn trips_match_reference(seed in any::<u64>(), size in 1..8usize) {
+            let input = generate(&mut generate::rng(seed), size);
+            let valley = load_input(&input).unwrap();
+            prop_assert_eq!(part_1_and_2(&valley, false, &mut Recorder::off()), Some(reference_minutes(&input, 1)));
+            prop_assert_eq!(part_1_and_2(&valley, true, &mut Recorder::off()), Some(reference_minutes(&input, 3)));
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1_and_2(&load_input(include_str!("../test.txt")).unwrap(), false, &mut Recorder::off()), Some(18))
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_1_and_2(&load_input(include_str!("../test.txt")).unwrap(), true, &mut Recorder::off()), Some(54))
+    }
+
+    #[test]
+    fn test_blocked_valley() {
+        // A blizzard blowing down in a valley one row high never moves
+        assert_eq!(part_1_and_2(&load_input("#.####\n#..v.#\n####.#\n").unwrap(), false, &mut Recorder::off()), None)
+    }
+
+}
@@ -0,0 +1,200 @@
+use aoc_common::generate::{self, Rng, SliceRandom, StdRng};
+use aoc_common::parse::parse_lines;
+use aoc_common::{Answer, Day, ParseError, Solution};
+
+pub struct Day25;
+
+pub const DAY: Day = Day::new::<Day25>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day25 {
+    type Input = Vec<isize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_snafu_numbers(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    // Day 25 only has one part
+}
+
+fn part_1(numbers: &[isize]) -> String {
+    decimal_to_snafu(numbers.iter().sum())
+}
+
+fn parse_snafu_numbers(input: &str) -> Result<Vec<isize>, ParseError> {
+    parse_lines(input, |line| {
+        let snafu = line.take_until(|c| !"=-012".contains(c), "a SNAFU number (=, -, 0, 1, 2)")?;
+        Ok(snafu_to_decimal(snafu))
+    })
+}
+
+fn snafu_to_decimal(snafu: &str) -> isize {
+    snafu.chars().rev().enumerate()
+        .fold(0, |acc, (ind, c)|
+        {
+            let delta = match c {
+                '=' =>   - 5isize.pow(ind as u32) * 2,
+                '-' =>  - 5isize.pow(ind as u32),
+                '0' => 0,
+                '1' => 5isize.pow(ind as u32),
+                '2' => 5isize.pow(ind as u32) * 2,
+                _ => panic!("Not a SNAFU digit.")
+            };
+            acc + delta
+        }
+    )
+}
+
+fn get_snafu_max_pow(value: isize) -> u32 {
+    let abs_value = value.abs();
+    let mut exp = 0;
+    let mut acc_pow = 0;
+    loop {
+        let pow = 5isize.pow(exp);
+        if abs_value <= 2*pow + acc_pow  {
+            return exp
+        }
+        acc_pow += 2*pow;
+        exp += 1;
+    }
+}
+
+fn deduced_pow(original_pow: u32) -> isize {
+    if original_pow == 0 {
+        0
+    } else {
+        (0..=(original_pow - 1)).rev().map(|pow| 5isize.pow(pow) * 2).sum()
+    }
+}
+
+fn decimal_to_snafu(decimal: isize) -> String {
+    let mut snafu_digits: Vec<char> = vec![];
+    let max_pow = get_snafu_max_pow(decimal);
+    let mut remaining = decimal;
+    for pow in (0..=max_pow).rev() {
+        let abs_remaining = remaining.abs();
+        let sign_remaining = remaining.signum();
+        let position_value = 5isize.pow(pow);
+        snafu_digits.push(
+            if abs_remaining < (position_value - deduced_pow(pow)) { // Måste vara så
+                '0' 
+            } else if abs_remaining < (position_value * 2 - deduced_pow(pow))  {
+                if sign_remaining < 0 {
+                    remaining += position_value;
+                    '-'
+                } else {
+                    remaining -= position_value;
+                    '1'
+                }
+            } else {
+                if sign_remaining < 0 {
+                    remaining += 2 * position_value;
+                    '='
+                } else {
+                    remaining -= 2 * position_value;
+                    '2'
+                }
+            }
+        );
+    }
+    snafu_digits.into_iter().collect::<String>()
+}
+
+
+/// `size` SNAFU numbers of up to 20 digits
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    generate::lines(rng, size.max(1), |rng, _| {
+        let mut snafu = String::from(*['1', '2'].choose(rng).unwrap());
+        for _ in 0..rng.gen_range(0..20) {
+            snafu.push(*['=', '-', '0', '1', '2'].choose(rng).unwrap())
+        }
+        snafu
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // A SNAFU number without leading zeros, as in the puzzle input
+    const SNAFU: &str = "[12][=\\-012]{0,19}";
+
+    // Plain positional notation, most significant digit first
+    fn reference_to_decimal(snafu: &str) -> isize {
+        snafu.chars().fold(0, |acc, c| acc * 5 + "=-012".find(c).unwrap() as isize - 2)
+    }
+
+    // Base 5, but digits 3 and 4 become -2 and -1 with a carry into the next digit
+    fn reference_to_snafu(mut decimal: isize) -> String {
+        let mut digits = vec![];
+        loop {
+            let digit = (decimal + 2).rem_euclid(5) - 2;
+            digits.push("=-012".chars().nth((digit + 2) as usize).unwrap());
+            decimal = (decimal - digit) / 5;
+            if decimal == 0 {
+                break;
+            }
+        }
+        digits.into_iter().rev().collect()
+    }
+
+    proptest! {
+        #[test]
+        fn snafu_round_trip(snafu in SNAFU) {
+            prop_assert_eq!(snafu_to_decimal(&snafu), reference_to_decimal(&snafu));
+            prop_assert_eq!(decimal_to_snafu(snafu_to_decimal(&snafu)), snafu);
+        }
+
+        #[test]
+        fn decimal_to_snafu_matches_reference(decimal in -1_000_000_000_000isize..1_000_000_000_000) {
+            prop_assert_eq!(decimal_to_snafu(decimal), reference_to_snafu(decimal));
+        }
+
+        #[test]
+        fn part_1_is_snafu_sum(numbers in prop::collection::vec(SNAFU, 1..30)) {
+            let input = numbers.iter().map(|snafu| format!("{snafu}\n")).collect::<String>();
+            let sum = numbers.iter().map(|snafu| reference_to_decimal(snafu)).sum();
+            prop_assert_eq!(part_1(&parse_snafu_numbers(&input).unwrap()), reference_to_snafu(sum));
+        }
+    }
+
+    #[test]
+    fn test_snafu_to_decimal() {
+        assert_eq!(snafu_to_decimal("20"), 10);
+        assert_eq!(snafu_to_decimal("1=-0-2"), 1747);
+        assert_eq!(snafu_to_decimal("12111"), 906);
+        assert_eq!(snafu_to_decimal("2=0="), 198);
+    }
+
+    #[test]
+    fn test_decimal_to_snafu() {
+        assert_eq!(decimal_to_snafu(-2), String::from("="));
+        assert_eq!(decimal_to_snafu(-1), String::from("-"));
+        assert_eq!(decimal_to_snafu(0), String::from("0"));
+        assert_eq!(decimal_to_snafu(1), String::from("1"));
+        assert_eq!(decimal_to_snafu(2), String::from("2"));
+        assert_eq!(decimal_to_snafu(snafu_to_decimal("1=")), String::from("1="));
+        assert_eq!(decimal_to_snafu(snafu_to_decimal("1-")), String::from("1-"));
+        assert_eq!(decimal_to_snafu(snafu_to_decimal("10")), String::from("10"));
+        assert_eq!(decimal_to_snafu(snafu_to_decimal("20")), String::from("20"));
+        assert_eq!(decimal_to_snafu(6), String::from("11"));
+        assert_eq!(decimal_to_snafu(snafu_to_decimal("12")), String::from("12"));
+        assert_eq!(decimal_to_snafu(11), String::from("21"));
+        assert_eq!(decimal_to_snafu(snafu_to_decimal("22")), String::from("22"));
+        assert_eq!(decimal_to_snafu(10), String::from("20"));
+        assert_eq!(decimal_to_snafu(8), String::from("2="));
+        assert_eq!(decimal_to_snafu(906), String::from("12111"));
+        assert_eq!(decimal_to_snafu(198), String::from("2=0="));
+        assert_eq!(decimal_to_snafu(1747), String::from("1=-0-2"));
+    }
+
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(&parse_snafu_numbers(include_str!("../test.txt")).unwrap()), String::from("2=-1=0"))
+    }
+}
\ No newline at end of file
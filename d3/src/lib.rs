@@ -0,0 +1,347 @@
+use aoc_common::generate::{self, Rng, SliceRandom, StdRng};
+use aoc_common::parse::parse_lines;
+use aoc_common::{Answer, Day, ParseError, Solution};
+use std::fmt;
+
+pub struct Day3;
+
+pub const DAY: Day = Day::new::<Day3>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day3 {
+    type Input = Vec<Rucksack>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, |line| {
+            let items = line.take_until(|c| !c.is_ascii_alphabetic(), "rucksack items (a-z, A-Z)")?;
+            if items.len() % 2 == 1 {
+                return Err(line.error_at(items, "an even number of items"))
+            }
+            Ok(Rucksack::new(items))
+        })
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        misplaced(input).map_or(Answer::Missing, |items| sum_of_priorities(&items).into())
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        badges(input, 3, 3).map_or(Answer::Missing, |badges| sum_of_priorities(&badges).into())
+    }
+}
+
+trait Priority {
+    fn priority(&self) -> usize;
+
+    /// The item type of a priority, 1 to 52.
+    fn from_priority(priority: usize) -> Self;
+}
+
+impl Priority for char {
+    fn priority(&self) -> usize {
+        if *self <= 'Z' {
+            *self as usize - 'A' as usize + 27
+        } else {
+            *self as usize - 'a' as usize + 1
+        }
+    }
+
+    fn from_priority(priority: usize) -> Self {
+        if priority > 26 {
+            (b'A' + (priority - 27) as u8) as char
+        } else {
+            (b'a' + (priority - 1) as u8) as char
+        }
+    }
+}
+
+/// A set of item types, as a bit per priority.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Items(u64);
+
+impl Items {
+    pub fn intersection(self, other: Items) -> Items {
+        Items(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Items) -> Items {
+        Items(self.0 | other.0)
+    }
+
+    pub fn contains(self, item: char) -> bool {
+        self.0 & 1 << item.priority() != 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The item types, by priority.
+    pub fn iter(self) -> impl Iterator<Item = char> {
+        (1..=52).filter(move |priority| self.0 & 1 << priority != 0).map(char::from_priority)
+    }
+
+    fn priorities(self) -> usize {
+        self.iter().map(|item| item.priority()).sum()
+    }
+}
+
+impl FromIterator<char> for Items {
+    fn from_iter<I: IntoIterator<Item = char>>(items: I) -> Self {
+        Items(items.into_iter().fold(0, |mask, item| mask | 1 << item.priority()))
+    }
+}
+
+impl fmt::Display for Items {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.iter().try_for_each(|item| write!(f, "{item}"))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rucksack {
+    compartments: [Items; 2],
+}
+
+impl Rucksack {
+    /// `items` are letters, as many for each compartment.
+    pub fn new(items: &str) -> Self {
+        let (first, second) = items.split_at(items.len() / 2);
+        Rucksack { compartments: [first.chars().collect(), second.chars().collect()] }
+    }
+
+    pub fn items(&self) -> Items {
+        self.compartments[0].union(self.compartments[1])
+    }
+
+    /// The item types packed in both compartments.
+    pub fn misplaced(&self) -> Items {
+        self.compartments[0].intersection(self.compartments[1])
+    }
+}
+
+/// What the elves can't have meant, with the index of the rucksack it is about (the first of the
+/// group for the groups).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RucksackError {
+    NothingMisplaced { rucksack: usize },
+    NoBadge { rucksack: usize },
+    IncompleteGroup { rucksack: usize, size: usize },
+}
+
+impl RucksackError {
+    pub fn rucksack(&self) -> usize {
+        match *self {
+            RucksackError::NothingMisplaced { rucksack } | RucksackError::NoBadge { rucksack } | RucksackError::IncompleteGroup { rucksack, .. } => rucksack,
+        }
+    }
+}
+
+impl fmt::Display for RucksackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RucksackError::NothingMisplaced { .. } => write!(f, "an item type in both compartments"),
+            RucksackError::NoBadge { .. } => write!(f, "a group with an item type in common"),
+            RucksackError::IncompleteGroup { size, .. } => write!(f, "rucksacks in groups of {size}"),
+        }
+    }
+}
+
+impl std::error::Error for RucksackError {}
+
+/// The item types in both compartments of each rucksack.
+pub fn misplaced(rucksacks: &[Rucksack]) -> Result<Vec<Items>, RucksackError> {
+    rucksacks
+        .iter()
+        .enumerate()
+        .map(|(ind, rucksack)| Some(rucksack.misplaced()).filter(|items| !items.is_empty()).ok_or(RucksackError::NothingMisplaced { rucksack: ind }))
+        .collect()
+}
+
+/// The badges of each group of `group_size` rucksacks: the item types that at least `at_least` of
+/// them carry (all of them for the puzzle).
+pub fn badges(rucksacks: &[Rucksack], group_size: usize, at_least: usize) -> Result<Vec<Items>, RucksackError> {
+    rucksacks
+        .chunks(group_size)
+        .enumerate()
+        .map(|(ind, group)| {
+            let rucksack = ind * group_size;
+            if group.len() < group_size {
+                return Err(RucksackError::IncompleteGroup { rucksack, size: group_size })
+            }
+            let badges = common_to_at_least(group.iter().map(Rucksack::items), at_least);
+            Some(badges).filter(|badges| !badges.is_empty()).ok_or(RucksackError::NoBadge { rucksack })
+        })
+        .collect()
+}
+
+// The item types in at least `at_least` of the sets. `counts[k]` holds the item types seen in at
+// least k + 1 of the sets so far, so each set moves its item types up a count, like adding a bit
+// to a binary counter per item type but without needing more than `at_least` levels.
+fn common_to_at_least(sets: impl Iterator<Item = Items>, at_least: usize) -> Items {
+    if at_least == 0 {
+        return Items((1..=52).fold(0, |mask, priority| mask | 1 << priority))
+    }
+    let mut counts = vec![Items::default(); at_least];
+    for items in sets {
+        for level in (1..at_least).rev() {
+            counts[level] = counts[level].union(counts[level - 1].intersection(items))
+        }
+        counts[0] = counts[0].union(items)
+    }
+    counts[at_least - 1]
+}
+
+fn sum_of_priorities(items: &[Items]) -> usize {
+    items.iter().map(|items| items.priorities()).sum()
+}
+
+/// `size` rucksacks, rounded up to whole groups of three. Each rucksack has exactly one item type in
+/// both compartments, and each group one badge, so the rest of the item types of the three
+/// rucksacks in a group (and of the two compartments) come from separate parts of the alphabet.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut item_types: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    generate::lines(rng, size.div_ceil(3) * 3, |rng, ind| {
+        if ind.is_multiple_of(3) {
+            item_types.shuffle(rng)
+        }
+        let badge = item_types[0];
+        let own = &item_types[1 + 17 * (ind % 3)..][..17];
+        let half_length = rng.gen_range(2..=12);
+        let mut compartments = [vec![own[0]], vec![own[0]]];
+        compartments[rng.gen_range(0..2)].push(badge);
+        for (compartment, item_types) in compartments.iter_mut().zip([&own[1..9], &own[9..]]) {
+            while compartment.len() < half_length {
+                compartment.push(*item_types.choose(rng).unwrap())
+            }
+            compartment.shuffle(rng)
+        }
+        compartments.concat().into_iter().collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    // Rucksacks of 2 to 20 items from a few item types, so that they share some
+    fn rucksacks() -> impl Strategy<Value = Vec<String>> {
+        prop::collection::vec((1..=10usize).prop_flat_map(|half| prop::collection::vec(prop::sample::select(b"abcdeXYZ".to_vec()), 2 * half)), 0..12)
+            .prop_map(|rucksacks| rucksacks.into_iter().map(|items| String::from_utf8(items).unwrap()).collect())
+    }
+
+    fn item_set(items: &str) -> HashSet<char> {
+        items.chars().collect()
+    }
+
+    fn as_set(items: Items) -> HashSet<char> {
+        items.iter().collect()
+    }
+
+    proptest! {
+        #[test]
+        fn misplaced_matches_hash_sets(rucksacks in rucksacks()) {
+            let packed: Vec<Rucksack> = rucksacks.iter().map(|items| Rucksack::new(items)).collect();
+            let reference: Vec<HashSet<char>> = rucksacks
+                .iter()
+                .map(|items| {
+                    let (first, second) = items.split_at(items.len() / 2);
+                    &item_set(first) & &item_set(second)
+                })
+                .collect();
+            match reference.iter().position(HashSet::is_empty) {
+                Some(rucksack) => prop_assert_eq!(misplaced(&packed), Err(RucksackError::NothingMisplaced { rucksack })),
+                None => prop_assert_eq!(misplaced(&packed).unwrap().into_iter().map(as_set).collect::<Vec<_>>(), reference),
+            }
+        }
+
+        #[test]
+        fn badges_match_counting(rucksacks in rucksacks(), group_size in 1..5usize, at_least in 1..5usize) {
+            let at_least = at_least.min(group_size);
+            let packed: Vec<Rucksack> = rucksacks.iter().map(|items| Rucksack::new(items)).collect();
+            let reference: Result<Vec<HashSet<char>>, RucksackError> = rucksacks
+                .chunks(group_size)
+                .enumerate()
+                .map(|(ind, group)| {
+                    let rucksack = ind * group_size;
+                    if group.len() < group_size {
+                        return Err(RucksackError::IncompleteGroup { rucksack, size: group_size })
+                    }
+                    let common: HashSet<char> = item_set("abcdeXYZ")
+                        .into_iter()
+                        .filter(|item| group.iter().filter(|items| items.contains(*item)).count() >= at_least)
+                        .collect();
+                    Some(common).filter(|common| !common.is_empty()).ok_or(RucksackError::NoBadge { rucksack })
+                })
+                .collect();
+            prop_assert_eq!(badges(&packed, group_size, at_least).map(|badges| badges.into_iter().map(as_set).collect()), reference);
+        }
+
+        #[test]
+        fn generated_rucksacks_parse(seed in any::<u64>(), size in 0..30usize) {
+            let input = generate(&mut generate::rng(seed), size);
+            let rucksacks = Day3::parse(&input).unwrap();
+            // Exactly one item type out of place and one badge, as the generator promises
+            prop_assert!(misplaced(&rucksacks).unwrap().iter().all(|items| items.len() == 1));
+            prop_assert!(badges(&rucksacks, 3, 3).unwrap().iter().all(|badges| badges.len() == 1));
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let input = Day3::parse(include_str!("../test.txt")).unwrap();
+        assert_eq!(Day3::part_1(&input), Answer::Number(157))
+    }
+
+    #[test]
+    fn test_part_2() {
+        let input = Day3::parse(include_str!("../test.txt")).unwrap();
+        assert_eq!(Day3::part_2(&input), Answer::Number(70))
+    }
+
+    #[test]
+    fn test_items() {
+        assert!((1..=52).all(|priority| char::from_priority(priority).priority() == priority));
+        let items: Items = "vJrwpWtwJgWrhcsFMMfFFhFp".chars().collect();
+        assert_eq!(items.to_string(), "cfghprstvwFJMW");
+        assert!(items.contains('J') && !items.contains('j'));
+        assert_eq!(Rucksack::new("vJrwpWtwJgWrhcsFMMfFFhFp").misplaced().to_string(), "p")
+    }
+
+    #[test]
+    fn test_badges() {
+        let rucksacks = Day3::parse(include_str!("../test.txt")).unwrap();
+        let badges = |group_size, at_least| -> Vec<String> {
+            super::badges(&rucksacks, group_size, at_least).unwrap().iter().map(Items::to_string).collect()
+        };
+        assert_eq!(badges(3, 3), vec!["r", "Z"]);
+        assert_eq!(badges(2, 2), vec!["frsFM", "qvwBT", "GJZ"]);
+        assert_eq!(super::badges(&rucksacks, 6, 6), Err(RucksackError::NoBadge { rucksack: 0 }));
+        // Carried by at least two of the three
+        assert_eq!(badges(3, 2), vec!["fgqrsvwzFMW", "cwGJLMQTZ"]);
+        assert_eq!(badges(1, 1), rucksacks.iter().map(|rucksack| rucksack.items().to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_errors() {
+        let rucksacks = [Rucksack::new("abcd"), Rucksack::new("aBcA"), Rucksack::new("ABCA")];
+        assert_eq!(misplaced(&rucksacks), Err(RucksackError::NothingMisplaced { rucksack: 0 }));
+        assert_eq!(badges(&rucksacks, 2, 2), Err(RucksackError::IncompleteGroup { rucksack: 2, size: 2 }));
+        assert_eq!(badges(&rucksacks[1..], 2, 2).unwrap(), vec!["AB".chars().collect()]);
+        assert_eq!(badges(&rucksacks, 3, 3), Err(RucksackError::NoBadge { rucksack: 0 }));
+        // Each part only needs what it looks for
+        let rucksacks = Day3::parse("abca\nabcd\nabad").unwrap();
+        assert_eq!((Day3::part_1(&rucksacks), Day3::part_2(&rucksacks)), (Answer::Missing, Answer::Number(3)));
+        let rucksacks = Day3::parse("abca\nbcdb\nefge").unwrap();
+        assert_eq!((Day3::part_1(&rucksacks), Day3::part_2(&rucksacks)), (Answer::Number(8), Answer::Missing));
+        let rucksacks = Day3::parse("abca\nabca").unwrap();
+        assert_eq!((Day3::part_1(&rucksacks), Day3::part_2(&rucksacks)), (Answer::Number(2), Answer::Missing));
+        assert!(Day3::parse("abc\nabca\nabca").is_err());
+        assert!(Day3::parse("ab1a\nabca\nabca").is_err())
+    }
+}
\ No newline at end of file
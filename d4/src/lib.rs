@@ -0,0 +1,205 @@
+use aoc_common::args::{RunArgs, UsageError, USAGE};
+use aoc_common::generate::{self, Rng, StdRng};
+use aoc_common::parse::{parse_lines, LineParser};
+use aoc_common::runner::read_input;
+use aoc_common::{Answer, Day, ParseError, Solution, Stats};
+use std::fmt::Write as _;
+use std::io::{self, Write as _};
+use std::path::Path;
+use std::process;
+
+pub mod sections;
+
+pub use sections::{audit, Assignment, Audit, SectionSet};
+
+pub struct Day4;
+
+pub const DAY: Day = Day::new::<Day4>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR"))
+    .with_stats::<Day4>()
+    .with_generator(generate);
+
+impl Solution for Day4 {
+    type Input = Vec<Pair>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_pairs(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input).into()
+    }
+}
+
+// The whole roster, not just the pairs
+impl Stats for Day4 {
+    fn stats(input: &Self::Input) -> Vec<(&'static str, String)> {
+        let assignments = assignments(input);
+        let audit = audit(&assignments);
+        let covered: SectionSet = assignments.iter().copied().collect();
+        vec![
+            ("elves", assignments.len().to_string()),
+            ("pairs of elves with overlapping assignments", audit.overlapping.len().to_string()),
+            ("sections assigned", covered.len().to_string()),
+            ("most elves on a section", format!("{} ({})", audit.max_coverage, audit.most_covered)),
+            ("sections nobody cleans", counted(&audit.uncovered)),
+        ]
+    }
+}
+
+fn part_1(pairs: &[Pair]) -> usize {
+    pairs
+        .iter()
+        .filter(|p| p.fully_overlapping())
+        .count()
+}
+
+fn part_2(pairs: &[Pair]) -> usize {
+    pairs
+        .iter()
+        .filter(|p| p.overlapping())
+        .count()
+}
+
+fn parse_pairs(input: &str) -> Result<Vec<Pair>, ParseError> {
+    parse_lines(input, Pair::parse)
+}
+
+// Both elves of each pair, in order
+fn assignments(pairs: &[Pair]) -> Vec<Assignment> {
+    pairs.iter().flat_map(|pair| [pair.a, pair.b]).collect()
+}
+
+// Assignment `ind` of the roster as the line of its pair, and a or b for the elf in it
+fn elf(ind: usize) -> String {
+    format!("{}{}", ind / 2 + 1, if ind.is_multiple_of(2) { 'a' } else { 'b' })
+}
+
+// How many sections there are in the set, and which if any
+fn counted(sections: &SectionSet) -> String {
+    if sections.is_empty() {
+        String::from("0")
+    } else {
+        format!("{} ({sections})", sections.len())
+    }
+}
+
+/// The audit of the whole roster, with every overlapping pair of elves last as there may be many.
+pub fn audit_report(pairs: &[Pair]) -> String {
+    let assignments = assignments(pairs);
+    let audit = audit(&assignments);
+    let covered: SectionSet = assignments.iter().copied().collect();
+    let mut report = String::new();
+    writeln!(report, "{} elves on {} sections: {covered}", assignments.len(), covered.len()).unwrap();
+    writeln!(report, "At most {} elves on the same section: {}", audit.max_coverage, audit.most_covered).unwrap();
+    writeln!(report, "Sections nobody cleans: {}", counted(&audit.uncovered)).unwrap();
+    writeln!(report, "{} pairs of elves with overlapping assignments:", audit.overlapping.len()).unwrap();
+    for (a, b) in audit.overlapping {
+        writeln!(report, "{} ({}) and {} ({})", elf(a), assignments[a], elf(b), assignments[b]).unwrap();
+    }
+    report
+}
+
+/// `d4 --audit ...`: audits the whole roster instead of the pairs.
+pub fn audit_main(args: Vec<String>) {
+    let program = std::env::args().next().unwrap_or_else(|| String::from(DAY.name));
+    let run_args = RunArgs::parse(args.into_iter().filter(|arg| arg != "--audit")).unwrap_or_else(|err: UsageError| {
+        eprintln!("{err}\nUsage: {program} --audit {USAGE}");
+        process::exit(2)
+    });
+    let input = read_input(&DAY, &run_args);
+    let pairs = parse_pairs(&input).unwrap_or_else(|err| {
+        eprintln!("Could not parse {}: {err}", run_args.input.name(Path::new(DAY.dir)));
+        process::exit(1)
+    });
+    // Fine if whoever reads it has stopped, e.g. `head`
+    let _ = write!(io::stdout().lock(), "{}", audit_report(&pairs));
+}
+
+pub struct Pair {
+    a: Assignment,
+    b: Assignment,
+}
+
+impl Pair {
+    fn parse(line: &mut LineParser) -> Result<Pair, ParseError> {
+        let a = Assignment::parse(line)?;
+        line.literal(",")?;
+        let b = Assignment::parse(line)?;
+        Ok(Pair { a, b })
+    }
+
+    fn fully_overlapping(&self) -> bool {
+        self.a.contains(&self.b) || self.b.contains(&self.a)
+    }
+
+    fn partially_overlapping(&self) -> bool {
+        self.a.partially_overlapping(&self.b)
+    }
+
+    fn overlapping(&self) -> bool {
+        self.fully_overlapping() || self.partially_overlapping()
+    }
+}
+
+/// `size` pairs of elves
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    generate::lines(rng, size, |rng, _| format!("{},{}", generate_sections(rng), generate_sections(rng)))
+}
+
+fn generate_sections(rng: &mut StdRng) -> String {
+    let (first, second) = (rng.gen_range(1..=99), rng.gen_range(1..=99));
+    format!("{}-{}", first.min(second), first.max(second))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    fn pairs() -> impl Strategy<Value = Vec<((usize, usize), (usize, usize))>> {
+        let assignment = (1..100usize, 0..20usize).prop_map(|(min, len)| (min, min + len));
+        prop::collection::vec((assignment.clone(), assignment), 0..50)
+    }
+
+    proptest! {
+        #[test]
+        fn parts_match_section_sets(pairs in pairs()) {
+            let input: String = pairs.iter().map(|((a_min, a_max), (b_min, b_max))| format!("{a_min}-{a_max},{b_min}-{b_max}\n")).collect();
+            let parsed = parse_pairs(&input).unwrap();
+            let sets: Vec<(HashSet<usize>, HashSet<usize>)> =
+                pairs.iter().map(|(a, b)| ((a.0..=a.1).collect(), (b.0..=b.1).collect())).collect();
+            let n_contained = sets.iter().filter(|(a, b)| a.is_subset(b) || b.is_subset(a)).count();
+            let n_overlapping = sets.iter().filter(|(a, b)| !a.is_disjoint(b)).count();
+            prop_assert_eq!(part_1(&parsed), n_contained);
+            prop_assert_eq!(part_2(&parsed), n_overlapping);
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(&parse_pairs(include_str!("../test.txt")).unwrap()), 2)
+    }
+
+    #[test]
+    fn test_part_2() {
+        assert_eq!(part_2(&parse_pairs(include_str!("../test.txt")).unwrap()), 4)
+    }
+
+    #[test]
+    fn test_audit_report() {
+        let report = audit_report(&parse_pairs(include_str!("../test.txt")).unwrap());
+        let expected = "\
+12 elves on 8 sections: 2-9
+At most 8 elves on the same section: 6-6
+Sections nobody cleans: 0
+";
+        assert!(report.starts_with(expected), "{report}");
+        assert!(report.contains("\n4a (2-8) and 4b (3-7)\n"));
+        assert_eq!(Day4::parse("5-3,1-2").err().map(|err| err.to_string()), Some(String::from("line 1, column 3: expected a last section of at least 5, found \"3\"")))
+    }
+}
@@ -0,0 +1,306 @@
+use aoc_common::parse::{LineParser, ParseError};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// The sections `min` to `max`, both included, that an elf has to clean.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
+pub struct Assignment {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Assignment {
+    pub fn new(min: usize, max: usize) -> Self {
+        assert!(min <= max, "an assignment of {min}-{max}");
+        Assignment { min, max }
+    }
+
+    pub(crate) fn parse(line: &mut LineParser) -> Result<Assignment, ParseError> {
+        let min = line.number::<usize>()?;
+        line.literal("-")?;
+        let column = line.column();
+        let max = line.number::<usize>()?;
+        if max < min {
+            return Err(ParseError::new(line.line(), column, &max.to_string(), &format!("a last section of at least {min}")))
+        }
+        Ok(Assignment { min, max })
+    }
+
+    pub fn contains(&self, other: &Assignment) -> bool {
+        other.min >= self.min && other.max <= self.max
+    }
+
+    pub fn as_range(&self) -> RangeInclusive<usize> {
+        RangeInclusive::new(self.min, self.max)
+    }
+
+    pub(crate) fn partially_overlapping(&self, other: &Assignment) -> bool {
+        self.as_range().contains(&other.min) || self.as_range().contains(&other.max)
+    }
+
+    /// The sections in both, if any.
+    pub fn intersection(&self, other: &Assignment) -> Option<Assignment> {
+        let (min, max) = (self.min.max(other.min), self.max.min(other.max));
+        (min <= max).then_some(Assignment { min, max })
+    }
+
+    pub fn n_sections(&self) -> usize {
+        self.max - self.min + 1
+    }
+}
+
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.min, self.max)
+    }
+}
+
+/// Any set of sections, as the assignments covering them: sorted, and with a gap between each two.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct SectionSet(Vec<Assignment>);
+
+impl SectionSet {
+    pub fn new() -> Self {
+        SectionSet(vec![])
+    }
+
+    pub fn assignments(&self) -> &[Assignment] {
+        &self.0
+    }
+
+    /// The number of sections in the set, its coverage.
+    pub fn len(&self) -> usize {
+        self.0.iter().map(Assignment::n_sections).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, section: usize) -> bool {
+        // The last assignment starting at or before the section
+        let ind = self.0.partition_point(|assignment| assignment.min <= section);
+        ind > 0 && self.0[ind - 1].max >= section
+    }
+
+    pub fn union(&self, other: &SectionSet) -> SectionSet {
+        self.0.iter().chain(&other.0).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &SectionSet) -> SectionSet {
+        let mut sections = vec![];
+        let (mut ind, mut other_ind) = (0, 0);
+        while ind < self.0.len() && other_ind < other.0.len() {
+            let (assignment, other_assignment) = (self.0[ind], other.0[other_ind]);
+            sections.extend(assignment.intersection(&other_assignment));
+            // Whichever ends first can't meet anything further on in the other set
+            if assignment.max < other_assignment.max {
+                ind += 1
+            } else {
+                other_ind += 1
+            }
+        }
+        SectionSet(sections)
+    }
+
+    /// The sections in this set but not in `other`.
+    pub fn difference(&self, other: &SectionSet) -> SectionSet {
+        let mut sections = vec![];
+        let mut first_other = 0;
+        for assignment in &self.0 {
+            while first_other < other.0.len() && other.0[first_other].max < assignment.min {
+                first_other += 1
+            }
+            // What is left of the assignment starts at `min`, after the ones of `other` so far
+            let mut min = assignment.min;
+            for other_assignment in other.0[first_other..].iter().take_while(|other_assignment| other_assignment.min <= assignment.max) {
+                if other_assignment.min > min {
+                    sections.push(Assignment { min, max: other_assignment.min - 1 })
+                }
+                min = min.max(other_assignment.max.saturating_add(1))
+            }
+            if min <= assignment.max {
+                sections.push(Assignment { min, max: assignment.max })
+            }
+        }
+        SectionSet(sections)
+    }
+}
+
+impl From<Assignment> for SectionSet {
+    fn from(assignment: Assignment) -> Self {
+        SectionSet(vec![assignment])
+    }
+}
+
+impl FromIterator<Assignment> for SectionSet {
+    fn from_iter<I: IntoIterator<Item = Assignment>>(assignments: I) -> Self {
+        let mut assignments: Vec<Assignment> = assignments.into_iter().collect();
+        assignments.sort_unstable();
+        let mut sections: Vec<Assignment> = vec![];
+        for assignment in assignments {
+            match sections.last_mut() {
+                // Overlapping or right next to each other
+                Some(last) if assignment.min <= last.max.saturating_add(1) => last.max = last.max.max(assignment.max),
+                _ => sections.push(assignment),
+            }
+        }
+        SectionSet(sections)
+    }
+}
+
+impl fmt::Display for SectionSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (ind, assignment) in self.0.iter().enumerate() {
+            if ind > 0 {
+                write!(f, ", ")?
+            }
+            write!(f, "{assignment}")?
+        }
+        Ok(())
+    }
+}
+
+/// What a sweep over all assignments of a roster found.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Audit {
+    /// The indices of every two assignments sharing a section, in order
+    pub overlapping: Vec<(usize, usize)>,
+    /// The most assignments covering a single section
+    pub max_coverage: usize,
+    /// The sections covered by that many
+    pub most_covered: SectionSet,
+    /// The sections between the first and the last assigned one that nobody covers
+    pub uncovered: SectionSet,
+}
+
+/// Sweeps over the sections from the first to the last assigned one, keeping track of the
+/// assignments covering the current section.
+pub fn audit(assignments: &[Assignment]) -> Audit {
+    // Where each assignment starts and where it no longer covers anything, ends first when both
+    // happen at the same section
+    let mut events: Vec<(usize, bool, usize)> = assignments
+        .iter()
+        .enumerate()
+        .flat_map(|(ind, assignment)| [(assignment.min, true, ind), (assignment.max.saturating_add(1), false, ind)])
+        .collect();
+    events.sort_unstable();
+    let mut audit = Audit::default();
+    let (mut most_covered, mut uncovered) = (vec![], vec![]);
+    let mut active = BTreeSet::new();
+    let mut events = events.into_iter().peekable();
+    while let Some((section, starts, ind)) = events.next() {
+        if starts {
+            audit.overlapping.extend(active.iter().map(|&other| (ind.min(other), ind.max(other))));
+            active.insert(ind);
+        } else {
+            active.remove(&ind);
+        }
+        // The coverage holds up to the next event at another section
+        let Some(&(next_section, _, _)) = events.peek() else { break };
+        if next_section == section {
+            continue
+        }
+        let covered = Assignment { min: section, max: next_section - 1 };
+        if active.is_empty() {
+            uncovered.push(covered)
+        } else if active.len() > audit.max_coverage {
+            audit.max_coverage = active.len();
+            most_covered = vec![covered]
+        } else if active.len() == audit.max_coverage {
+            most_covered.push(covered)
+        }
+    }
+    audit.overlapping.sort_unstable();
+    audit.most_covered = most_covered.into_iter().collect();
+    audit.uncovered = SectionSet(uncovered);
+    audit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    fn set(assignments: &[(usize, usize)]) -> SectionSet {
+        assignments.iter().map(|&(min, max)| Assignment::new(min, max)).collect()
+    }
+
+    #[test]
+    fn test_section_set() {
+        let a = set(&[(8, 10), (2, 4), (3, 5), (6, 6)]);
+        assert_eq!(a.to_string(), "2-6, 8-10");
+        assert_eq!(a.len(), 8);
+        assert!(a.contains(6) && !a.contains(7) && a.contains(10) && !a.contains(1));
+        let b = set(&[(5, 8), (12, 12)]);
+        assert_eq!(a.union(&b).to_string(), "2-10, 12-12");
+        assert_eq!(a.intersection(&b).to_string(), "5-6, 8-8");
+        assert_eq!(a.difference(&b).to_string(), "2-4, 9-10");
+        assert_eq!(b.difference(&a).to_string(), "7-7, 12-12");
+        assert!(a.difference(&a).is_empty())
+    }
+
+    #[test]
+    fn test_audit() {
+        let assignments: Vec<Assignment> = [(2, 4), (6, 8), (2, 3), (4, 5), (5, 7), (7, 9), (12, 14)]
+            .iter()
+            .map(|&(min, max)| Assignment::new(min, max))
+            .collect();
+        let audit = audit(&assignments);
+        assert_eq!(audit.overlapping, vec![(0, 2), (0, 3), (1, 4), (1, 5), (3, 4), (4, 5)]);
+        assert_eq!(audit.max_coverage, 3);
+        assert_eq!(audit.most_covered.to_string(), "7-7");
+        assert_eq!(audit.uncovered.to_string(), "10-11");
+        assert_eq!(super::audit(&[]), Audit::default())
+    }
+
+    fn sections(set: &SectionSet) -> HashSet<usize> {
+        set.assignments().iter().flat_map(Assignment::as_range).collect()
+    }
+
+    fn assignments() -> impl Strategy<Value = Vec<Assignment>> {
+        let assignment = (1..60usize, 0..10usize).prop_map(|(min, len)| Assignment::new(min, min + len));
+        prop::collection::vec(assignment, 0..12)
+    }
+
+    proptest! {
+        #[test]
+        fn set_operations_match_hash_sets(a in assignments(), b in assignments()) {
+            let (a, b): (SectionSet, SectionSet) = (a.into_iter().collect(), b.into_iter().collect());
+            let (a_sections, b_sections) = (sections(&a), sections(&b));
+            for result in [a.union(&b), a.intersection(&b), a.difference(&b)] {
+                // Sorted with gaps in between, so that equal sets look the same
+                prop_assert!(result.assignments().windows(2).all(|pair| pair[0].max + 1 < pair[1].min));
+            }
+            prop_assert_eq!(sections(&a.union(&b)), &a_sections | &b_sections);
+            prop_assert_eq!(sections(&a.intersection(&b)), &a_sections & &b_sections);
+            prop_assert_eq!(sections(&a.difference(&b)), &a_sections - &b_sections);
+            prop_assert_eq!(a.len(), a_sections.len());
+        }
+
+        #[test]
+        fn audit_matches_counting(assignments in assignments()) {
+            let audit = audit(&assignments);
+            let mut overlapping = vec![];
+            for (ind, a) in assignments.iter().enumerate() {
+                for (other, b) in assignments.iter().enumerate().skip(ind + 1) {
+                    if a.intersection(b).is_some() {
+                        overlapping.push((ind, other))
+                    }
+                }
+            }
+            prop_assert_eq!(audit.overlapping, overlapping);
+            let coverage = |section| assignments.iter().filter(|assignment| assignment.as_range().contains(&section)).count();
+            let all: Vec<usize> = (1..70).collect();
+            let max_coverage = all.iter().map(|&section| coverage(section)).max().unwrap();
+            prop_assert_eq!(audit.max_coverage, max_coverage);
+            let most_covered: HashSet<usize> = all.iter().copied().filter(|&section| max_coverage > 0 && coverage(section) == max_coverage).collect();
+            prop_assert_eq!(sections(&audit.most_covered), most_covered);
+            let span = assignments.iter().map(|assignment| assignment.min).min().zip(assignments.iter().map(|assignment| assignment.max).max());
+            let uncovered: HashSet<usize> = span.map_or(HashSet::new(), |(min, max)| (min..=max).filter(|&section| coverage(section) == 0).collect());
+            prop_assert_eq!(sections(&audit.uncovered), uncovered);
+        }
+    }
+}
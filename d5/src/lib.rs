@@ -0,0 +1,269 @@
+use aoc_common::generate::{Rng, StdRng};
+use aoc_common::input_store::split_sections;
+use aoc_common::parse::{LineParser, Lines};
+use aoc_common::{Answer, Day, ParseError, Solution};
+
+pub struct Day5;
+
+pub const DAY: Day = Day::new::<Day5>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR"))
+    .with_sections(&["stacks", "moves"])
+    .with_generator(generate);
+
+impl Solution for Day5 {
+    type Input = (Vec<Vec<char>>, Vec<(usize, usize, usize)>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1((stacks, moves): &Self::Input) -> Answer {
+        rearrange(stacks.clone(), moves, false).map_or(Answer::Missing, Answer::from)
+    }
+
+    fn part_2((stacks, moves): &Self::Input) -> Answer {
+        rearrange(stacks.clone(), moves, true).map_or(Answer::Missing, Answer::from)
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn parse_input(input: &str) -> Result<(Vec<Vec<char>>, Vec<(usize, usize, usize)>), ParseError> {
+    let sections = split_sections(input, 2);
+    // A blank first line doesn't end the section, but leaves no stacks to parse
+    let stack_lines: Vec<LineParser> = Lines::new(sections[0]).take_while(|line| !line.is_done()).collect();
+    let n_stack_lines = stack_lines.len();
+    let Some(moves_section) = sections.get(1) else {
+        return Err(ParseError::new(n_stack_lines + 1, 1, "", "crates, stack numbers or the blank line before the moves"))
+    };
+    let stacks = parse_stacks(stack_lines)?;
+    // How many crates each stack has after the moves so far, as no move can take more than that
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    // After the stacks and the blank line
+    let moves = Lines::after(n_stack_lines + 1, moves_section)
+        .map(|mut line| {
+            let mv = parse_move(&mut line, &mut heights)?;
+            line.end()?;
+            Ok(mv)
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((stacks, moves))
+}
+
+#[allow(clippy::needless_range_loop)]
+fn parse_stacks(mut lines: Vec<LineParser>) -> Result<Vec<Vec<char>>, ParseError> {
+    let mut stacks: Vec<Vec<char>> = vec!();
+    let index_line = lines.pop().unwrap_or_else(|| LineParser::new(1, ""));
+    let n_stacks = (index_line.rest().len() + 1) / 4;
+    if n_stacks == 0 {
+        return Err(index_line.error("stack numbers"))
+    }
+    stacks.resize(n_stacks, vec!());
+    for line in lines.into_iter().rev() {
+        for stack_ind in 0..n_stacks {
+            let cr = line.rest().chars().nth( 1 + stack_ind*4).unwrap_or(' ');
+            if cr.is_ascii_uppercase() {
+                stacks[stack_ind].push(cr)
+            } else if cr != ' ' {
+                return Err(ParseError::new(line.line(), 2 + stack_ind*4, &cr.to_string(), "a crate (A-Z)"))
+            }
+        }
+    }
+    Ok(stacks)
+}
+
+fn parse_move(line: &mut LineParser, heights: &mut [usize]) -> Result<(usize, usize, usize), ParseError> {
+    line.literal("move ")?;
+    let n = line.number::<usize>()?;
+    line.literal(" from ")?;
+    let from = parse_stack_number(line, heights.len())?;
+    if heights[from - 1] < n {
+        return Err(line.error_at(&from.to_string(), &format!("a stack with at least {n} crates")))
+    }
+    line.literal(" to ")?;
+    let to = parse_stack_number(line, heights.len())?;
+    heights[from - 1] -= n;
+    heights[to - 1] += n;
+    Ok((n, from, to))
+}
+
+fn parse_stack_number(line: &mut LineParser, n_stacks: usize) -> Result<usize, ParseError> {
+    let stack = line.number::<usize>()?;
+    if !(1..=n_stacks).contains(&stack) {
+        return Err(line.error_at(&stack.to_string(), &format!("a stack number from 1 to {n_stacks}")))
+    }
+    Ok(stack)
+}
+
+// The crates on top of the stacks, `None` if a stack ends up empty
+fn rearrange(mut stacks: Vec<Vec<char>>, moves: &[(usize, usize, usize)], fancy_crane: bool) -> Option<String> {
+    for &mv in moves {
+        stacks = make_move(stacks, mv, fancy_crane);
+    }
+    stacks.iter().map(|s| s.last()).collect()
+}
+
+fn make_move(mut stacks: Vec<Vec<char>>, mv: (usize, usize, usize), fancy_crane: bool) -> Vec<Vec<char>> {
+    if fancy_crane {
+        stacks = move_all_items(stacks, mv)
+    } else {
+        for _ in 0..mv.0 {
+            stacks = move_one_item(stacks, mv)
+        }    
+    }
+    stacks
+}
+
+fn move_one_item(mut stacks: Vec<Vec<char>>, mv: (usize, usize, usize)) -> Vec<Vec<char>> {
+    let cr = stacks[mv.1 - 1].pop().unwrap();
+    stacks[mv.2 - 1].push(cr);
+    stacks
+}
+
+fn move_all_items(mut stacks: Vec<Vec<char>>, mv: (usize, usize, usize)) -> Vec<Vec<char>> {
+    let split_ind = stacks[mv.1 - 1].len() - mv.0;
+    let (r, m) = stacks[mv.1 - 1].split_at(split_ind);
+    let mut m: Vec<char> = m.to_vec();
+    stacks[mv.1 - 1] = r.to_vec();
+    stacks[mv.2 - 1].append(&mut m);
+    stacks
+}
+
+/// Three to nine stacks and `size` moves, plus a few at the end if some stacks have been emptied,
+/// so that there is a crate on top of each of them. No move takes more crates than there are.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let n_stacks = rng.gen_range(3..=9);
+    let mut heights: Vec<usize> = (0..n_stacks).map(|_| rng.gen_range(1..=8)).collect();
+    let mut input = String::new();
+    for level in (0..*heights.iter().max().unwrap()).rev() {
+        let crates: Vec<String> = heights
+            .iter()
+            .map(|height| if level < *height { format!("[{}]", rng.gen_range('A'..='Z')) } else { String::from("   ") })
+            .collect();
+        input += &(crates.join(" ") + "\n")
+    }
+    let numbers: Vec<String> = (1..=n_stacks).map(|number| format!(" {number} ")).collect();
+    input += &(numbers.join(" ") + "\n\n");
+    let mut moves = vec![];
+    while moves.len() < size {
+        let (from, to) = (rng.gen_range(0..n_stacks), rng.gen_range(0..n_stacks));
+        if from != to && heights[from] > 0 {
+            let n_crates = rng.gen_range(1..=heights[from]);
+            heights[from] -= n_crates;
+            heights[to] += n_crates;
+            moves.push((n_crates, from, to))
+        }
+    }
+    while let Some(to) = heights.iter().position(|height| *height == 0) {
+        let from = heights.iter().position(|height| *height > 1).unwrap();
+        heights[from] -= 1;
+        heights[to] += 1;
+        moves.push((1, from, to))
+    }
+    for (n_crates, from, to) in moves {
+        input += &format!("move {n_crates} from {} to {}\n", from + 1, to + 1)
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_part_1() {
+        let (stacks, moves) = parse_input(include_str!("../test.txt")).unwrap();
+        assert_eq!(rearrange(stacks, &moves, false), Some(String::from("CMZ")))
+    }
+
+    #[test]
+    fn test_part_2() {
+        let (stacks, moves) = parse_input(include_str!("../test.txt")).unwrap();
+        assert_eq!(rearrange(stacks, &moves, true), Some(String::from("MCD")))
+    }
+
+    #[test]
+    fn test_too_many_crates() {
+        let err = parse_input("[A]    \n 1   2 \n\nmove 1 from 1 to 2\nmove 2 from 2 to 1\n").err().unwrap();
+        assert_eq!(err, ParseError::new(5, 13, "2", "a stack with at least 2 crates"));
+        let (stacks, moves) = parse_input("[A]    \n 1   2 \n\nmove 1 from 1 to 2\n").unwrap();
+        assert_eq!(rearrange(stacks, &moves, false), None)
+    }
+
+    // Up to 9 stacks of up to 8 crates, and moves that never take more crates than there are
+    #[allow(clippy::type_complexity)]
+    fn stacks_and_moves() -> impl Strategy<Value = (Vec<Vec<char>>, Vec<(usize, usize, usize)>)> {
+        let stacks = prop::collection::vec(prop::collection::vec(prop::char::range('A', 'Z'), 0..8), 1..10);
+        let picks = prop::collection::vec((any::<usize>(), any::<usize>(), any::<usize>()), 0..30);
+        (stacks, picks).prop_map(|(stacks, picks)| {
+            let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+            let moves = picks
+                .into_iter()
+                .map(|(n, from, to)| {
+                    let (from, to) = (from % heights.len(), to % heights.len());
+                    let n = n % (heights[from] + 1);
+                    heights[from] -= n;
+                    heights[to] += n;
+                    (n, from + 1, to + 1)
+                })
+                .collect();
+            (stacks, moves)
+        })
+    }
+
+    // The drawing of the stacks and the move list, as in the puzzle input
+    fn input_text(stacks: &[Vec<char>], moves: &[(usize, usize, usize)]) -> String {
+        let height = stacks.iter().map(Vec::len).max().unwrap();
+        let mut text = String::new();
+        for level in (0..height).rev() {
+            let row: Vec<String> = stacks
+                .iter()
+                .map(|stack| stack.get(level).map_or_else(|| String::from("   "), |cr| format!("[{cr}]")))
+                .collect();
+            text += &format!("{}\n", row.join(" "));
+        }
+        let numbers: Vec<String> = (1..=stacks.len()).map(|number| format!(" {number} ")).collect();
+        text += &format!("{}\n\n", numbers.join(" "));
+        for (n, from, to) in moves {
+            text += &format!("move {n} from {from} to {to}\n");
+        }
+        text
+    }
+
+    // The old crane moves one crate at a time, the new one picks up all of them and keeps their order
+    fn reference_rearrange(mut stacks: Vec<Vec<char>>, moves: &[(usize, usize, usize)], fancy_crane: bool) -> Vec<Vec<char>> {
+        for &(n, from, to) in moves {
+            if fancy_crane {
+                let mut held: Vec<char> = (0..n).map(|_| stacks[from - 1].pop().unwrap()).collect();
+                held.reverse();
+                stacks[to - 1].extend(held);
+            } else {
+                for _ in 0..n {
+                    let cr = stacks[from - 1].pop().unwrap();
+                    stacks[to - 1].push(cr);
+                }
+            }
+        }
+        stacks
+    }
+
+    proptest! {
+        #[test]
+        fn parse_round_trip((stacks, moves) in stacks_and_moves()) {
+            prop_assert_eq!(parse_input(&input_text(&stacks, &moves)).unwrap(), (stacks, moves));
+        }
+
+        #[test]
+        fn rearrange_matches_reference((stacks, moves) in stacks_and_moves(), fancy_crane in any::<bool>()) {
+            let rearranged = moves.iter().fold(stacks.clone(), |stacks, &mv| make_move(stacks, mv, fancy_crane));
+            let sorted_crates = |stacks: &[Vec<char>]| {
+                let mut crates: Vec<char> = stacks.concat();
+                crates.sort();
+                crates
+            };
+            prop_assert_eq!(sorted_crates(&rearranged), sorted_crates(&stacks));
+            prop_assert_eq!(&rearranged, &reference_rearrange(stacks.clone(), &moves, fancy_crane));
+            let tops: Option<String> = rearranged.iter().map(|stack| stack.last()).collect();
+            prop_assert_eq!(rearrange(stacks, &moves, fancy_crane), tops);
+        }
+    }
+}
\ No newline at end of file
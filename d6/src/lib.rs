@@ -0,0 +1,121 @@
+use aoc_common::generate::{Rng, SliceRandom, StdRng};
+use aoc_common::parse::Lines;
+use aoc_common::{Answer, Day, ParseError, Solution};
+use std::collections::HashSet;
+
+pub struct Day6;
+
+pub const DAY: Day = Day::new::<Day6>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day6 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut line = Lines::new(input).expect_line("the datastream")?;
+        let datastream = line.take_until(|c| !c.is_ascii_lowercase(), "the datastream (a-z)")?;
+        line.end()?;
+        Ok(String::from(datastream))
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        start_position(input, 4).map_or(Answer::Missing, Answer::from)
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        start_position(input, 14).map_or(Answer::Missing, Answer::from)
+    }
+}
+
+// `None` if there is no marker in the datastream
+fn start_position(s: &str, n: usize) -> Option<usize> {
+    let mut n_received = n;
+    while n_received <= s.len() {
+        let test_str = &s[(n_received-n)..n_received];
+        let test_set: HashSet<_> = test_str.chars().collect();
+        if test_set.len() == n {
+            return Some(n_received)
+        }
+        n_received += 1;
+    }
+    None
+}
+
+/// A datastream of `size` characters, but at least 14, with a start-of-message marker somewhere. The
+/// rest only uses a few letters, so that the marker isn't just anywhere.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let length = size.max(14);
+    let mut datastream: Vec<char> = (0..length).map(|_| rng.gen_range('a'..='f')).collect();
+    let mut marker: Vec<char> = ('a'..='z').collect();
+    marker.shuffle(rng);
+    let start = rng.gen_range(0..=length - 14);
+    datastream[start..start + 14].copy_from_slice(&marker[..14]);
+    datastream.into_iter().collect::<String>() + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Compares every pair in every window
+    fn reference_start_position(datastream: &[u8], n: usize) -> Option<usize> {
+        (n..=datastream.len()).find(|&end| {
+            let window = &datastream[end - n..end];
+            (0..n).all(|i| (i + 1..n).all(|j| window[i] != window[j]))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn start_position_matches_reference(datastream in "[a-p]{0,200}", n in prop::sample::select(vec![4usize, 14])) {
+            prop_assert_eq!(start_position(&datastream, n), reference_start_position(datastream.as_bytes(), n));
+        }
+    }
+
+    #[test]
+    fn test_1_part_1() {
+        assert_eq!(start_position(include_str!("../test1.txt"), 4), Some(7))
+    }
+    #[test]
+    fn test_2_part_1() {
+        assert_eq!(start_position(include_str!("../test2.txt"), 4), Some(5))
+    }
+    #[test]
+    fn test_3_part_1() {
+        assert_eq!(start_position(include_str!("../test3.txt"), 4), Some(6))
+    }
+    #[test]
+    fn test_4_part_1() {
+        assert_eq!(start_position(include_str!("../test4.txt"), 4), Some(10))
+    }
+    #[test]
+    fn test_5_part_1() {
+        assert_eq!(start_position(include_str!("../test5.txt"), 4), Some(11))
+    }
+
+    #[test]
+    fn test_no_marker() {
+        assert_eq!(start_position("abcabcabc", 4), None)
+    }
+
+    #[test]
+    fn test_21_part_2() {
+        assert_eq!(start_position(include_str!("../test21.txt"), 14), Some(19))
+    }
+    #[test]
+    fn test_22_part_2() {
+        assert_eq!(start_position(include_str!("../test22.txt"), 14), Some(23))
+    }
+    #[test]
+    fn test_23_part_2() {
+        assert_eq!(start_position(include_str!("../test23.txt"), 14), Some(23))
+    }
+    #[test]
+    fn test_24_part_2() {
+        assert_eq!(start_position(include_str!("../test24.txt"), 14), Some(29))
+    }
+    #[test]
+    fn test_25_part_2() {
+        assert_eq!(start_position(include_str!("../test25.txt"), 14), Some(26))
+    }
+}
\ No newline at end of file
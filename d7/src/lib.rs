@@ -0,0 +1,224 @@
+use aoc_common::generate::{self, Rng, StdRng};
+use aoc_common::parse::Lines;
+use aoc_common::{Answer, Day, ParseError, Solution};
+use std::iter::Peekable;
+
+pub struct Day7;
+
+pub const DAY: Day = Day::new::<Day7>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day7 {
+    type Input = Directory;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_file_system(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        part_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        part_2(input).into()
+    }
+}
+
+fn part_1(root: &Directory) -> usize {
+    root.size_recursive()
+        .iter()
+        .filter(|s| **s <= 100_000)
+        .sum::<usize>()
+}
+
+fn part_2(root: &Directory) -> usize {
+    const FS_SIZE: usize = 70_000_000;
+    const UPDATE_SIZE: usize = 30_000_000;
+    let required_freeup = UPDATE_SIZE - (FS_SIZE - root.size());
+    root.size_recursive().iter().fold(usize::MAX, |acc, s| {
+        if *s >= required_freeup && *s < acc {
+            *s
+        } else {
+            acc
+        }
+    })
+}
+
+fn parse_file_system(input: &str) -> Result<Directory, ParseError> {
+    let mut root = Directory::new("/");
+    let mut lines = Lines::new(input).peekable();
+    // We start out in the root anyway
+    lines.next_if(|line| line.rest() == "$ cd /");
+    parse_directory(&mut root, &mut lines)?;
+    Ok(root)
+}
+
+fn parse_directory(directory: &mut Directory, lines: &mut Peekable<Lines>) -> Result<(), ParseError> {
+    while let Some(mut line) = lines.next() {
+        if line.skip("$ cd ") {
+            let dir_name = line.word("a directory name")?;
+            line.end()?;
+            if dir_name != ".." {
+                let sub_dir = directory
+                    .directories
+                    .iter_mut()
+                    .find(|d| d.name == dir_name)
+                    .ok_or_else(|| line.error_at(dir_name, "a directory listed by ls"))?;
+                parse_directory(sub_dir, lines)?
+            } else {
+                break;
+            }
+        } else if line.skip("$ ls") {
+            line.end()?;
+            while let Some(mut line) = lines.next_if(|line| !line.rest().starts_with('$')) {
+                if line.skip("dir ") {
+                    directory.directories.push(Directory::new(line.word("a directory name")?))
+                } else {
+                    let size = line.number::<usize>()?;
+                    line.literal(" ")?;
+                    let _name = String::from(line.word("a file name")?);
+                    directory.files.push(File { _name, size })
+                }
+                line.end()?
+            }
+        } else {
+            return Err(line.error("$ cd or $ ls"))
+        }
+    }
+    Ok(())
+}
+
+struct File {
+    _name: String,
+    size: usize,
+}
+
+pub struct Directory {
+    name: String,
+    files: Vec<File>,
+    directories: Vec<Directory>,
+}
+
+impl Directory {
+    fn new(name: &str) -> Self {
+        Directory {
+            name: String::from(name),
+            files: vec![],
+            directories: vec![],
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.files.iter().map(|f| f.size).sum::<usize>()
+            + self.directories.iter().map(|d| d.size()).sum::<usize>()
+    }
+
+    fn size_recursive(&self) -> Vec<usize> {
+        let mut sizes = self.directories.iter().fold(vec![], |mut acc, d| {
+            acc.append(&mut d.size_recursive());
+            acc
+        });
+        sizes.push(self.size());
+        sizes
+    }
+}
+
+/// A terminal session looking through `size` directories with a few files in each. The files add up
+/// to more than the 40 000 000 that must be in use for part 2.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let n_directories = size.max(1);
+    // Of each directory but the root
+    let parents: Vec<usize> = (1..n_directories).map(|ind| rng.gen_range(0..ind)).collect();
+    let mut file_sizes: Vec<Vec<usize>> = (0..n_directories)
+        .map(|_| {
+            let n_files = rng.gen_range(0..=4);
+            (0..n_files).map(|_| rng.gen_range(1_000..=150_000)).collect()
+        })
+        .collect();
+    let total_size: usize = file_sizes.iter().flatten().sum();
+    if total_size <= 40_000_000 {
+        file_sizes[0].push(40_000_001 - total_size + rng.gen_range(0..1_000_000))
+    }
+    let mut transcript = String::from("$ cd /\n");
+    generate_listing(rng, 0, &parents, &file_sizes, &mut transcript);
+    transcript
+}
+
+// Lists the directory and then goes into each of its subdirectories and back out
+fn generate_listing(rng: &mut StdRng, directory: usize, parents: &[usize], file_sizes: &[Vec<usize>], transcript: &mut String) {
+    let subdirectories: Vec<usize> = (1..file_sizes.len()).filter(|ind| parents[ind - 1] == directory).collect();
+    let names = generate::names(rng, subdirectories.len(), 4, generate::LOWERCASE, &[]);
+    *transcript += "$ ls\n";
+    for name in &names {
+        *transcript += &format!("dir {name}\n")
+    }
+    let file_names = generate::names(rng, file_sizes[directory].len(), 5, generate::LOWERCASE, &[]);
+    for (size, name) in file_sizes[directory].iter().zip(file_names) {
+        *transcript += &format!("{size} {name}.{}\n", ["txt", "dat", "log"][rng.gen_range(0..3)])
+    }
+    for (subdirectory, name) in subdirectories.into_iter().zip(names) {
+        *transcript += &format!("$ cd {name}\n");
+        generate_listing(rng, subdirectory, parents, file_sizes, transcript);
+        *transcript += "$ cd ..\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashMap;
+
+    // The size of every directory, from adding each file to all the directories on the way to it
+    fn reference_sizes(transcript: &str) -> Vec<usize> {
+        let mut path: Vec<&str> = vec![];
+        let mut sizes: HashMap<Vec<&str>, usize> = HashMap::from([(vec![], 0)]);
+        for line in transcript.lines() {
+            match line.split(' ').collect::<Vec<_>>()[..] {
+                ["$", "cd", "/"] => path.clear(),
+                ["$", "cd", ".."] => {
+                    path.pop();
+                }
+                ["$", "cd", name] => path.push(name),
+                ["$", "ls"] => {}
+                ["dir", name] => {
+                    sizes.insert([&path[..], &[name]].concat(), 0);
+                }
+                [size, _] => {
+                    for depth in 0..=path.len() {
+                        *sizes.get_mut(&path[..depth]).unwrap() += size.parse::<usize>().unwrap()
+                    }
+                }
+                _ => panic!("{line}"),
+            }
+        }
+        sizes.into_values().collect()
+    }
+
+    proptest! {
+        #[test]
+        fn sizes_match_reference(seed in any::<u64>(), size in 1..40usize) {
+            let transcript = generate(&mut generate::rng(seed), size);
+            let root = parse_file_system(&transcript).unwrap();
+            let mut sizes = root.size_recursive();
+            let mut reference = reference_sizes(&transcript);
+            sizes.sort();
+            reference.sort();
+            prop_assert_eq!(part_1(&root), reference.iter().filter(|size| **size <= 100_000).sum::<usize>());
+            let needed = reference.last().unwrap() - 40_000_000;
+            prop_assert_eq!(part_2(&root), *reference.iter().find(|size| **size >= needed).unwrap());
+            prop_assert_eq!(sizes, reference);
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let root = parse_file_system(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_1(&root), 95437)
+    }
+
+    #[test]
+    fn test_part_2() {
+        let root = parse_file_system(include_str!("../test.txt")).unwrap();
+        assert_eq!(part_2(&root), 24933642)
+    }
+}
@@ -0,0 +1,141 @@
+use aoc_common::generate::{self, Rng, StdRng};
+use aoc_common::{Answer, Day, Grid, ParseError, Solution};
+use itertools::iproduct;
+
+pub struct Day8;
+
+pub const DAY: Day = Day::new::<Day8>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_generator(generate);
+
+impl Solution for Day8 {
+    type Input = Grid<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        load_trees(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        count_visible(input).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        highest_scenic_score(input).into()
+    }
+}
+
+fn load_trees(input: &str) -> Result<Grid<usize>, ParseError> {
+    Grid::parse(input, "a digit", |c| c.to_digit(10).map(|d| d as usize))
+}
+
+fn count_visible(trees: &Grid<usize>) -> usize {
+    // Columns as rows, so that both are slices
+    let tree_cols = trees.transpose();
+    trees
+        .positions()
+        .filter(|(ir, ic)| is_visible(trees, &tree_cols, *ir, *ic))
+        .count()
+}
+
+fn highest_scenic_score(trees: &Grid<usize>) -> usize {
+    let tree_cols = trees.transpose();
+    iproduct!(0..trees.n_rows(), 0..trees.n_cols())
+        .map(|(ir, ic)| scenic_score(trees, &tree_cols, ir, ic))
+        .max()
+        .unwrap()
+}
+
+fn in_outer_line_of_sight(trees: &[usize], ind: usize) -> bool {
+    if ind == 0 || ind == trees.len() - 1 {
+        true
+    } else {
+        *trees[0..ind].iter().max().unwrap() < trees[ind]
+            || *trees[ind + 1..].iter().max().unwrap() < trees[ind]
+    }
+}
+
+fn is_visible(rows: &Grid<usize>, cols: &Grid<usize>, row: usize, col: usize) -> bool {
+    in_outer_line_of_sight(rows.row(row), col) || in_outer_line_of_sight(cols.row(col), row)
+}
+
+fn scenic_score(rows: &Grid<usize>, cols: &Grid<usize>, row: usize, col: usize) -> usize {
+    line_of_sight_score(rows.row(row), col) * line_of_sight_score(cols.row(col), row)
+}
+
+fn line_of_sight_score(trees: &[usize], ind: usize) -> usize {
+    let upper_length = trees.len() - ind - 1;
+    let lower_length = ind;
+    let hi_score = if let Some(pos) = trees[ind + 1..].iter().position(|t| t >= &trees[ind]) {
+        pos + 1
+    } else {
+        upper_length
+    };
+    let lo_score = if let Some(pos) = trees[0..ind].iter().rev().position(|t| t >= &trees[ind]) {
+        pos + 1
+    } else {
+        lower_length
+    };
+    hi_score * lo_score
+}
+
+/// A square forest of `size` by `size` trees
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    generate::grid(rng, size.max(1), size.max(1), |rng, _| char::from_digit(rng.gen_range(0..10), 10).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Rows of tree heights, each as long as the first
+    fn forest() -> impl Strategy<Value = Vec<Vec<usize>>> {
+        (1..8usize, 1..8usize).prop_flat_map(|(n_rows, n_cols)| prop::collection::vec(prop::collection::vec(0..10usize, n_cols), n_rows))
+    }
+
+    // The trees seen looking from (row, col) in each direction, up to the edge or the first tree as tall
+    fn reference_views(forest: &[Vec<usize>], row: usize, col: usize) -> Vec<(usize, bool)> {
+        let height = forest[row][col];
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .iter()
+            .map(|(d_row, d_col)| {
+                let (mut r, mut c, mut seen) = (row as isize, col as isize, 0);
+                loop {
+                    (r, c) = (r + d_row, c + d_col);
+                    let Some(tree) = forest.get(r as usize).and_then(|trees| trees.get(c as usize)) else {
+                        return (seen, true)
+                    };
+                    seen += 1;
+                    if *tree >= height {
+                        return (seen, false)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn parts_match_walking(forest in forest()) {
+            let text: String = forest.iter().map(|trees| trees.iter().map(|tree| tree.to_string()).collect::<String>() + "\n").collect();
+            let trees = load_trees(&text).unwrap();
+            let views: Vec<Vec<(usize, bool)>> = iproduct!(0..forest.len(), 0..forest[0].len())
+                .map(|(row, col)| reference_views(&forest, row, col))
+                .collect();
+            // Visible from outside if some view reaches the edge
+            prop_assert_eq!(count_visible(&trees), views.iter().filter(|views| views.iter().any(|(_, edge)| *edge)).count());
+            let scenic_score = views.iter().map(|views| views.iter().map(|(seen, _)| seen).product::<usize>()).max().unwrap();
+            prop_assert_eq!(highest_scenic_score(&trees), scenic_score);
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let trees = load_trees(include_str!("../test.txt")).unwrap();
+        assert_eq!(count_visible(&trees), 21)
+    }
+
+    #[test]
+    fn test_part_2() {
+        let trees = load_trees(include_str!("../test.txt")).unwrap();
+        assert_eq!(highest_scenic_score(&trees), 8)
+    }
+}
@@ -0,0 +1,188 @@
+use aoc_common::frames::{Frame, Palette, Recorder, Visualize};
+use aoc_common::generate::{self, Rng, SliceRandom, StdRng};
+use aoc_common::parse::{parse_lines, LineParser};
+use aoc_common::sparse_grid::Legend;
+use aoc_common::{Answer, Day, ParseError, Part, Point2, Solution, SparseGrid, Vec2};
+
+pub struct Day9;
+
+pub const DAY: Day =
+    Day::new::<Day9>(env!("CARGO_PKG_NAME"), env!("CARGO_MANIFEST_DIR")).with_frames::<Day9>().with_generator(generate);
+
+impl Solution for Day9 {
+    type Input = Vec<Move>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_moves(input)
+    }
+
+    fn part_1(input: &Self::Input) -> Answer {
+        count_tail_positions(input, 2, &mut Recorder::off()).into()
+    }
+
+    fn part_2(input: &Self::Input) -> Answer {
+        count_tail_positions(input, 10, &mut Recorder::off()).into()
+    }
+}
+
+// A frame for every step of the head
+impl Visualize for Day9 {
+    fn simulate(input: &Self::Input, part: Part, recorder: &mut Recorder) {
+        let n_knots = if part == Part::One { 2 } else { 10 };
+        count_tail_positions(input, n_knots, recorder);
+    }
+
+    fn palette() -> Palette {
+        let palette = Palette::new([0x0f, 0x0f, 0x23]).with('#', [0x00, 0x66, 0x00]).with('s', [0xff, 0xff, 0x66]);
+        "T123456789".chars().fold(palette, |palette, knot| palette.with(knot, [0xcc, 0xcc, 0xcc])).with('H', [0xff, 0x33, 0x33])
+    }
+}
+
+pub struct Move {
+    n_steps: usize,
+    delta: Vec2
+}
+
+impl Move {
+    fn parse(line: &mut LineParser) -> Result<Self, ParseError> {
+        let directions = [("L", Vec2::new(-1, 0)), ("R", Vec2::new(1, 0)), ("D", Vec2::new(0, -1)), ("U", Vec2::new(0, 1))];
+        let delta = line.one_of(&directions, "L, R, D or U")?;
+        line.literal(" ")?;
+        let n_steps = line.number::<usize>()?;
+        Ok(Move {n_steps, delta})
+    }
+}
+
+fn parse_moves(input: &str) -> Result<Vec<Move>, ParseError> {
+    parse_lines(input, Move::parse)
+}
+
+fn count_tail_positions(moves: &[Move], n_knots: usize, recorder: &mut Recorder) -> usize {
+    let mut tail_visits: SparseGrid<()> = SparseGrid::new();
+    let mut knots: Vec<Point2> = vec![];
+    knots.resize(n_knots, Point2::new(0, 0));
+    tail_visits.insert(knots[n_knots - 1].into(), ());
+    recorder.record(|| draw(&tail_visits, &knots));
+    for mv in moves {
+        for _ in 0..(mv.n_steps) {
+            knots[0] += mv.delta;
+            for ind in 1..n_knots {
+                knots[ind] = update_tail_pos(knots[ind], knots[ind-1])
+            }
+            tail_visits.insert(knots[n_knots - 1].into(), ());
+            recorder.record(|| draw(&tail_visits, &knots));
+        }
+    }
+    recorder.finish(|| draw(&tail_visits, &knots));
+    tail_visits.len()
+}
+
+// The rope on top of where its tail has been, which is kept as (x, y) with y up, drawn as
+// (row, col) with the head on top of the other knots
+fn draw(tail_visits: &SparseGrid<()>, knots: &[Point2]) -> Frame {
+    let cell = |(x, y): (isize, isize)| (-y, x);
+    let symbols: Vec<char> = if knots.len() == 2 { vec!['H', 'T'] } else { "H123456789".chars().collect() };
+    let mut picture: SparseGrid<char> = tail_visits.positions().map(|pos| (cell(pos), '#')).collect();
+    picture.insert((0, 0), 's');
+    for (knot, symbol) in knots.iter().zip(&symbols).rev() {
+        picture.insert(cell((*knot).into()), *symbol);
+    }
+    let legend = symbols
+        .iter()
+        .fold(Legend::new('.', "nothing").with('#', '#', "visited by the tail").with('s', 's', "start"), |legend, symbol| {
+            legend.with(*symbol, *symbol, "knot")
+        });
+    Frame::from_sparse(&picture, &legend)
+}
+
+fn update_tail_pos(tail_pos: Point2, head_pos: Point2) -> Point2 {
+    // Touching, diagonally or not, is close enough
+    if tail_pos.chebyshev(head_pos) > 1 {
+        tail_pos.step_towards(head_pos)
+    } else {
+        tail_pos
+    }
+}
+
+/// `size` moves of the head
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    generate::lines(rng, size, |rng, _| format!("{} {}", ["L", "R", "D", "U"].choose(rng).unwrap(), rng.gen_range(1..=20)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    fn moves_text() -> impl Strategy<Value = String> {
+        let mv = (prop::sample::select(vec!['L', 'R', 'D', 'U']), 0..20usize);
+        prop::collection::vec(mv, 0..60)
+            .prop_map(|moves| moves.into_iter().map(|(direction, n_steps)| format!("{direction} {n_steps}\n")).collect())
+    }
+
+    // Every knot steps towards the one ahead of it whenever they stop touching, diagonals included
+    fn reference_tail_positions(text: &str, n_knots: usize) -> usize {
+        let mut knots = vec![(0isize, 0isize); n_knots];
+        let mut tail_visits = HashSet::from([(0, 0)]);
+        for line in text.lines() {
+            let (direction, n_steps) = line.split_once(' ').unwrap();
+            let (dx, dy) = match direction {
+                "L" => (-1, 0),
+                "R" => (1, 0),
+                "D" => (0, -1),
+                _ => (0, 1),
+            };
+            for _ in 0..n_steps.parse().unwrap() {
+                knots[0] = (knots[0].0 + dx, knots[0].1 + dy);
+                for ind in 1..n_knots {
+                    let (head, tail) = (knots[ind - 1], knots[ind]);
+                    if (head.0 - tail.0).abs().max((head.1 - tail.1).abs()) > 1 {
+                        knots[ind] = (tail.0 + (head.0 - tail.0).signum(), tail.1 + (head.1 - tail.1).signum());
+                    }
+                    assert!((knots[ind - 1].0 - knots[ind].0).abs() <= 1 && (knots[ind - 1].1 - knots[ind].1).abs() <= 1);
+                }
+                tail_visits.insert(knots[n_knots - 1]);
+            }
+        }
+        tail_visits.len()
+    }
+
+    proptest! {
+        #[test]
+        fn tail_positions_match_reference(text in moves_text(), n_knots in 1..12usize) {
+            let moves = parse_moves(&text).unwrap();
+            prop_assert_eq!(count_tail_positions(&moves, n_knots, &mut Recorder::off()), reference_tail_positions(&text, n_knots));
+        }
+
+        #[test]
+        fn longer_ropes_visit_fewer_positions(text in moves_text()) {
+            let moves = parse_moves(&text).unwrap();
+            let n_steps: usize = moves.iter().map(|mv| mv.n_steps).sum();
+            let head_positions = count_tail_positions(&moves, 1, &mut Recorder::off());
+            prop_assert!(head_positions <= n_steps + 1);
+            prop_assert!(count_tail_positions(&moves, 2, &mut Recorder::off()) <= head_positions);
+            prop_assert!(count_tail_positions(&moves, 10, &mut Recorder::off()) <= count_tail_positions(&moves, 2, &mut Recorder::off()));
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let moves = parse_moves(include_str!("../test1.txt")).unwrap();
+        assert_eq!(count_tail_positions(&moves, 2, &mut Recorder::off()), 13)
+    }
+
+    #[test]
+    fn test_part_2() {
+        let moves = parse_moves(include_str!("../test2.txt")).unwrap();
+        assert_eq!(count_tail_positions(&moves, 10, &mut Recorder::off()), 36)
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_moves("R 4\nU\n").err().unwrap();
+        assert_eq!(err, ParseError::new(2, 2, "", "\" \""));
+        let err = parse_moves("R 4\nX 1\n").err().unwrap();
+        assert_eq!(err, ParseError::new(2, 1, "X", "L, R, D or U"))
+    }
+}
\ No newline at end of file